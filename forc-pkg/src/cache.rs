@@ -0,0 +1,346 @@
+//! A persistent cache of compiled packages.
+//!
+//! Each package is cached under a key produced by hashing its manifest and sources, the keys of
+//! its dependencies, the build profile and the compiler version. As a result, a cached package is
+//! only reused in the case that neither the package nor any of its dependencies have changed.
+//!
+//! The namespace produced by type-checking a library refers to the compiler's in-memory type and
+//! declaration engines and cannot be cached. As a result, a cached library is still type-checked in
+//! the case that any package depending on it must be compiled, though its cached output is reused
+//! rather than generating its IR, ASM and bytecode again.
+//!
+//! Only the [MAX_ENTRIES_PER_PACKAGE] most recently used entries are retained for each package,
+//! with older entries evicted as new ones are stored.
+
+use crate::{manifest::PackageManifestFile, BuildProfile};
+use anyhow::{Context, Result};
+use forc_util::user_forc_directory;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use sway_core::{fuel_prelude::fuel_tx::StorageSlot, source_map::SourceMap};
+use sway_utils::constants::{MANIFEST_FILE_NAME, SRC_DIR};
+
+/// The version of the compiler, included within every key.
+const COMPILER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The maximum number of entries retained within the cache for each package name.
+pub const MAX_ENTRIES_PER_PACKAGE: usize = 8;
+
+/// The persistent cache of compiled packages for a single build profile.
+pub(crate) struct BuildCache {
+    dir: PathBuf,
+    /// Identifies the build profile and compiler, hashed into the key of every package.
+    context: String,
+}
+
+/// The compiled output of a package that may be reused by later builds.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct CachedPackage {
+    pub(crate) json_abi_program: fuels_types::ProgramABI,
+    pub(crate) storage_slots: Vec<StorageSlot>,
+    pub(crate) bytecode: Vec<u8>,
+    pub(crate) entries: Vec<CachedEntry>,
+    pub(crate) source_map: SourceMap,
+    /// The number of warnings produced while compiling the package.
+    pub(crate) warnings: usize,
+}
+
+/// An entry point of a cached package. Packages are only cached when built without tests.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct CachedEntry {
+    pub(crate) fn_name: String,
+    pub(crate) imm: u64,
+    pub(crate) selector: Option<[u8; 4]>,
+}
+
+/// A dependency of a package, as hashed into the package's key.
+pub(crate) struct CacheDep<'a> {
+    /// The name of the dependency within the dependent's manifest.
+    pub(crate) name: &'a str,
+    /// Describes the kind of dependency, including the salt of contract dependencies.
+    pub(crate) kind: String,
+    /// The key of the dependency.
+    pub(crate) key: &'a str,
+}
+
+impl BuildCache {
+    /// The build cache for packages built with the given profile.
+    ///
+    /// Returns `None` in the case that the profile requires output only produced during
+    /// compilation, e.g. tests, or printed ASTs, IR and ASM.
+    pub(crate) fn new(profile: &BuildProfile) -> Option<Self> {
+        let BuildProfile {
+            print_ast,
+            print_dca_graph,
+            print_ir,
+            print_finalized_asm,
+            print_intermediate_asm,
+            terse: _,
+            time_phases,
+            include_tests,
+        } = profile;
+        if *print_ast
+            || *print_dca_graph
+            || *print_ir
+            || *print_finalized_asm
+            || *print_intermediate_asm
+            || *time_phases
+            || *include_tests
+        {
+            return None;
+        }
+        // Terse mode only affects the compiler's output, so is excluded from the key.
+        let profile = BuildProfile {
+            terse: false,
+            ..profile.clone()
+        };
+        let context = format!(
+            "{}\n{}\n{}",
+            COMPILER_VERSION,
+            compiler_build_id(),
+            serde_json::to_string(&profile).ok()?
+        );
+        Some(Self {
+            dir: cache_directory(),
+            context,
+        })
+    }
+
+    /// Produce the key for the given package from its manifest, sources, enabled features and
+    /// dependencies.
+    pub(crate) fn key(
+        &self,
+        manifest: &PackageManifestFile,
+        features: &BTreeSet<String>,
+        deps: &[CacheDep],
+    ) -> Result<String> {
+        let mut hasher = Sha256::new();
+        hasher.update(self.context.as_bytes());
+        for feature in features {
+            hash_field(&mut hasher, feature.as_bytes());
+        }
+        for path in package_files(manifest.dir())? {
+            let bytes =
+                fs::read(&path).with_context(|| format!("failed to read {}", path.display()))?;
+            let rel_path = path.strip_prefix(manifest.dir()).unwrap_or(&path);
+            hash_field(&mut hasher, rel_path.to_string_lossy().as_bytes());
+            hash_field(&mut hasher, &bytes);
+        }
+        for dep in deps {
+            hash_field(&mut hasher, dep.name.as_bytes());
+            hash_field(&mut hasher, dep.kind.as_bytes());
+            hash_field(&mut hasher, dep.key.as_bytes());
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// Load the cached package with the given name and key, if any.
+    ///
+    /// Unreadable cache entries are treated as missing. Loading an entry marks it as the most
+    /// recently used entry of the package.
+    pub(crate) fn load(&self, name: &str, key: &str) -> Option<CachedPackage> {
+        let path = self.entry_path(name, key);
+        let string = fs::read_to_string(&path).ok()?;
+        let pkg = serde_json::from_str(&string).ok()?;
+        let _ = fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now()));
+        Some(pkg)
+    }
+
+    /// Store the given package within the cache under the given name and key.
+    pub(crate) fn store(&self, name: &str, key: &str, pkg: &CachedPackage) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("failed to create directory {}", self.dir.display()))?;
+        // Write to a temporary file first, so that concurrent builds never observe a partially
+        // written entry.
+        let path = self.entry_path(name, key);
+        let tmp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        fs::write(&tmp_path, serde_json::to_string(pkg)?)
+            .with_context(|| format!("failed to write {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, &path)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        self.evict(name);
+        Ok(())
+    }
+
+    /// Remove all but the [MAX_ENTRIES_PER_PACKAGE] most recently used entries of the given package.
+    ///
+    /// Eviction is best-effort, such that entries that cannot be removed are left in place.
+    fn evict(&self, name: &str) {
+        let read_dir = match fs::read_dir(&self.dir) {
+            Ok(read_dir) => read_dir,
+            Err(_) => return,
+        };
+        let mut entries: Vec<(SystemTime, PathBuf)> = read_dir
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let file_name = entry.file_name();
+                let key = file_name
+                    .to_str()?
+                    .strip_prefix(name)?
+                    .strip_prefix('-')?
+                    .strip_suffix(".json")?;
+                // Keys are hex-encoded SHA-256 hashes, which distinguishes the entries of this
+                // package from those of any package whose name begins with `<name>-`.
+                if key.len() != 64 || !key.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return None;
+                }
+                let modified = entry.metadata().ok()?.modified().ok()?;
+                Some((modified, entry.path()))
+            })
+            .collect();
+        entries.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+        for (_, path) in entries.into_iter().skip(MAX_ENTRIES_PER_PACKAGE) {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    fn entry_path(&self, name: &str, key: &str) -> PathBuf {
+        self.dir.join(format!("{}-{}.json", name, key))
+    }
+}
+
+/// The directory in which compiled packages are cached.
+///
+/// ```ignore
+/// $HOME/.forc/cache/build
+/// ```
+pub fn cache_directory() -> PathBuf {
+    user_forc_directory().join("cache").join("build")
+}
+
+/// Identifies the build of the running compiler, such that development builds of the same
+/// version never reuse each other's output.
+fn compiler_build_id() -> String {
+    std::env::current_exe()
+        .and_then(|exe| {
+            let metadata = fs::metadata(&exe)?;
+            let modified = metadata
+                .modified()?
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default();
+            Ok(format!(
+                "{}:{}:{}",
+                exe.display(),
+                metadata.len(),
+                modified.as_nanos()
+            ))
+        })
+        .unwrap_or_default()
+}
+
+/// The manifest and all files under the `src` directory of the package, sorted by path.
+fn package_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = vec![dir.join(MANIFEST_FILE_NAME)];
+    for entry in walkdir::WalkDir::new(dir.join(SRC_DIR)).sort_by_file_name() {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            files.push(entry.into_path());
+        }
+    }
+    Ok(files)
+}
+
+/// Hash the given field, prefixed by its length so that adjacent fields remain distinct.
+fn hash_field(hasher: &mut Sha256, bytes: &[u8]) {
+    hasher.update((bytes.len() as u64).to_le_bytes());
+    hasher.update(bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_changes_with_sources_and_deps() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        fs::create_dir_all(dir.join(SRC_DIR)).unwrap();
+        fs::write(
+            dir.join(MANIFEST_FILE_NAME),
+            "[project]\nauthors = []\nentry = \"lib.sw\"\nlicense = \"Apache-2.0\"\nname = \"foo\"\n",
+        )
+        .unwrap();
+        let lib_path = dir.join(SRC_DIR).join("lib.sw");
+        fs::write(&lib_path, "library foo;\n").unwrap();
+        let manifest = PackageManifestFile::from_dir(dir).unwrap();
+
+        let cache = BuildCache::new(&BuildProfile::default()).unwrap();
+        let no_features = BTreeSet::new();
+        let key = cache.key(&manifest, &no_features, &[]).unwrap();
+        assert_eq!(key, cache.key(&manifest, &no_features, &[]).unwrap());
+
+        let features = BTreeSet::from(["logging".to_string()]);
+        assert_ne!(key, cache.key(&manifest, &features, &[]).unwrap());
+
+        let dep = |key| CacheDep {
+            name: "bar",
+            kind: "library".to_string(),
+            key,
+        };
+        let with_dep = cache.key(&manifest, &no_features, &[dep("a")]).unwrap();
+        assert_ne!(key, with_dep);
+        assert_ne!(
+            with_dep,
+            cache.key(&manifest, &no_features, &[dep("b")]).unwrap()
+        );
+
+        fs::write(&lib_path, "library foo;\n\nfn f() {}\n").unwrap();
+        assert_ne!(key, cache.key(&manifest, &no_features, &[]).unwrap());
+
+        let tests_profile = BuildProfile {
+            include_tests: true,
+            ..BuildProfile::default()
+        };
+        assert!(BuildCache::new(&tests_profile).is_none());
+    }
+
+    #[test]
+    fn least_recently_used_entries_are_evicted() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache = BuildCache {
+            dir: tmp.path().to_path_buf(),
+            context: String::new(),
+        };
+        let pkg = CachedPackage {
+            json_abi_program: fuels_types::ProgramABI::default(),
+            storage_slots: vec![],
+            bytecode: vec![],
+            entries: vec![],
+            source_map: SourceMap::new(),
+            warnings: 0,
+        };
+        let key = |i: usize| format!("{:064x}", i);
+        let age = |i: usize| SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(i as u64);
+        for i in 0..MAX_ENTRIES_PER_PACKAGE {
+            cache.store("foo", &key(i), &pkg).unwrap();
+            let file = fs::File::options()
+                .write(true)
+                .open(cache.entry_path("foo", &key(i)))
+                .unwrap();
+            file.set_modified(age(i + 1)).unwrap();
+        }
+        cache.store("foo-bar", &key(0), &pkg).unwrap();
+
+        // Using the oldest entry makes the next oldest the least recently used.
+        assert!(cache.load("foo", &key(0)).is_some());
+        cache
+            .store("foo", &key(MAX_ENTRIES_PER_PACKAGE), &pkg)
+            .unwrap();
+        assert!(cache.load("foo", &key(0)).is_some());
+        assert!(cache.load("foo", &key(1)).is_none());
+        for i in 2..=MAX_ENTRIES_PER_PACKAGE {
+            assert!(cache.load("foo", &key(i)).is_some());
+        }
+        // Entries of other packages are unaffected.
+        assert!(cache.load("foo-bar", &key(0)).is_some());
+    }
+}
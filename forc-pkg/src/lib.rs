@@ -4,10 +4,13 @@
 //! The project should consist of one or more Sway modules under a `src` directory. It may also
 //! declare a set of forc package dependencies within its manifest.
 
+mod cache;
 pub mod lock;
 pub mod manifest;
 mod pkg;
+pub mod registry;
 
+pub use cache::cache_directory;
 pub use lock::Lock;
 pub use manifest::{
     BuildProfile, PackageManifest, PackageManifestFile, WorkspaceManifest, WorkspaceManifestFile,
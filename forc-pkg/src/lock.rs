@@ -61,7 +61,7 @@ impl PkgLock {
         let pinned = &graph[node];
         let name = pinned.name.clone();
         let version = match &pinned.source {
-            pkg::SourcePinned::Registry(reg) => Some(reg.source.version.clone()),
+            pkg::SourcePinned::Registry(reg) => Some(reg.version.clone()),
             _ => None,
         };
         let source = pinned.source.to_string();
@@ -183,6 +183,9 @@ impl Lock {
         // Add all nodes to the graph.
         // Keep track of "<name> <source>" to node-index mappings for the edge collection pass.
         let mut pkg_to_node: HashMap<String, pkg::NodeIx> = HashMap::new();
+        // Entries that pin the same package share a node. This is only the case for registry
+        // packages within lock files that predate the unification of registry packages.
+        let mut pinned_to_node: HashMap<pkg::Pinned, pkg::NodeIx> = HashMap::new();
         for pkg in &self.package {
             // Note: `key` may be either `<name> <source>` or just `<name>` if disambiguation not
             // required.
@@ -192,7 +195,9 @@ impl Lock {
                 anyhow!("invalid 'source' entry for package {} lock: {:?}", name, e)
             })?;
             let pkg = pkg::Pinned { name, source };
-            let node = graph.add_node(pkg);
+            let node = *pinned_to_node
+                .entry(pkg.clone())
+                .or_insert_with(|| graph.add_node(pkg));
             pkg_to_node.insert(key, node);
         }
 
@@ -367,11 +372,15 @@ where
     }
 }
 
-// Only includes source after the name for git sources for friendlier printing.
+// Only includes source after the name for git sources and the version for registry sources for
+// friendlier printing.
 fn name_or_git_unique_string(pkg: &PkgLock) -> Cow<str> {
-    match pkg.source.starts_with(pkg::SourceGitPinned::PREFIX) {
-        true => Cow::Owned(pkg.unique_string()),
-        false => Cow::Borrowed(&pkg.name),
+    if pkg.source.starts_with(pkg::SourceGitPinned::PREFIX) {
+        return Cow::Owned(pkg.unique_string());
+    }
+    match &pkg.version {
+        Some(version) => Cow::Owned(format!("{} v{}", pkg.name, version)),
+        None => Cow::Borrowed(&pkg.name),
     }
 }
 
@@ -379,7 +388,8 @@ fn name_or_git_unique_string(pkg: &PkgLock) -> Cow<str> {
 mod tests {
     use sway_core::fuel_prelude::fuel_tx;
 
-    use super::parse_pkg_dep_line;
+    use super::{parse_pkg_dep_line, Lock};
+    use crate::pkg;
 
     #[test]
     fn test_parse_pkg_line_with_salt_with_dep_name() {
@@ -424,4 +434,47 @@ mod tests {
         let pkg_dep_line = "std path+from-root (1)";
         parse_pkg_dep_line(pkg_dep_line).unwrap();
     }
+
+    #[test]
+    fn test_legacy_registry_sources_share_a_node() {
+        let lock: Lock = toml::de::from_str(
+            r#"
+            [[package]]
+            name = 'a'
+            source = 'registry+1.0.0'
+            dependencies = ['foo registry+~1.2#1.2.0']
+
+            [[package]]
+            name = 'app'
+            source = 'member'
+            dependencies = ['a', 'foo registry+^1.0#1.2.0']
+
+            [[package]]
+            name = 'foo'
+            version = '1.2.0'
+            source = 'registry+^1.0#1.2.0'
+
+            [[package]]
+            name = 'foo'
+            version = '1.2.0'
+            source = 'registry+~1.2#1.2.0'
+            "#,
+        )
+        .unwrap();
+        let graph = lock.to_graph().unwrap();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 3);
+        let foo = graph
+            .node_indices()
+            .find(|&n| graph[n].name == "foo")
+            .unwrap();
+        let expected = pkg::SourcePinned::Registry(pkg::SourceRegistryPinned {
+            version: semver::Version::new(1, 2, 0),
+            checksum: None,
+        });
+        assert_eq!(graph[foo].source, expected);
+        let new_lock = Lock::from_graph(&graph);
+        let foo_lock = new_lock.package.iter().find(|p| p.name == "foo").unwrap();
+        assert_eq!(foo_lock.source, "registry+1.2.0");
+    }
 }
@@ -145,6 +145,12 @@ pub struct PackageManifest {
     pub constants: Option<BTreeMap<String, ConfigTimeConstant>>,
     build_profile: Option<BTreeMap<String, BuildProfile>>,
     pub contract_dependencies: Option<BTreeMap<String, ContractDependency>>,
+    /// The features of the package that may be enabled for conditional compilation, each mapped
+    /// to the other features it enables. The features listed under `default` are enabled unless
+    /// default features are disabled.
+    pub features: Option<BTreeMap<String, Vec<String>>>,
+    /// The environment in which the package's unit tests are executed.
+    pub test: Option<TestEnvironment>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -152,6 +158,8 @@ pub struct PackageManifest {
 pub struct Project {
     pub authors: Option<Vec<String>>,
     pub name: String,
+    /// The version of the package. Required in order to package the project for a registry.
+    pub version: Option<semver::Version>,
     pub organization: Option<String>,
     pub license: String,
     #[serde(default = "default_entry")]
@@ -198,6 +206,8 @@ pub struct DependencyDetails {
     pub(crate) tag: Option<String>,
     pub(crate) package: Option<String>,
     pub(crate) rev: Option<String>,
+    pub(crate) features: Option<Vec<String>>,
+    pub(crate) default_features: Option<bool>,
 }
 
 /// Parameters to pass through to the `sway_core::BuildConfig` during compilation.
@@ -214,6 +224,98 @@ pub struct BuildProfile {
     pub include_tests: bool,
 }
 
+/// Describes the transaction within which each of the package's unit tests is executed.
+///
+/// Fields that are not specified fall back to the defaults used by `forc test`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct TestEnvironment {
+    /// The height of the block in which the tests are executed.
+    pub block_height: Option<u32>,
+    /// The TAI64 timestamp of the block in which the tests are executed.
+    pub timestamp: Option<u64>,
+    /// The maturity of the test transaction.
+    pub maturity: Option<u64>,
+    /// The script data provided to the test transaction, given as a hex string.
+    #[serde(
+        default,
+        with = "hex_bytes::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub script_data: Option<Vec<u8>>,
+    /// The inputs of the test transaction. If unspecified, a single coin input of the base asset
+    /// is provided.
+    pub inputs: Option<Vec<TestInput>>,
+    /// The outputs of the test transaction, in addition to an output for each deployed contract.
+    pub outputs: Option<Vec<TestOutput>>,
+    /// Overrides of the above fields for individual tests, keyed by the name of the test.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub overrides: BTreeMap<String, TestEnvironment>,
+}
+
+impl TestEnvironment {
+    /// The environment of the test with the given name, i.e. this environment with any of the
+    /// test's overrides applied.
+    pub fn for_test(&self, test_name: &str) -> TestEnvironment {
+        let base = TestEnvironment {
+            overrides: BTreeMap::new(),
+            ..self.clone()
+        };
+        let test_env = match self.overrides.get(test_name) {
+            None => return base,
+            Some(test_env) => test_env.clone(),
+        };
+        TestEnvironment {
+            block_height: test_env.block_height.or(base.block_height),
+            timestamp: test_env.timestamp.or(base.timestamp),
+            maturity: test_env.maturity.or(base.maturity),
+            script_data: test_env.script_data.or(base.script_data),
+            inputs: test_env.inputs.or(base.inputs),
+            outputs: test_env.outputs.or(base.outputs),
+            overrides: BTreeMap::new(),
+        }
+    }
+}
+
+/// An input provided to the test transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum TestInput {
+    /// A coin owned by the transaction's signer.
+    Coin {
+        amount: u64,
+        /// The asset of the coin. Defaults to the base asset.
+        #[serde(default, rename = "asset-id")]
+        asset_id: fuel_tx::AssetId,
+    },
+    /// A message whose recipient is the transaction's signer.
+    Message {
+        amount: u64,
+        #[serde(default)]
+        sender: fuel_tx::Address,
+        #[serde(default, with = "hex_bytes")]
+        data: Vec<u8>,
+    },
+}
+
+/// An output of the test transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum TestOutput {
+    Coin {
+        to: fuel_tx::Address,
+        amount: u64,
+        #[serde(default, rename = "asset-id")]
+        asset_id: fuel_tx::AssetId,
+    },
+    Change {
+        to: fuel_tx::Address,
+        #[serde(default, rename = "asset-id")]
+        asset_id: fuel_tx::AssetId,
+    },
+    Variable,
+}
+
 impl Dependency {
     /// The string of the `package` field if specified.
     pub fn package(&self) -> Option<&str> {
@@ -222,6 +324,22 @@ impl Dependency {
             Self::Detailed(ref det) => det.package.as_deref(),
         }
     }
+
+    /// The features of the dependency enabled by the dependent.
+    pub fn features(&self) -> &[String] {
+        match *self {
+            Self::Simple(_) => &[],
+            Self::Detailed(ref det) => det.features.as_deref().unwrap_or_default(),
+        }
+    }
+
+    /// Whether or not the default features of the dependency are enabled. Defaults to `true`.
+    pub fn default_features(&self) -> bool {
+        match *self {
+            Self::Simple(_) => true,
+            Self::Detailed(ref det) => det.default_features.unwrap_or(true),
+        }
+    }
 }
 
 impl PackageManifestFile {
@@ -396,6 +514,8 @@ impl PackageManifestFile {
 
 impl PackageManifest {
     pub const DEFAULT_ENTRY_FILE_NAME: &'static str = "main.sw";
+    /// The feature whose listed features are enabled by default.
+    pub const DEFAULT_FEATURE: &'static str = "default";
 
     /// Given a path to a `Forc.toml`, read it and construct a `PackageManifest`.
     ///
@@ -430,12 +550,62 @@ impl PackageManifest {
     /// Validate the `PackageManifest`.
     ///
     /// This checks the project and organization names against a set of reserved/restricted
-    /// keywords and patterns.
+    /// keywords and patterns, and checks that each feature only enables declared features.
     pub fn validate(&self) -> Result<()> {
         validate_name(&self.project.name, "package name")?;
         if let Some(ref org) = self.project.organization {
             validate_name(org, "organization name")?;
         }
+        self.validate_features()?;
+        self.validate_test_overrides()
+    }
+
+    /// Check that the per-test overrides of the `[test]` section do not declare overrides of
+    /// their own.
+    fn validate_test_overrides(&self) -> Result<()> {
+        let overrides = self.test.iter().flat_map(|test_env| &test_env.overrides);
+        for (test_name, test_env) in overrides {
+            if !test_env.overrides.is_empty() {
+                bail!(
+                    "the overrides of test `{}` may not declare overrides of their own",
+                    test_name
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that feature names are valid, and that each feature only enables other declared
+    /// features of this package, or features of declared dependencies via `<dependency>/<feature>`.
+    fn validate_features(&self) -> Result<()> {
+        for (name, enables) in self.features() {
+            validate_feature_name(name)?;
+            for enabled in enables {
+                match enabled.split_once('/') {
+                    Some((dep_name, dep_feature)) => {
+                        if self.dep(dep_name).is_none() && self.contract_dep(dep_name).is_none() {
+                            bail!(
+                                "feature `{}` enables `{}`, but `{}` is not a dependency",
+                                name,
+                                enabled,
+                                dep_name
+                            );
+                        }
+                        validate_feature_name(dep_feature)?;
+                    }
+                    None => {
+                        if self.feature(enabled).is_none() {
+                            bail!(
+                                "feature `{}` enables `{}`, but no such feature is declared \
+                                under `[features]`",
+                                name,
+                                enabled
+                            );
+                        }
+                    }
+                }
+            }
+        }
         Ok(())
     }
 
@@ -481,6 +651,21 @@ impl PackageManifest {
         })
     }
 
+    /// Produce an iterator yielding all declared features along with the features they enable.
+    pub fn features(&self) -> impl Iterator<Item = (&String, &Vec<String>)> {
+        self.features
+            .as_ref()
+            .into_iter()
+            .flat_map(|features| features.iter())
+    }
+
+    /// Retrieve the features enabled by the feature with the given name, if it is declared.
+    pub fn feature(&self, feature_name: &str) -> Option<&Vec<String>> {
+        self.features
+            .as_ref()
+            .and_then(|features| features.get(feature_name))
+    }
+
     /// Produce an iterator yielding all listed patches.
     pub fn patches(&self) -> impl Iterator<Item = (&String, &PatchMap)> {
         self.patch
@@ -633,6 +818,19 @@ impl Default for BuildProfile {
     }
 }
 
+/// Feature names may only contain alphanumeric characters, `_` and `-`.
+fn validate_feature_name(name: &str) -> Result<()> {
+    let valid_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-';
+    if name.is_empty() || !name.chars().all(valid_char) {
+        bail!(
+            "invalid feature name `{}`, feature names may only contain alphanumeric \
+            characters, `_` and `-`",
+            name
+        );
+    }
+    Ok(())
+}
+
 /// The definition for the implicit `std` dependency.
 fn implicit_std_dep() -> Dependency {
     // Here, we use the `forc-pkg` crate version formatted with the `v` prefix (e.g. "v1.2.3"),
@@ -679,6 +877,42 @@ fn default_url() -> String {
     constants::DEFAULT_NODE_URL.into()
 }
 
+/// (De)serialize bytes as a hex string, optionally prefixed with `0x`.
+mod hex_bytes {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let s = s.strip_prefix("0x").unwrap_or(&s);
+        hex::decode(s).map_err(|e| D::Error::custom(format!("invalid hex string: {e}")))
+    }
+
+    /// (De)serialize optional bytes as a hex string.
+    pub mod option {
+        use serde::{Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            bytes: &Option<Vec<u8>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match bytes {
+                Some(bytes) => super::serialize(bytes, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Vec<u8>>, D::Error> {
+            super::deserialize(deserializer).map(Some)
+        }
+    }
+}
+
 /// A [WorkspaceManifest] that was deserialized from a file at a particular path.
 #[derive(Clone, Debug)]
 pub struct WorkspaceManifestFile {
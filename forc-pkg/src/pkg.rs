@@ -1,8 +1,9 @@
 use crate::{
+    cache::{BuildCache, CacheDep, CachedEntry, CachedPackage},
     lock::Lock,
     manifest::{
         BuildProfile, ConfigTimeConstant, Dependency, ManifestFile, MemberManifestFiles,
-        PackageManifest, PackageManifestFile,
+        PackageManifest, PackageManifestFile, TestEnvironment,
     },
     CORE, PRELUDE, STD,
 };
@@ -36,14 +37,14 @@ use sway_core::{
 use sway_core::{
     language::{
         parsed::{ParseProgram, TreeType},
-        ty,
+        ty, Literal,
     },
     semantic_analysis::namespace,
     source_map::SourceMap,
     CompileResult, CompiledBytecode, FinalizedEntry,
 };
-use sway_error::error::CompileError;
-use sway_types::Ident;
+use sway_error::{error::CompileError, warning::CompileWarning};
+use sway_types::{Ident, Spanned};
 use sway_utils::constants;
 use tracing::{info, warn};
 use url::Url;
@@ -79,6 +80,9 @@ pub type EdgeIx = petgraph::graph::EdgeIndex<GraphIx>;
 pub type NodeIx = petgraph::graph::NodeIndex<GraphIx>;
 pub type ManifestMap = HashMap<PinnedId, PackageManifestFile>;
 
+/// The version selected for each registry package, keyed by package name.
+type RegistryVersions = HashMap<String, semver::Version>;
+
 /// A unique ID for a pinned package.
 ///
 /// The internal value is produced by hashing the package's name and `SourcePinned`.
@@ -89,6 +93,11 @@ pub struct PinnedId(u64);
 #[derive(Debug, Clone)]
 pub struct BuiltPackage {
     pub json_abi_program: fuels_types::ProgramABI,
+    /// The JSON ABI of the package's test functions, used to encode the inputs of tests that
+    /// declare parameters.
+    ///
+    /// Contains no functions unless the package was built with tests included.
+    pub json_abi_tests: fuels_types::ProgramABI,
     pub storage_slots: Vec<StorageSlot>,
     pub bytecode: Vec<u8>,
     pub entries: Vec<FinalizedEntry>,
@@ -96,6 +105,39 @@ pub struct BuiltPackage {
     source_map: SourceMap,
     pub pkg_name: String,
     pub declaration_engine: DeclarationEngine,
+    /// The bytecode of the contract as it would be deployed, i.e. with all tests excluded.
+    ///
+    /// Only `Some` for contracts that were built with tests included.
+    pub bytecode_without_tests: Option<Vec<u8>>,
+    /// The source map of the contract as it would be deployed, i.e. with all tests excluded.
+    ///
+    /// Only `Some` for contracts that were built with tests included.
+    pub source_map_without_tests: Option<SourceMap>,
+    /// All contract dependencies of the package, direct and transitive, compiled without tests.
+    pub contract_dependencies: Vec<BuiltContractDependency>,
+    /// The environment in which the package's tests are executed, as declared under `[test]`.
+    pub test_environment: TestEnvironment,
+    /// The directory containing the package's manifest.
+    pub manifest_dir: PathBuf,
+    /// The warnings produced while compiling the package.
+    pub warnings: Vec<CompileWarning>,
+    /// The revert codes declared within `std::error_signals`, by name.
+    ///
+    /// Empty unless the package was built with tests included and depends on `std`.
+    pub error_signals: BTreeMap<String, u64>,
+}
+
+/// A contract dependency that has been compiled without tests and is ready to be deployed.
+#[derive(Clone, Debug)]
+pub struct BuiltContractDependency {
+    /// The name of the contract dependency's package.
+    pub pkg_name: String,
+    pub bytecode: Vec<u8>,
+    pub storage_slots: Vec<StorageSlot>,
+    /// The salt declared for the contract dependency within the manifest.
+    pub salt: fuel_tx::Salt,
+    /// The ID of the contract, as provided to dependents via the `CONTRACT_ID` constant.
+    pub contract_id: ContractId,
 }
 
 /// The result of successfully compiling a workspace.
@@ -140,7 +182,7 @@ pub enum Source {
     Git(SourceGit),
     /// A path to a directory with a `Forc.toml` manifest at its root.
     Path(PathBuf),
-    /// A forc project hosted on the official registry.
+    /// A forc project published to the package registry.
     Registry(SourceRegistry),
 }
 
@@ -165,11 +207,13 @@ pub enum GitReference {
     DefaultBranch,
 }
 
-/// A package from the official registry.
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+/// A package from the package registry.
+///
+/// See the [crate::registry] module for details on how the registry is located.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
 pub struct SourceRegistry {
-    /// The base version specified for the package.
-    pub version: semver::Version,
+    /// The version requirement specified for the package.
+    pub version: semver::VersionReq,
 }
 
 /// A pinned instance of a git source.
@@ -198,12 +242,20 @@ pub struct SourcePathPinned {
 }
 
 /// A pinned instance of the registry source.
+///
+/// Registry packages are unified by name, such that a single version is selected for each package
+/// that satisfies the version requirements of all of its dependents. As a result, the pinned source
+/// does not include any particular dependent's version requirement.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
 pub struct SourceRegistryPinned {
-    /// The registry package with base version.
-    pub source: SourceRegistry,
     /// The pinned version.
     pub version: semver::Version,
+    /// The hex-encoded SHA-256 checksum of the pinned version's archive, such that an archive
+    /// that is re-published under the same version is detected.
+    ///
+    /// `None` for lock files that predate the recording of checksums, in which case the package is
+    /// pinned again.
+    pub checksum: Option<String>,
 }
 
 /// A pinned instance of the package source.
@@ -224,6 +276,8 @@ pub struct BuildPlan {
     graph: Graph,
     manifest_map: ManifestMap,
     compilation_order: Vec<NodeIx>,
+    /// The features enabled for each package.
+    features: HashMap<NodeIx, BTreeSet<String>>,
 }
 
 /// Error returned upon failed parsing of `PinnedId::from_str`.
@@ -234,6 +288,14 @@ pub struct PinnedIdParseError;
 #[derive(Clone, Debug)]
 pub struct SourcePathPinnedParseError;
 
+/// Error returned upon failed parsing of `SourceRegistryPinned::from_str`.
+#[derive(Clone, Debug)]
+pub enum SourceRegistryPinnedParseError {
+    Prefix,
+    Version,
+    Checksum,
+}
+
 /// Error returned upon failed parsing of `SourceGitPinned::from_str`.
 #[derive(Clone, Debug)]
 pub enum SourceGitPinnedParseError {
@@ -304,12 +366,27 @@ pub struct MinifyOpts {
     pub json_storage_slots: bool,
 }
 
+/// The features enabled for the workspace members being built.
+///
+/// The features of dependencies are enabled by their dependents.
+#[derive(Default, Clone)]
+pub struct FeatureOpts {
+    /// Features to enable in addition to default features. Each feature is enabled for all
+    /// members that declare it.
+    pub features: Vec<String>,
+    /// Enable all features declared by members.
+    pub all_features: bool,
+    /// Do not enable the `default` features of members.
+    pub no_default_features: bool,
+}
+
 /// The set of options provided to the `build` functions.
 #[derive(Default)]
 pub struct BuildOpts {
     pub pkg: PkgOpts,
     pub print: PrintOpts,
     pub minify: MinifyOpts,
+    pub features: FeatureOpts,
     /// If set, outputs a binary file representing the script bytes.
     pub binary_outfile: Option<String>,
     /// If set, outputs source file mapping in JSON format
@@ -323,6 +400,9 @@ pub struct BuildOpts {
     pub release: bool,
     /// Output the time elapsed over each part of the compilation process.
     pub time_phases: bool,
+    /// Compile every package from source, rather than reusing unchanged packages from the build
+    /// cache.
+    pub no_cache: bool,
     /// Include all test functions within the build.
     pub tests: bool,
 }
@@ -349,6 +429,11 @@ impl BuiltPackage {
         Ok(())
     }
 
+    /// The mapping from each instruction within the package's bytecode to its source location.
+    pub fn source_map(&self) -> &SourceMap {
+        &self.source_map
+    }
+
     /// Writes debug_info (source_map) of the BuiltPackage to the given `path`.
     pub fn write_debug_info(&self, path: &Path) -> Result<()> {
         let source_map_json =
@@ -472,15 +557,29 @@ impl BuildPlan {
         validate_version(manifests)?;
         let mut graph = Graph::default();
         let mut manifest_map = ManifestMap::default();
-        fetch_graph(manifests, offline, &mut graph, &mut manifest_map)?;
+        let mut registry_versions = RegistryVersions::default();
+        fetch_graph(
+            manifests,
+            offline,
+            &mut graph,
+            &mut manifest_map,
+            &mut registry_versions,
+        )?;
         // Validate the graph, since we constructed the graph from scratch the paths will not be a
         // problem but the version check is still needed
         validate_graph(&graph, manifests)?;
         let compilation_order = compilation_order(&graph)?;
+        let features = resolve_features(
+            &graph,
+            &manifest_map,
+            &compilation_order,
+            &FeatureOpts::default(),
+        )?;
         Ok(Self {
             graph,
             manifest_map,
             compilation_order,
+            features,
         })
     }
 
@@ -526,6 +625,7 @@ impl BuildPlan {
             new_lock_cause = Some(anyhow!("Invalid lock: {}", e));
             Graph::default()
         });
+        validate_registry_checksums(&graph)?;
 
         // Since the lock file was last created there are many ways in which it might have been
         // invalidated. E.g. a package's manifest `[dependencies]` table might have changed, a user
@@ -533,6 +633,10 @@ impl BuildPlan {
         // longer exists at its specified location, etc. We must first remove all invalid nodes
         // before we can determine what we need to fetch.
         let invalid_deps = validate_graph(&graph, manifests)?;
+
+        // Prefer the registry package versions selected by the lock, even where the lock's
+        // dependency edges have been invalidated.
+        let mut registry_versions = registry_versions(&graph);
         let members: HashSet<String> = manifests
             .iter()
             .map(|(member_name, _)| member_name.clone())
@@ -544,15 +648,28 @@ impl BuildPlan {
         let mut manifest_map = graph_to_manifest_map(manifests, &graph)?;
 
         // Attempt to fetch the remainder of the graph.
-        let _added = fetch_graph(manifests, offline, &mut graph, &mut manifest_map)?;
+        let _added = fetch_graph(
+            manifests,
+            offline,
+            &mut graph,
+            &mut manifest_map,
+            &mut registry_versions,
+        )?;
 
         // Determine the compilation order.
         let compilation_order = compilation_order(&graph)?;
+        let features = resolve_features(
+            &graph,
+            &manifest_map,
+            &compilation_order,
+            &FeatureOpts::default(),
+        )?;
 
         let plan = Self {
             graph,
             manifest_map,
             compilation_order,
+            features,
         };
 
         // Construct the new lock and check the diff.
@@ -614,6 +731,25 @@ impl BuildPlan {
         &self.compilation_order
     }
 
+    /// Enable the given features for the workspace members, along with the features of their
+    /// dependencies enabled as a result.
+    ///
+    /// By default, the `default` features of all packages are enabled.
+    pub fn select_features(&mut self, opts: &FeatureOpts) -> Result<()> {
+        self.features = resolve_features(
+            &self.graph,
+            &self.manifest_map,
+            &self.compilation_order,
+            opts,
+        )?;
+        Ok(())
+    }
+
+    /// The features enabled for the package at the given node.
+    pub fn features(&self, node: NodeIx) -> &BTreeSet<String> {
+        &self.features[&node]
+    }
+
     /// Produce the node index of the member with the given name.
     pub fn find_member_index(&self, member_name: &str) -> Option<NodeIx> {
         self.member_nodes()
@@ -698,6 +834,49 @@ fn validate_pkg_version(pkg_manifest: &PackageManifestFile) -> Result<()> {
     Ok(())
 }
 
+/// The versions of all registry packages within the graph.
+fn registry_versions(graph: &Graph) -> RegistryVersions {
+    graph
+        .node_weights()
+        .filter_map(|pinned| match &pinned.source {
+            SourcePinned::Registry(reg) => Some((pinned.name.clone(), reg.version.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Ensure that the archive checksums recorded for registry packages within the graph match those
+/// of the registry's index.
+///
+/// A mismatch indicates that a different archive has been published under the pinned version, in
+/// which case the package must not be silently pinned again.
+fn validate_registry_checksums(graph: &Graph) -> Result<()> {
+    let registry = crate::registry::Registry::from_env();
+    for pinned in graph.node_weights() {
+        let reg = match &pinned.source {
+            SourcePinned::Registry(reg) => reg,
+            _ => continue,
+        };
+        let (locked, entry) = match (&reg.checksum, registry.entry(&pinned.name, &reg.version)) {
+            (Some(locked), Ok(entry)) => (locked, entry),
+            _ => continue,
+        };
+        if *locked != entry.checksum {
+            bail!(
+                "checksum mismatch for version {} of `{}`: the lock file records {}, but registry {} \
+                 records {}. The archive appears to have been re-published; remove the package \
+                 from the lock file to accept the new archive.",
+                reg.version,
+                pinned.name,
+                locked,
+                registry.path().display(),
+                entry.checksum
+            );
+        }
+    }
+    Ok(())
+}
+
 fn member_nodes(g: &Graph) -> impl Iterator<Item = NodeIx> + '_ {
     g.node_indices()
         .filter(|&n| g[n].source == SourcePinned::Member)
@@ -786,7 +965,14 @@ fn validate_dep(
         .ok_or_else(|| anyhow!("no entry in parent manifest"))?;
     let dep_source = dep_to_source_patched(node_manifest, dep_name, dep_entry, manifests)?;
     let dep_pkg = graph[dep_node].unpinned(&dep_path);
-    if dep_pkg.source != dep_source {
+    let source_matches = match (&graph[dep_node].source, &dep_source) {
+        // Registry packages are unified by name, so any version satisfying the requirement is valid.
+        (SourcePinned::Registry(pinned), Source::Registry(source)) => {
+            source.version.matches(&pinned.version)
+        }
+        _ => dep_pkg.source == dep_source,
+    };
+    if !source_matches {
         bail!("dependency node's source does not match manifest entry");
     }
 
@@ -881,7 +1067,16 @@ fn dep_path(
                 node_manifest.project.name
             )
         }
-        SourcePinned::Registry(_reg) => unreachable!("registry dependencies not yet supported"),
+        SourcePinned::Registry(reg) => match &reg.checksum {
+            Some(checksum) => {
+                crate::registry::Registry::from_env().fetch(&dep.name, &reg.version, checksum)
+            }
+            None => bail!(
+                "no checksum recorded for version {} of `{}`",
+                reg.version,
+                dep.name
+            ),
+        },
         SourcePinned::Member => {
             // If a node has a root dependency it is a member of the workspace.
             manifests
@@ -1002,7 +1197,8 @@ impl Pinned {
             SourcePinned::Member => Source::Member(path.to_owned()),
             SourcePinned::Git(git) => Source::Git(git.source.clone()),
             SourcePinned::Path(_) => Source::Path(path.to_owned()),
-            SourcePinned::Registry(reg) => Source::Registry(reg.source.clone()),
+            // Registry packages are unified by name, so any version requirement may be satisfied.
+            SourcePinned::Registry(_) => Source::Registry(SourceRegistry::any()),
         };
         let name = self.name.clone();
         Pkg { name, source }
@@ -1027,6 +1223,19 @@ impl SourceGitPinned {
     pub const PREFIX: &'static str = "git";
 }
 
+impl SourceRegistry {
+    /// A registry source that accepts any version of the package.
+    pub fn any() -> Self {
+        Self {
+            version: semver::VersionReq::STAR,
+        }
+    }
+}
+
+impl SourceRegistryPinned {
+    pub const PREFIX: &'static str = "registry";
+}
+
 impl fmt::Display for PinnedId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // Format the inner `u64` as hex.
@@ -1055,6 +1264,17 @@ impl fmt::Display for SourceGitPinned {
     }
 }
 
+impl fmt::Display for SourceRegistryPinned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // registry+<version>#<checksum>
+        write!(f, "{}+{}", Self::PREFIX, self.version)?;
+        if let Some(checksum) = &self.checksum {
+            write!(f, "#{}", checksum)?;
+        }
+        Ok(())
+    }
+}
+
 impl fmt::Display for GitReference {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -1072,7 +1292,7 @@ impl fmt::Display for SourcePinned {
             SourcePinned::Member => write!(f, "member"),
             SourcePinned::Path(src) => src.fmt(f),
             SourcePinned::Git(src) => src.fmt(f),
-            SourcePinned::Registry(_reg) => unimplemented!("pkg registries not yet implemented"),
+            SourcePinned::Registry(src) => src.fmt(f),
         }
     }
 }
@@ -1165,6 +1385,45 @@ impl FromStr for SourceGitPinned {
     }
 }
 
+impl FromStr for SourceRegistryPinned {
+    type Err = SourceRegistryPinnedParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // registry+<version>#<checksum>
+        let s = s.trim();
+
+        // Check for "registry+" at the start.
+        let prefix_plus = format!("{}+", Self::PREFIX);
+        if s.find(&prefix_plus) != Some(0) {
+            return Err(SourceRegistryPinnedParseError::Prefix);
+        }
+        let s = &s[prefix_plus.len()..];
+
+        // Lock files produced prior to the unification of registry packages use the form
+        // `registry+<version_req>#<version>`. The requirement is no longer part of the pinned
+        // source, so only the version is retained. Neither this form nor `registry+<version>`
+        // record the checksum of the archive.
+        let (version, checksum) = match s.rsplit_once('#') {
+            Some((_version_req, version)) if semver::Version::parse(version).is_ok() => {
+                (version, None)
+            }
+            Some((version, checksum)) => (version, Some(checksum)),
+            None => (s, None),
+        };
+
+        let version =
+            semver::Version::parse(version).map_err(|_| SourceRegistryPinnedParseError::Version)?;
+        let checksum = match checksum {
+            Some(checksum) => {
+                validate_registry_checksum(checksum)
+                    .map_err(|_| SourceRegistryPinnedParseError::Checksum)?;
+                Some(checksum.to_string())
+            }
+            None => None,
+        };
+        Ok(Self { version, checksum })
+    }
+}
+
 impl FromStr for SourcePinned {
     type Err = SourcePinnedParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -1176,14 +1435,30 @@ impl FromStr for SourcePinned {
             SourcePinned::Path(src)
         } else if let Ok(src) = SourceGitPinned::from_str(s) {
             SourcePinned::Git(src)
+        } else if let Ok(src) = SourceRegistryPinned::from_str(s) {
+            SourcePinned::Registry(src)
         } else {
-            // TODO: Try parse registry source.
             return Err(SourcePinnedParseError);
         };
         Ok(source)
     }
 }
 
+fn validate_registry_checksum(checksum: &str) -> Result<()> {
+    const LEN: usize = 64;
+    if checksum.len() != LEN {
+        bail!(
+            "invalid checksum length: expected {}, found {}",
+            LEN,
+            checksum.len()
+        )
+    }
+    if checksum.chars().any(|c| !c.is_ascii_hexdigit()) {
+        bail!("checksum contains invalid characters");
+    }
+    Ok(())
+}
+
 fn validate_git_commit_hash(commit_hash: &str) -> Result<()> {
     const LEN: usize = 40;
     if commit_hash.len() != LEN {
@@ -1205,6 +1480,20 @@ impl Default for GitReference {
     }
 }
 
+// `semver::VersionReq` does not implement `Ord`, so registry sources are ordered by the string
+// representation of their version requirement.
+impl Ord for SourceRegistry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.version.to_string().cmp(&other.version.to_string())
+    }
+}
+
+impl PartialOrd for SourceRegistry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 /// The `pkg::Graph` is of *a -> b* where *a* depends on *b*. We can determine compilation order by
 /// performing a toposort of the graph with reversed weights. The resulting order ensures all
 /// dependencies are always compiled before their dependents.
@@ -1363,27 +1652,367 @@ pub fn fetch_id(path: &Path, timestamp: std::time::Instant) -> u64 {
 /// root nodes, each representing a member of the workspace. Otherwise resulting graph will only
 /// have a single root node, representing the package that is described by the ManifestFile::Package
 ///
+/// Registry packages are pinned to their version within `registry_versions` where possible. In the
+/// case that the selected version of a registry package does not satisfy the requirements of all of
+/// its dependents, a version that does is selected and the package is fetched again. Upon success,
+/// `registry_versions` contains the version selected for each registry package.
+///
 /// Checks the created graph after fetching for conflicting salt declarations.
 fn fetch_graph(
     member_manifests: &MemberManifestFiles,
     offline: bool,
     graph: &mut Graph,
     manifest_map: &mut ManifestMap,
+    registry_versions: &mut RegistryVersions,
 ) -> Result<HashSet<NodeIx>> {
+    let member_names: HashSet<String> = member_manifests.keys().cloned().collect();
+    let registry = crate::registry::Registry::from_env();
+    let mut attempted = HashSet::default();
     let mut added_nodes = HashSet::default();
-    for member_pkg_manifest in member_manifests.values() {
-        added_nodes.extend(&fetch_pkg_graph(
-            member_pkg_manifest,
-            offline,
+    loop {
+        for member_pkg_manifest in member_manifests.values() {
+            added_nodes.extend(&fetch_pkg_graph(
+                member_pkg_manifest,
+                offline,
+                graph,
+                manifest_map,
+                member_manifests,
+                registry_versions,
+            )?);
+        }
+        let reselected = resolve_versions(
             graph,
             manifest_map,
-            member_manifests,
-        )?);
+            &registry,
+            registry_versions,
+            &mut attempted,
+        )?;
+        if reselected.is_empty() {
+            break;
+        }
+        remove_deps(graph, &member_names, &reselected);
     }
+    added_nodes.retain(|&n| graph.contains_node(n));
     validate_contract_deps(graph)?;
+    validate_source_conflicts(graph, manifest_map)?;
     Ok(added_nodes)
 }
 
+/// Check that every package within the graph satisfies the version requirements declared by each
+/// of its dependents.
+///
+/// Registry packages that do not satisfy all of their requirements are re-selected from the given
+/// `registry`, updating `registry_versions` with the greatest version that satisfies all of them.
+/// Returns the set of dependency edges to the re-selected packages, so that they may be removed and
+/// fetched again.
+///
+/// Returns an error describing the chain of dependents behind each requirement in the case that
+/// the requirements cannot be satisfied.
+fn resolve_versions(
+    graph: &Graph,
+    manifest_map: &ManifestMap,
+    registry: &crate::registry::Registry,
+    registry_versions: &mut RegistryVersions,
+    attempted: &mut HashSet<(String, semver::Version)>,
+) -> Result<BTreeSet<EdgeIx>> {
+    let mut reselected = BTreeSet::default();
+    for node in graph.node_indices() {
+        let pinned = &graph[node];
+        let reqs = version_reqs(graph, manifest_map, node)?;
+        let version = match &pinned.source {
+            SourcePinned::Registry(reg) => Some(reg.version.clone()),
+            _ => manifest_map[&pinned.id()].project.version.clone(),
+        };
+        let satisfied = match &version {
+            Some(version) => reqs.iter().all(|(req, _)| req.matches(version)),
+            None => reqs.is_empty(),
+        };
+        if satisfied {
+            continue;
+        }
+
+        let conflict = version_conflict(graph, node, version.as_ref(), &reqs);
+        match pinned.source {
+            SourcePinned::Registry(_) => {
+                let reqs: Vec<_> = reqs.into_iter().map(|(req, _)| req).collect();
+                let entry = registry
+                    .resolve(&pinned.name, &reqs)
+                    .context(conflict.clone())?;
+                // Guard against alternating between versions as the requirements change.
+                if !attempted.insert((pinned.name.clone(), entry.version.clone())) {
+                    bail!(conflict);
+                }
+                registry_versions.insert(pinned.name.clone(), entry.version);
+                reselected.extend(
+                    graph
+                        .edges_directed(node, Direction::Incoming)
+                        .map(|edge| edge.id()),
+                );
+            }
+            _ => bail!(conflict),
+        }
+    }
+    Ok(reselected)
+}
+
+/// Collect the version requirements declared for the given node by each of its dependents.
+fn version_reqs(
+    graph: &Graph,
+    manifest_map: &ManifestMap,
+    node: NodeIx,
+) -> Result<Vec<(semver::VersionReq, NodeIx)>> {
+    let mut reqs = vec![];
+    for edge in graph.edges_directed(node, Direction::Incoming) {
+        let parent = edge.source();
+        let parent_manifest = &manifest_map[&graph[parent].id()];
+        let dep_name = &edge.weight().name;
+        let dep = match edge.weight().kind {
+            DepKind::Library => parent_manifest.dep(dep_name),
+            DepKind::Contract { .. } => parent_manifest
+                .contract_dep(dep_name)
+                .map(|contract_dep| &contract_dep.dependency),
+        };
+        let req_str = match dep {
+            Some(Dependency::Simple(version)) => version,
+            Some(Dependency::Detailed(details)) => match &details.version {
+                Some(version) => version,
+                None => continue,
+            },
+            None => continue,
+        };
+        let req = semver::VersionReq::parse(req_str).with_context(|| {
+            format!(
+                "invalid version requirement `{}` for dependency `{}` of `{}`",
+                req_str, dep_name, graph[parent].name
+            )
+        })?;
+        reqs.push((req, parent));
+    }
+    Ok(reqs)
+}
+
+/// Describe the version requirements of the given node that could not be satisfied, along with
+/// every chain of dependents from a workspace member to each dependent that declared a requirement.
+fn version_conflict(
+    graph: &Graph,
+    node: NodeIx,
+    version: Option<&semver::Version>,
+    reqs: &[(semver::VersionReq, NodeIx)],
+) -> String {
+    let pinned = &graph[node];
+    let mut msg = match (&pinned.source, version) {
+        (SourcePinned::Registry(_), _) => format!(
+            "failed to select a version of `{}` that satisfies the requirements of all dependents:",
+            pinned.name
+        ),
+        (_, Some(version)) => format!(
+            "package `{}` version {} does not satisfy the requirements of all dependents:",
+            pinned.name, version
+        ),
+        (_, None) => format!(
+            "package `{}` does not specify a version, but is required to satisfy:",
+            pinned.name
+        ),
+    };
+    let lines: BTreeSet<String> = reqs
+        .iter()
+        .flat_map(|(req, dependent)| {
+            dependent_chains(graph, *dependent)
+                .into_iter()
+                .map(move |chain| format!("\n    `{}` required by {}", req, chain))
+        })
+        .collect();
+    msg.extend(lines);
+    msg
+}
+
+/// The features requested of a package by its dependents, or by the user in the case of members.
+#[derive(Default)]
+struct FeatureRequest {
+    default: bool,
+    features: BTreeSet<String>,
+}
+
+/// Determine the features enabled for each package in the graph.
+///
+/// Members are requested the features selected by the given options. Each package then requests
+/// the features of its dependencies listed within its dependency declarations, along with those
+/// listed as `<dependency>/<feature>` by its own enabled features. As the graph is traversed from
+/// dependents to dependencies, the requests of all dependents are unified before a package's
+/// enabled features are determined.
+fn resolve_features(
+    graph: &Graph,
+    manifest_map: &ManifestMap,
+    compilation_order: &[NodeIx],
+    opts: &FeatureOpts,
+) -> Result<HashMap<NodeIx, BTreeSet<String>>> {
+    let mut requests: HashMap<NodeIx, FeatureRequest> = HashMap::new();
+    let members: Vec<NodeIx> = member_nodes(graph).collect();
+    for &member in &members {
+        let manifest = &manifest_map[&graph[member].id()];
+        let request = requests.entry(member).or_default();
+        request.default = !opts.no_default_features;
+        if opts.all_features {
+            request
+                .features
+                .extend(manifest.features().map(|(name, _)| name.clone()));
+        }
+        request.features.extend(
+            opts.features
+                .iter()
+                .filter(|name| manifest.feature(name).is_some())
+                .cloned(),
+        );
+    }
+    for name in &opts.features {
+        let declared = members
+            .iter()
+            .any(|&member| manifest_map[&graph[member].id()].feature(name).is_some());
+        if !declared {
+            bail!(
+                "the feature `{}` is not declared under `[features]` by any of the packages \
+                being built",
+                name
+            );
+        }
+    }
+
+    let mut enabled_features = HashMap::new();
+    for &node in compilation_order.iter().rev() {
+        let manifest = &manifest_map[&graph[node].id()];
+        let request = requests.remove(&node).unwrap_or_default();
+
+        // Enable the requested features, along with all features that they enable in turn.
+        let mut enabled = BTreeSet::new();
+        let mut dep_features: HashMap<&str, Vec<String>> = HashMap::new();
+        let mut queue: Vec<String> = request.features.into_iter().collect();
+        if request.default && manifest.feature(PackageManifest::DEFAULT_FEATURE).is_some() {
+            queue.push(PackageManifest::DEFAULT_FEATURE.to_string());
+        }
+        while let Some(feature) = queue.pop() {
+            let enables = manifest.feature(&feature).ok_or_else(|| {
+                anyhow!(
+                    "package `{}` does not declare the feature `{}`, required by {}",
+                    manifest.project.name,
+                    feature,
+                    dependent_chains(graph, node).join(", "),
+                )
+            })?;
+            if !enabled.insert(feature) {
+                continue;
+            }
+            for enabled_feature in enables {
+                match enabled_feature.split_once('/') {
+                    Some((dep_name, dep_feature)) => dep_features
+                        .entry(dep_name)
+                        .or_default()
+                        .push(dep_feature.to_string()),
+                    None => queue.push(enabled_feature.clone()),
+                }
+            }
+        }
+
+        // Request the features of each dependency.
+        for edge in graph.edges_directed(node, Direction::Outgoing) {
+            let dep_name = &edge.weight().name;
+            let dep = match edge.weight().kind {
+                DepKind::Library => manifest.dep(dep_name),
+                DepKind::Contract { .. } => manifest
+                    .contract_dep(dep_name)
+                    .map(|contract_dep| &contract_dep.dependency),
+            };
+            let dep_request = requests.entry(edge.target()).or_default();
+            dep_request.default |= dep.map(|dep| dep.default_features()).unwrap_or(true);
+            if let Some(dep) = dep {
+                dep_request.features.extend(dep.features().iter().cloned());
+            }
+            if let Some(features) = dep_features.get(dep_name.as_str()) {
+                dep_request.features.extend(features.iter().cloned());
+            }
+        }
+        enabled_features.insert(node, enabled);
+    }
+    Ok(enabled_features)
+}
+
+/// Check that each package name within the dependencies of each workspace member refers to a
+/// single package.
+///
+/// Registry packages are unified by `resolve_versions`, however a package may also be pinned to
+/// multiple `git` or `path` sources, e.g. where one dependent depends on a package via `git` and
+/// another via `path`. Returns an error describing the chains of dependents behind each source in
+/// this case.
+fn validate_source_conflicts(graph: &Graph, manifest_map: &ManifestMap) -> Result<()> {
+    for member in member_nodes(graph) {
+        let mut nodes_by_name: BTreeMap<&str, BTreeSet<NodeIx>> = BTreeMap::new();
+        for node in Dfs::new(graph, member).iter(graph) {
+            nodes_by_name
+                .entry(graph[node].name.as_str())
+                .or_default()
+                .insert(node);
+        }
+        let (name, nodes) = match nodes_by_name.into_iter().find(|(_, nodes)| nodes.len() > 1) {
+            Some(conflict) => conflict,
+            None => continue,
+        };
+        let mut msg = format!(
+            "package `{}` resolves to multiple sources within the dependencies of `{}`:",
+            name, graph[member].name
+        );
+        let lines: BTreeSet<String> = nodes
+            .into_iter()
+            .flat_map(|node| {
+                let source = source_description(&graph[node], manifest_map);
+                graph
+                    .neighbors_directed(node, Direction::Incoming)
+                    .flat_map(|dependent| dependent_chains(graph, dependent))
+                    .map(move |chain| format!("\n    {} required by {}", source, chain))
+            })
+            .collect();
+        msg.extend(lines);
+        bail!(msg);
+    }
+    Ok(())
+}
+
+/// A description of the source of the given pinned package for use within error messages.
+fn source_description(pinned: &Pinned, manifest_map: &ManifestMap) -> String {
+    match &pinned.source {
+        SourcePinned::Path(_) => match manifest_map.get(&pinned.id()) {
+            Some(manifest) => format!("path `{}`", manifest.dir().display()),
+            None => pinned.source.to_string(),
+        },
+        SourcePinned::Registry(reg) => format!("registry version {}", reg.version),
+        source => format!("`{}`", source),
+    }
+}
+
+/// Every chain of dependents from a workspace member to the given node, e.g. `app -> a -> b`.
+///
+/// Chains are sorted, such that the result is deterministic.
+fn dependent_chains(graph: &Graph, node: NodeIx) -> Vec<String> {
+    fn collect(graph: &Graph, path: &mut Vec<NodeIx>, chains: &mut BTreeSet<String>) {
+        let node = *path.last().expect("path is never empty");
+        let parents: BTreeSet<NodeIx> = graph
+            .neighbors_directed(node, Direction::Incoming)
+            .filter(|parent| !path.contains(parent))
+            .collect();
+        if parents.is_empty() {
+            let names: Vec<_> = path.iter().rev().map(|&n| graph[n].name.as_str()).collect();
+            chains.insert(names.join(" -> "));
+            return;
+        }
+        for parent in parents {
+            path.push(parent);
+            collect(graph, path, chains);
+            path.pop();
+        }
+    }
+    let mut chains = BTreeSet::new();
+    collect(graph, &mut vec![node], &mut chains);
+    chains.into_iter().collect()
+}
+
 /// Given an empty or partially completed package `graph`, complete the graph.
 ///
 /// The given `graph` may be empty, partially complete, or fully complete. All existing nodes
@@ -1403,6 +2032,7 @@ fn fetch_pkg_graph(
     graph: &mut Graph,
     manifest_map: &mut ManifestMap,
     member_manifests: &MemberManifestFiles,
+    registry_versions: &RegistryVersions,
 ) -> Result<HashSet<NodeIx>> {
     // Retrieve the project node, or create one if it does not exist.
     let proj_node = match find_proj_node(graph, &proj_manifest.project.name) {
@@ -1441,6 +2071,7 @@ fn fetch_pkg_graph(
         &mut fetched,
         &mut visited,
         member_manifests,
+        registry_versions,
     )
 }
 
@@ -1458,6 +2089,7 @@ fn fetch_deps(
     fetched: &mut HashMap<Pkg, NodeIx>,
     visited: &mut HashSet<NodeIx>,
     member_manifests: &MemberManifestFiles,
+    registry_versions: &RegistryVersions,
 ) -> Result<HashSet<NodeIx>> {
     let mut added = HashSet::default();
     let parent_id = graph[node].id();
@@ -1485,11 +2117,26 @@ fn fetch_deps(
             .context("Failed to source dependency")?;
 
         // If we haven't yet fetched this dependency, fetch it, pin it and add it to the graph.
+        // Registry packages are unified by name, regardless of the version requirement.
         let dep_pkg = Pkg { name, source };
-        let dep_node = match fetched.entry(dep_pkg) {
+        let fetched_key = match dep_pkg.source {
+            Source::Registry(_) => Pkg {
+                name: dep_pkg.name.clone(),
+                source: Source::Registry(SourceRegistry::any()),
+            },
+            _ => dep_pkg.clone(),
+        };
+        let dep_node = match fetched.entry(fetched_key) {
             hash_map::Entry::Occupied(entry) => *entry.get(),
             hash_map::Entry::Vacant(entry) => {
-                let dep_pinned = pin_pkg(fetch_id, path_root, entry.key(), manifest_map, offline)?;
+                let dep_pinned = pin_pkg(
+                    fetch_id,
+                    path_root,
+                    &dep_pkg,
+                    manifest_map,
+                    registry_versions,
+                    offline,
+                )?;
                 let dep_node = graph.add_node(dep_pinned);
                 added.insert(dep_node);
                 *entry.insert(dep_node)
@@ -1533,6 +2180,7 @@ fn fetch_deps(
             fetched,
             visited,
             member_manifests,
+            registry_versions,
         )?);
     }
     Ok(added)
@@ -1673,11 +2321,15 @@ pub fn pin_git(fetch_id: u64, name: &str, source: SourceGit) -> Result<SourceGit
 ///
 /// The `path_root` is required for `Path` dependencies and must specify the package that is the
 /// root of the current subgraph of path dependencies.
+///
+/// Registry packages are pinned to their version within `registry_versions` if one has been
+/// selected and satisfies the package's requirement, or the greatest matching version otherwise.
 fn pin_pkg(
     fetch_id: u64,
     path_root: PinnedId,
     pkg: &Pkg,
     manifest_map: &mut ManifestMap,
+    registry_versions: &RegistryVersions,
     offline: bool,
 ) -> Result<Pinned> {
     let name = pkg.name.clone();
@@ -1786,13 +2438,33 @@ fn pin_pkg(
             }
             pinned
         }
-        Source::Registry(ref _source) => {
-            if offline {
-                bail!("Unable to fetch pkg {:?} in offline mode", name);
+        Source::Registry(ref registry_source) => {
+            // The registry index is located on the local file system, so registry packages are
+            // available in offline mode.
+            // Prefer the version already selected for this package if it satisfies the requirement.
+            let registry = crate::registry::Registry::from_env();
+            let entry = match registry_versions.get(&name) {
+                Some(version) if registry_source.version.matches(version) => {
+                    registry.entry(&name, version)?
+                }
+                _ => {
+                    let reqs = std::slice::from_ref(&registry_source.version);
+                    registry.resolve(&name, reqs)?
+                }
+            };
+            let pinned_registry = SourceRegistryPinned {
+                version: entry.version.clone(),
+                checksum: Some(entry.checksum.clone()),
+            };
+            let source = SourcePinned::Registry(pinned_registry);
+            let pinned = Pinned { name, source };
+            let id = pinned.id();
+            if let hash_map::Entry::Vacant(vacant) = manifest_map.entry(id) {
+                let path = registry.fetch(&pinned.name, &entry.version, &entry.checksum)?;
+                let manifest = PackageManifestFile::from_dir(&path)?;
+                vacant.insert(manifest);
             }
-            // TODO: determine registry pkg git URL, fetch to determine latest available
-            // semver-compatible version
-            bail!("registry dependencies are not yet supported");
+            pinned
         }
     };
     Ok(pinned)
@@ -1830,7 +2502,7 @@ fn fd_lock_path(path: &Path) -> PathBuf {
 /// Create an advisory lock over the given path.
 ///
 /// See [fd_lock_path] for details.
-fn path_lock(path: &Path) -> Result<fd_lock::RwLock<File>> {
+pub(crate) fn path_lock(path: &Path) -> Result<fd_lock::RwLock<File>> {
     let lock_path = fd_lock_path(path);
     let lock_dir = lock_path
         .parent()
@@ -2043,14 +2715,7 @@ fn dep_to_source(
     member_manifests: &MemberManifestFiles,
 ) -> Result<Source> {
     let source = match dep {
-        Dependency::Simple(ref ver_str) => {
-            bail!(
-                "Unsupported dependency declaration in \"{}\": `{}` - \
-                currently only `git` and `path` dependencies are supported",
-                pkg_path.display(),
-                ver_str
-            )
-        }
+        Dependency::Simple(ref ver_str) => registry_source(pkg_path, ver_str)?,
         Dependency::Detailed(ref det) => match (&det.path, &det.version, &det.git) {
             (Some(relative_path), _, _) => {
                 let path = pkg_path.join(relative_path);
@@ -2082,6 +2747,7 @@ fn dep_to_source(
                 let source = SourceGit { repo, reference };
                 Source::Git(source)
             }
+            (None, Some(ver_str), None) => registry_source(pkg_path, ver_str)?,
             _ => {
                 bail!("unsupported set of fields for dependency: {:?}", dep);
             }
@@ -2090,6 +2756,19 @@ fn dep_to_source(
     Ok(source)
 }
 
+/// Parse the version requirement of a registry dependency declared within the given package.
+fn registry_source(pkg_path: &Path, ver_str: &str) -> Result<Source> {
+    let version = semver::VersionReq::parse(ver_str).map_err(|e| {
+        anyhow!(
+            "invalid version requirement `{}` in \"{}\": {}",
+            ver_str,
+            pkg_path.display(),
+            e
+        )
+    })?;
+    Ok(Source::Registry(SourceRegistry { version }))
+}
+
 /// If a patch exists for the given dependency source within the given project manifest, this
 /// returns the patch.
 fn dep_source_patch<'manifest>(
@@ -2141,6 +2820,7 @@ pub fn sway_build_config(
     manifest_dir: &Path,
     entry_path: &Path,
     build_profile: &BuildProfile,
+    features: &BTreeSet<String>,
 ) -> Result<sway_core::BuildConfig> {
     // Prepare the build config to pass through to the compiler.
     let file_name = find_file_name(manifest_dir, entry_path)?;
@@ -2152,7 +2832,8 @@ pub fn sway_build_config(
     .print_finalized_asm(build_profile.print_finalized_asm)
     .print_intermediate_asm(build_profile.print_intermediate_asm)
     .print_ir(build_profile.print_ir)
-    .include_tests(build_profile.include_tests);
+    .include_tests(build_profile.include_tests)
+    .features(features.clone());
     Ok(build_config)
 }
 
@@ -2196,14 +2877,10 @@ pub fn dependency_namespace(
                 };
 
                 // Construct namespace with contract id
-                let contract_dep_constant_name = "CONTRACT_ID";
-                let contract_id_value = format!("0x{dep_contract_id}");
-                let contract_id_constant = ConfigTimeConstant {
-                    r#type: "b256".to_string(),
-                    value: contract_id_value,
-                    public: true,
-                };
-                constants.insert(contract_dep_constant_name.to_string(), contract_id_constant);
+                constants.insert(
+                    CONTRACT_ID_CONSTANT_NAME.to_string(),
+                    contract_id_constant(&dep_contract_id),
+                );
                 namespace::Module::default_with_constants(engines, constants)?
             }
         };
@@ -2231,6 +2908,36 @@ pub fn dependency_namespace(
     Ok(namespace)
 }
 
+/// Construct the `CONTRACT_ID` constant for the contract with the given ID.
+fn contract_id_constant(contract_id: &ContractId) -> ConfigTimeConstant {
+    ConfigTimeConstant {
+        r#type: "b256".to_string(),
+        value: format!("0x{contract_id}"),
+        public: true,
+    }
+}
+
+/// Provide the given ID to the contract's own tests via the `CONTRACT_ID` constant, unless the
+/// contract's manifest declares a constant of the same name.
+fn insert_contract_id_for_tests(
+    namespace: &mut namespace::Module,
+    manifest: &PackageManifestFile,
+    contract_id: &ContractId,
+    engines: Engines<'_>,
+) -> Result<(), vec1::Vec1<CompileError>> {
+    if manifest
+        .config_time_constants()
+        .contains_key(CONTRACT_ID_CONSTANT_NAME)
+    {
+        return Ok(());
+    }
+    let constants = BTreeMap::from([(
+        CONTRACT_ID_CONSTANT_NAME.to_string(),
+        contract_id_constant(contract_id),
+    )]);
+    namespace.insert_test_constants(engines, constants)
+}
+
 /// Find the `std` dependency, if it is a direct one, of the given node.
 fn has_std_dep(graph: &Graph, node: NodeIx) -> bool {
     // If we are `std`, do nothing.
@@ -2288,11 +2995,16 @@ pub fn compile_ast(
     engines: Engines<'_>,
     manifest: &PackageManifestFile,
     build_profile: &BuildProfile,
+    features: &BTreeSet<String>,
     namespace: namespace::Module,
 ) -> Result<CompileResult<ty::TyProgram>> {
     let source = manifest.entry_string()?;
-    let sway_build_config =
-        sway_build_config(manifest.dir(), &manifest.entry_path(), build_profile)?;
+    let sway_build_config = sway_build_config(
+        manifest.dir(),
+        &manifest.entry_path(),
+        build_profile,
+        features,
+    )?;
     let ast_res = sway_core::compile_to_ast(engines, source, namespace, Some(&sway_build_config));
     Ok(ast_res)
 }
@@ -2319,6 +3031,7 @@ pub fn compile(
     pkg: &Pinned,
     manifest: &PackageManifestFile,
     build_profile: &BuildProfile,
+    features: &BTreeSet<String>,
     namespace: namespace::Module,
     engines: Engines<'_>,
     source_map: &mut SourceMap,
@@ -2344,18 +3057,31 @@ pub fn compile(
     let entry_path = manifest.entry_path();
     let sway_build_config = time_expr!(
         "produce `sway_core::BuildConfig`",
-        sway_build_config(manifest.dir(), &entry_path, build_profile,)?
+        sway_build_config(manifest.dir(), &entry_path, build_profile, features)?
     );
     let terse_mode = build_profile.terse;
-    let fail = |warnings, errors| {
+    let fail = |warnings: &[CompileWarning], errors: &[CompileError]| {
         print_on_failure(terse_mode, warnings, errors);
         bail!("Failed to compile {}", pkg.name);
     };
 
+    // The tests of a contract may refer to the contract via `CONTRACT_ID`. The ID is only known
+    // once the contract has been compiled, so a placeholder is provided for type-checking. Tests
+    // are type-checked even when they're not included, so the placeholder is always provided, but
+    // it is only in scope within tests and is never compiled into the deployed contract.
+    let mut namespace = namespace;
+    if let Ok(TreeType::Contract) = manifest.program_type() {
+        if let Err(errs) =
+            insert_contract_id_for_tests(&mut namespace, manifest, &ContractId::default(), engines)
+        {
+            return fail(&[], &errs);
+        }
+    }
+
     // First, compile to an AST. We'll update the namespace and check for JSON ABI output.
     let ast_res = time_expr!(
         "compile to ast",
-        compile_ast(engines, manifest, build_profile, namespace)?
+        compile_ast(engines, manifest, build_profile, features, namespace)?
     );
     let typed_program = match ast_res.value.as_ref() {
         None => return fail(&ast_res.warnings, &ast_res.errors),
@@ -2371,50 +3097,163 @@ pub fn compile(
         "generate JSON ABI program",
         typed_program.generate_json_abi_program(engines.te(), &mut types)
     );
+    let mut test_types = vec![];
+    let test_functions =
+        typed_program.generate_json_abi_test_functions(engines.te(), engines.de(), &mut test_types);
+    let json_abi_tests = fuels_types::ProgramABI {
+        types: test_types,
+        functions: test_functions,
+        logged_types: None,
+        messages_types: None,
+    };
 
     let storage_slots = typed_program.storage_slots.clone();
     let tree_type = typed_program.kind.tree_type();
 
+    let error_signals = match build_profile.include_tests {
+        true => error_signals(engines, pkg, &typed_program.root.namespace),
+        false => BTreeMap::new(),
+    };
+
     let namespace = typed_program.root.namespace.clone().into();
 
     if !ast_res.errors.is_empty() {
         return fail(&ast_res.warnings, &ast_res.errors);
     }
 
-    let asm_res = time_expr!(
-        "compile ast to asm",
-        sway_core::ast_to_asm(engines, &ast_res, &sway_build_config)
-    );
-    let entries = asm_res
-        .value
-        .as_ref()
-        .map(|asm| asm.0.entries.clone())
-        .unwrap_or_default();
-    let bc_res = time_expr!(
-        "compile asm to bytecode",
-        sway_core::asm_to_bytecode(asm_res, source_map)
-    );
-
-    match bc_res.value {
-        Some(CompiledBytecode(bytes)) if bc_res.errors.is_empty() => {
-            print_on_success(terse_mode, &pkg.name, &bc_res.warnings, &tree_type);
-            let bytecode = bytes;
-            let built_package = BuiltPackage {
-                json_abi_program,
-                storage_slots,
-                bytecode,
-                tree_type,
-                entries,
-                source_map: source_map.to_owned(),
-                pkg_name: pkg.name.clone(),
-                declaration_engine: engines.de().clone(),
-            };
-            Ok((built_package, namespace))
+    // Compile the typed program to bytecode, returning its entries along with any warnings.
+    let compile_to_bytecode = |ast_res: &CompileResult<ty::TyProgram>,
+                               sway_build_config: &sway_core::BuildConfig,
+                               source_map: &mut SourceMap| {
+        let asm_res = time_expr!(
+            "compile ast to asm",
+            sway_core::ast_to_asm(engines, ast_res, sway_build_config)
+        );
+        let entries = asm_res
+            .value
+            .as_ref()
+            .map(|asm| asm.0.entries.clone())
+            .unwrap_or_default();
+        let bc_res = time_expr!(
+            "compile asm to bytecode",
+            sway_core::asm_to_bytecode(asm_res, source_map)
+        );
+        match bc_res.value {
+            Some(CompiledBytecode(bytes)) if bc_res.errors.is_empty() => {
+                Ok((bytes, entries, bc_res.warnings))
+            }
+            _ => {
+                print_on_failure(terse_mode, &bc_res.warnings, &bc_res.errors);
+                bail!("Failed to compile {}", pkg.name);
+            }
         }
-        _ => fail(&bc_res.warnings, &bc_res.errors),
+    };
+
+    // Contracts are always deployed without their tests. When tests are included, the contract is
+    // first compiled without them, such that its ID is unaffected by its tests. The ID is then
+    // provided to the contract's own tests via the `CONTRACT_ID` constant before the tests are
+    // compiled.
+    let mut ast_res = ast_res;
+    let (bytecode_without_tests, source_map_without_tests) =
+        match (&tree_type, build_profile.include_tests) {
+            (TreeType::Contract, true) => {
+                let sway_build_config = sway_build_config.clone().include_tests(false);
+                let mut source_map_without_tests = SourceMap::new();
+                let (bytecode_without_tests, _, _) = compile_to_bytecode(
+                    &ast_res,
+                    &sway_build_config,
+                    &mut source_map_without_tests,
+                )?;
+                let contract_id = contract_id_from_bytecode(
+                    &bytecode_without_tests,
+                    &storage_slots,
+                    &fuel_tx::Salt::zeroed(),
+                );
+                let typed_program = ast_res
+                    .value
+                    .as_mut()
+                    .expect("the typed program was checked above");
+                if let Err(errs) = insert_contract_id_for_tests(
+                    &mut typed_program.root.namespace,
+                    manifest,
+                    &contract_id,
+                    engines,
+                ) {
+                    return fail(&[], &errs);
+                }
+                (Some(bytecode_without_tests), Some(source_map_without_tests))
+            }
+            _ => (None, None),
+        };
+
+    let (bytecode, entries, warnings) =
+        compile_to_bytecode(&ast_res, &sway_build_config, source_map)?;
+    print_on_success(terse_mode, &pkg.name, &warnings, &tree_type);
+    let built_package = BuiltPackage {
+        json_abi_program,
+        json_abi_tests,
+        storage_slots,
+        bytecode,
+        tree_type,
+        entries,
+        source_map: source_map.to_owned(),
+        pkg_name: pkg.name.clone(),
+        declaration_engine: engines.de().clone(),
+        bytecode_without_tests,
+        source_map_without_tests,
+        contract_dependencies: vec![],
+        test_environment: manifest.test.clone().unwrap_or_default(),
+        manifest_dir: manifest.dir().to_path_buf(),
+        warnings,
+        error_signals,
+    };
+    Ok((built_package, namespace))
+}
+
+/// The name of the `std` module declaring the revert codes used throughout the standard library.
+const ERROR_SIGNALS_MODULE_NAME: &str = "error_signals";
+
+/// Collect the `u64` constants declared within `std::error_signals` as visible from the given
+/// package's namespace, such that they may be referred to by name within tests.
+fn error_signals(
+    engines: Engines<'_>,
+    pkg: &Pinned,
+    namespace: &namespace::Module,
+) -> BTreeMap<String, u64> {
+    let mut path = vec![];
+    if pkg.name != STD {
+        path.push(Ident::new_no_span(STD));
     }
+    path.push(Ident::new_no_span(ERROR_SIGNALS_MODULE_NAME));
+    let module = match namespace.submodule(&path) {
+        Some(module) => module,
+        None => return BTreeMap::new(),
+    };
+    module
+        .symbols()
+        .iter()
+        .filter_map(|(name, decl)| match decl {
+            ty::TyDeclaration::ConstantDeclaration(decl_id) => {
+                let decl = engines
+                    .de()
+                    .get_constant(decl_id.clone(), &name.span())
+                    .ok()?;
+                match decl.value.expression {
+                    ty::TyExpressionVariant::Literal(Literal::U64(value)) => {
+                        Some((name.to_string(), value))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect()
 }
 
+/// The name of the constant through which the ID of a contract is provided to the contract's
+/// dependents, as well as to the contract's own tests.
+pub const CONTRACT_ID_CONSTANT_NAME: &str = "CONTRACT_ID";
+
 /// The suffix that helps identify the file which contains the hash of the binary file created when
 /// scripts are built_package.
 pub const SWAY_BIN_HASH_SUFFIX: &str = "-bin-hash";
@@ -2507,12 +3346,13 @@ pub fn build_with_options(build_options: BuildOpts) -> Result<Built> {
         bail!("No member found to build")
     }
     let lock_path = manifest_file.lock_path()?;
-    let build_plan = BuildPlan::from_lock_and_manifests(
+    let mut build_plan = BuildPlan::from_lock_and_manifests(
         &lock_path,
         &member_manifests,
         build_options.pkg.locked,
         build_options.pkg.offline,
     )?;
+    build_plan.select_features(&build_options.features)?;
     let graph = build_plan.graph();
     let manifest_map = build_plan.manifest_map();
     let build_profiles: HashMap<String, BuildProfile> = build_plan.build_profiles().collect();
@@ -2531,7 +3371,12 @@ pub fn build_with_options(build_options: BuildOpts) -> Result<Built> {
     };
     // Build it!
     let mut built_workspace = HashMap::new();
-    let built_packages = build(&build_plan, &build_profile, &outputs)?;
+    let built_packages = build(
+        &build_plan,
+        &build_profile,
+        &outputs,
+        !build_options.no_cache,
+    )?;
     let output_dir = pkg.output_directory.as_ref().map(PathBuf::from);
     for (node_ix, built_package) in built_packages.into_iter() {
         let pinned = &graph[node_ix];
@@ -2563,11 +3408,32 @@ pub fn build_with_options(build_options: BuildOpts) -> Result<Built> {
     }
 }
 
+/// The contract as it is deployed, i.e. built without its tests.
+fn deployed_contract(built_package: &BuiltPackage) -> BuiltPackage {
+    let mut deployed = built_package.clone();
+    if let Some(bytecode) = &built_package.bytecode_without_tests {
+        deployed.bytecode = bytecode.clone();
+    }
+    if let Some(source_map) = &built_package.source_map_without_tests {
+        deployed.source_map = source_map.clone();
+    }
+    deployed
+}
+
 /// Returns the ContractId of a built_package contract with specified `salt`.
 fn contract_id(built_package: &BuiltPackage, salt: &fuel_tx::Salt) -> ContractId {
+    contract_id_from_bytecode(&built_package.bytecode, &built_package.storage_slots, salt)
+}
+
+/// Returns the ContractId of the contract with the given bytecode, storage slots and `salt`.
+fn contract_id_from_bytecode(
+    bytecode: &[u8],
+    storage_slots: &[StorageSlot],
+    salt: &fuel_tx::Salt,
+) -> ContractId {
     // Construct the contract ID
-    let contract = Contract::from(built_package.bytecode.clone());
-    let mut storage_slots = built_package.storage_slots.clone();
+    let contract = Contract::from(bytecode.to_vec());
+    let mut storage_slots = storage_slots.to_vec();
     storage_slots.sort();
     let state_root = Contract::initial_state_root(storage_slots.iter());
     contract.id(salt, &contract.root(), &state_root)
@@ -2603,10 +3469,14 @@ fn validate_contract_deps(graph: &Graph) -> Result<()> {
 /// This compiles all packages (including dependencies) in the order specified by the `BuildPlan`.
 ///
 /// Also returns the resulting `sway_core::SourceMap` which may be useful for debugging purposes.
+///
+/// If `use_cache` is `true`, packages that are unchanged since they were last compiled are reused
+/// from the build cache where possible, see [crate::cache_directory].
 pub fn build(
     plan: &BuildPlan,
     profile: &BuildProfile,
     outputs: &HashSet<NodeIx>,
+    use_cache: bool,
 ) -> anyhow::Result<Vec<(NodeIx, BuiltPackage)>> {
     let mut built_packages = Vec::new();
 
@@ -2619,6 +3489,40 @@ pub fn build(
     let declaration_engine = DeclarationEngine::default();
     let engines = Engines::new(&type_engine, &declaration_engine);
 
+    // Packages that are not being output are only built as dependencies, so their tests are
+    // never run.
+    let dep_profile = BuildProfile {
+        include_tests: false,
+        ..profile.clone()
+    };
+
+    // Determine which of the required packages may be reused from the build cache. Packages built
+    // with their tests are never cached, as the tests are only produced during compilation.
+    let cache = use_cache.then(|| BuildCache::new(&dep_profile)).flatten();
+    let cache_keys = match &cache {
+        Some(cache) => build_cache_keys(plan, &required, cache)?,
+        None => HashMap::default(),
+    };
+    let cacheable = |node: &NodeIx| !(profile.include_tests && outputs.contains(node));
+    let mut cached_pkgs: HashMap<NodeIx, CachedPackage> = match &cache {
+        Some(cache) => cache_keys
+            .iter()
+            .filter(|(node, _)| cacheable(node))
+            .filter_map(|(&node, key)| {
+                let cached = cache.load(&plan.graph()[node].name, key)?;
+                Some((node, cached))
+            })
+            .collect(),
+        None => HashMap::default(),
+    };
+    let cached_nodes = cached_pkgs.keys().cloned().collect();
+    let build_kinds = build_kinds(
+        plan.graph(),
+        &plan.compilation_order,
+        &required,
+        &cached_nodes,
+    );
+
     let mut lib_namespace_map = Default::default();
     let mut compiled_contract_deps = HashMap::new();
     for &node in plan
@@ -2626,47 +3530,88 @@ pub fn build(
         .iter()
         .filter(|node| required.contains(node))
     {
-        let mut source_map = SourceMap::new();
         let pkg = &plan.graph()[node];
         let manifest = &plan.manifest_map()[&pkg.id()];
         let constants = manifest.config_time_constants();
-        let dep_namespace = match dependency_namespace(
+        let is_contract_dependency = is_contract_dependency(plan.graph(), node);
+        let pkg_profile = match outputs.contains(&node) {
+            true => profile.clone(),
+            false => dep_profile.clone(),
+        };
+        let features = plan.features(node);
+        let dep_namespace = || match dependency_namespace(
             &lib_namespace_map,
             &compiled_contract_deps,
             &plan.graph,
             node,
-            constants,
+            constants.clone(),
             engines,
         ) {
-            Ok(o) => o,
+            Ok(o) => Ok(o),
             Err(errs) => {
                 print_on_failure(profile.terse, &[], &errs);
                 bail!("Failed to compile {}", pkg.name);
             }
         };
-        let res = compile(
-            pkg,
-            manifest,
-            profile,
-            dep_namespace,
-            engines,
-            &mut source_map,
-        )?;
-        let (mut built_package, namespace) = res;
+
+        let build_kind = build_kinds[&node];
+        let mut built_package = match cached_pkgs.remove(&node) {
+            Some(cached) if build_kind != BuildKind::Compile => {
+                if build_kind == BuildKind::CachedWithNamespace {
+                    let namespace = check_namespace(
+                        pkg,
+                        manifest,
+                        &pkg_profile,
+                        features,
+                        dep_namespace()?,
+                        engines,
+                    )?;
+                    lib_namespace_map.insert(node, namespace);
+                }
+                if !profile.terse {
+                    let warnings = match cached.warnings {
+                        0 => String::new(),
+                        1 => " with 1 warning".to_string(),
+                        n => format!(" with {} warnings", n),
+                    };
+                    info!("  Reusing cached build of {:?}{}.", pkg.name, warnings);
+                }
+                built_package_from_cache(pkg, manifest, cached)?
+            }
+            _ => {
+                let dep_namespace = dep_namespace()?;
+                let mut source_map = SourceMap::new();
+                let (mut built_package, namespace) = compile(
+                    pkg,
+                    manifest,
+                    &pkg_profile,
+                    features,
+                    dep_namespace,
+                    engines,
+                    &mut source_map,
+                )?;
+                if let TreeType::Library { .. } = built_package.tree_type {
+                    lib_namespace_map.insert(node, namespace.into());
+                }
+                source_map.insert_dependency(manifest.dir());
+                standardize_json_abi_types(&mut built_package.json_abi_program);
+                let key = cache_keys.get(&node).filter(|_| cacheable(&node));
+                if let (Some(cache), Some(key)) = (&cache, key) {
+                    let cached = cached_package(&built_package);
+                    if let Err(e) = cache.store(&pkg.name, key, &cached) {
+                        warn!("failed to cache the build of {:?}: {}", pkg.name, e);
+                    }
+                }
+                built_package
+            }
+        };
         // If the current node is a contract dependency, collect the contract_id
-        if plan
-            .graph()
-            .edges_directed(node, Direction::Incoming)
-            .any(|e| matches!(e.weight().kind, DepKind::Contract { .. }))
-        {
-            compiled_contract_deps.insert(node, built_package.clone());
-        }
-        if let TreeType::Library { .. } = built_package.tree_type {
-            lib_namespace_map.insert(node, namespace.into());
+        if is_contract_dependency {
+            compiled_contract_deps.insert(node, deployed_contract(&built_package));
         }
-        source_map.insert_dependency(manifest.dir());
-        standardize_json_abi_types(&mut built_package.json_abi_program);
         if outputs.contains(&node) {
+            built_package.contract_dependencies =
+                built_contract_dependencies(plan, node, &compiled_contract_deps);
             built_packages.push((node, built_package));
         }
     }
@@ -2674,6 +3619,195 @@ pub fn build(
     Ok(built_packages)
 }
 
+/// Produce the build cache key for each of the required nodes.
+fn build_cache_keys(
+    plan: &BuildPlan,
+    required: &HashSet<NodeIx>,
+    cache: &BuildCache,
+) -> Result<HashMap<NodeIx, String>> {
+    let graph = plan.graph();
+    let mut keys: HashMap<NodeIx, String> = HashMap::new();
+    for &node in plan
+        .compilation_order
+        .iter()
+        .filter(|node| required.contains(node))
+    {
+        let manifest = &plan.manifest_map()[&graph[node].id()];
+        let mut deps: Vec<_> = graph
+            .edges_directed(node, Direction::Outgoing)
+            .map(|edge| CacheDep {
+                name: &edge.weight().name,
+                kind: format!("{:?}", edge.weight().kind),
+                key: &keys[&edge.target()],
+            })
+            .collect();
+        deps.sort_by(|a, b| a.name.cmp(b.name));
+        let key = cache.key(manifest, plan.features(node), &deps)?;
+        keys.insert(node, key);
+    }
+    Ok(keys)
+}
+
+/// How a required package is built, see [build_kinds].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum BuildKind {
+    /// The package's output is reused from the build cache.
+    Cached,
+    /// The package's output is reused from the build cache, though the package is type-checked in
+    /// order to produce the namespace against which its dependents are compiled.
+    CachedWithNamespace,
+    /// The package is compiled from source.
+    Compile,
+}
+
+/// Determine how each of the required nodes is built given the set of nodes with cached output.
+///
+/// Nodes without cached output are compiled. The namespace of a library is required in order to
+/// compile its dependents, so a cached library is also type-checked if any package depending on it
+/// is compiled or type-checked.
+fn build_kinds(
+    graph: &Graph,
+    compilation_order: &[NodeIx],
+    required: &HashSet<NodeIx>,
+    cached: &HashSet<NodeIx>,
+) -> HashMap<NodeIx, BuildKind> {
+    let mut kinds = HashMap::new();
+    // Visit dependents before their dependencies.
+    for &node in compilation_order
+        .iter()
+        .rev()
+        .filter(|node| required.contains(node))
+    {
+        let namespace_required = graph.edges_directed(node, Direction::Incoming).any(|edge| {
+            matches!(edge.weight().kind, DepKind::Library)
+                && matches!(
+                    kinds.get(&edge.source()),
+                    Some(BuildKind::Compile | BuildKind::CachedWithNamespace)
+                )
+        });
+        let kind = match (cached.contains(&node), namespace_required) {
+            (false, _) => BuildKind::Compile,
+            (true, true) => BuildKind::CachedWithNamespace,
+            (true, false) => BuildKind::Cached,
+        };
+        kinds.insert(node, kind);
+    }
+    kinds
+}
+
+/// Type-check the given package, returning the namespace against which its dependents are
+/// compiled.
+fn check_namespace(
+    pkg: &Pinned,
+    manifest: &PackageManifestFile,
+    build_profile: &BuildProfile,
+    features: &BTreeSet<String>,
+    namespace: namespace::Module,
+    engines: Engines<'_>,
+) -> Result<namespace::Module> {
+    let ast_res = compile_ast(engines, manifest, build_profile, features, namespace)?;
+    match ast_res.value {
+        Some(typed_program) if ast_res.errors.is_empty() => Ok(typed_program.root.namespace),
+        _ => {
+            print_on_failure(build_profile.terse, &ast_res.warnings, &ast_res.errors);
+            bail!("Failed to compile {}", pkg.name);
+        }
+    }
+}
+
+/// The output of the given compiled package to store within the build cache.
+fn cached_package(built_package: &BuiltPackage) -> CachedPackage {
+    let entries = built_package
+        .entries
+        .iter()
+        .map(|entry| CachedEntry {
+            fn_name: entry.fn_name.clone(),
+            imm: entry.imm,
+            selector: entry.selector,
+        })
+        .collect();
+    CachedPackage {
+        json_abi_program: built_package.json_abi_program.clone(),
+        storage_slots: built_package.storage_slots.clone(),
+        bytecode: built_package.bytecode.clone(),
+        entries,
+        source_map: built_package.source_map.clone(),
+        warnings: built_package.warnings.len(),
+    }
+}
+
+/// Reconstruct the built package from the output stored within the build cache.
+fn built_package_from_cache(
+    pkg: &Pinned,
+    manifest: &PackageManifestFile,
+    cached: CachedPackage,
+) -> Result<BuiltPackage> {
+    let entries = cached
+        .entries
+        .into_iter()
+        .map(|entry| FinalizedEntry {
+            fn_name: entry.fn_name,
+            imm: entry.imm,
+            selector: entry.selector,
+            test_decl_id: None,
+        })
+        .collect();
+    Ok(BuiltPackage {
+        json_abi_program: cached.json_abi_program,
+        json_abi_tests: fuels_types::ProgramABI::default(),
+        storage_slots: cached.storage_slots,
+        bytecode: cached.bytecode,
+        entries,
+        tree_type: manifest.program_type()?,
+        source_map: cached.source_map,
+        pkg_name: pkg.name.clone(),
+        declaration_engine: DeclarationEngine::default(),
+        bytecode_without_tests: None,
+        source_map_without_tests: None,
+        contract_dependencies: vec![],
+        test_environment: manifest.test.clone().unwrap_or_default(),
+        manifest_dir: manifest.dir().to_path_buf(),
+        warnings: vec![],
+        error_signals: BTreeMap::new(),
+    })
+}
+
+/// Whether or not the given node is a contract dependency of any other package within the graph.
+fn is_contract_dependency(graph: &Graph, node: NodeIx) -> bool {
+    graph
+        .edges_directed(node, Direction::Incoming)
+        .any(|e| matches!(e.weight().kind, DepKind::Contract { .. }))
+}
+
+/// Collect all contract dependencies of the given node, direct and transitive, ready for
+/// deployment.
+fn built_contract_dependencies(
+    plan: &BuildPlan,
+    node: NodeIx,
+    compiled_contract_deps: &HashMap<NodeIx, BuiltPackage>,
+) -> Vec<BuiltContractDependency> {
+    plan.node_deps(node)
+        .filter(|&dep_node| dep_node != node)
+        .filter_map(|dep_node| {
+            let compiled = compiled_contract_deps.get(&dep_node)?;
+            let salt = plan
+                .graph()
+                .edges_directed(dep_node, Direction::Incoming)
+                .find_map(|e| match e.weight().kind {
+                    DepKind::Contract { salt } => Some(salt),
+                    DepKind::Library => None,
+                })?;
+            Some(BuiltContractDependency {
+                pkg_name: compiled.pkg_name.clone(),
+                bytecode: compiled.bytecode.clone(),
+                storage_slots: compiled.storage_slots.clone(),
+                salt,
+                contract_id: contract_id(compiled, &salt),
+            })
+        })
+        .collect()
+}
+
 /// Standardize the JSON ABI data structure by eliminating duplicate types. This is an iterative
 /// process because every time two types are merged, new opportunities for more merging arise.
 fn standardize_json_abi_types(json_abi_program: &mut fuels_types::ProgramABI) {
@@ -2820,7 +3954,7 @@ pub fn check(
         let pkg = &plan.graph[node];
         let manifest = &plan.manifest_map()[&pkg.id()];
         let constants = manifest.config_time_constants();
-        let dep_namespace = dependency_namespace(
+        let mut dep_namespace = dependency_namespace(
             &lib_namespace_map,
             &compiled_contract_deps,
             &plan.graph,
@@ -2829,11 +3963,21 @@ pub fn check(
             engines,
         )
         .expect("failed to create dependency namespace");
+        // As contracts are not compiled, their own tests are provided a placeholder contract ID.
+        if let Ok(TreeType::Contract) = manifest.program_type() {
+            insert_contract_id_for_tests(
+                &mut dep_namespace,
+                manifest,
+                &ContractId::default(),
+                engines,
+            )
+            .expect("failed to provide the contract ID to tests");
+        }
         let CompileResult {
             value,
             mut warnings,
             mut errors,
-        } = parse(manifest, terse_mode, engines)?;
+        } = parse(manifest, plan.features(node), terse_mode, engines)?;
 
         let parse_program = match value {
             None => {
@@ -2876,6 +4020,7 @@ pub fn check(
 /// Returns a parsed AST from the supplied [PackageManifestFile]
 pub fn parse(
     manifest: &PackageManifestFile,
+    features: &BTreeSet<String>,
     terse_mode: bool,
     engines: Engines<'_>,
 ) -> anyhow::Result<CompileResult<ParseProgram>> {
@@ -2884,7 +4029,8 @@ pub fn parse(
         ..BuildProfile::debug()
     };
     let source = manifest.entry_string()?;
-    let sway_build_config = sway_build_config(manifest.dir(), &manifest.entry_path(), &profile)?;
+    let sway_build_config =
+        sway_build_config(manifest.dir(), &manifest.entry_path(), &profile, features)?;
     Ok(sway_core::parse(source, engines, Some(&sway_build_config)))
 }
 
@@ -2990,6 +4136,448 @@ fn test_source_git_pinned_parsing() {
     }
 }
 
+#[test]
+fn test_source_registry_pinned_parsing() {
+    let checksum = "a".repeat(64);
+    let string = format!("registry+1.4.2#{}", checksum);
+    let expected = SourcePinned::Registry(SourceRegistryPinned {
+        version: semver::Version::new(1, 4, 2),
+        checksum: Some(checksum),
+    });
+    assert_eq!(expected.to_string(), string);
+    assert_eq!(string.parse::<SourcePinned>().unwrap(), expected);
+
+    // Neither `registry+<version>` nor the legacy `registry+<version_req>#<version>` record a
+    // checksum, and the version requirement of the latter is discarded.
+    let expected = SourcePinned::Registry(SourceRegistryPinned {
+        version: semver::Version::new(1, 4, 2),
+        checksum: None,
+    });
+    assert_eq!(expected.to_string(), "registry+1.4.2");
+    for string in ["registry+1.4.2", "registry+>=1.2,<1.5#1.4.2"] {
+        assert_eq!(string.parse::<SourcePinned>().unwrap(), expected);
+    }
+
+    assert!("registry+1.4.2#not-a-checksum"
+        .parse::<SourceRegistryPinned>()
+        .is_err());
+}
+
+/// Write a library package with the given version and dependencies within `dir/name`.
+#[cfg(test)]
+fn test_pkg_manifest(
+    dir: &Path,
+    name: &str,
+    version: Option<&str>,
+    deps: &[(&str, &str)],
+) -> PackageManifestFile {
+    let pkg_dir = dir.join(name);
+    fs::create_dir_all(pkg_dir.join(constants::SRC_DIR)).unwrap();
+    fs::write(
+        pkg_dir.join(constants::SRC_DIR).join("lib.sw"),
+        format!("library {};\n", name),
+    )
+    .unwrap();
+    let mut toml = format!(
+        "[project]\nname = \"{}\"\nlicense = \"Apache-2.0\"\nentry = \"lib.sw\"\nimplicit-std = false\n",
+        name
+    );
+    if let Some(version) = version {
+        toml.push_str(&format!("version = \"{}\"\n", version));
+    }
+    toml.push_str("\n[dependencies]\n");
+    for (dep, entry) in deps {
+        toml.push_str(&format!("{} = {}\n", dep, entry));
+    }
+    fs::write(pkg_dir.join(constants::MANIFEST_FILE_NAME), toml).unwrap();
+    PackageManifestFile::from_dir(&pkg_dir).unwrap()
+}
+
+/// A registry within `dir` that publishes the given versions of `foo`.
+#[cfg(test)]
+fn test_registry(dir: &Path, versions: &[&str]) -> crate::registry::Registry {
+    let mut index = crate::registry::RegistryIndex::default();
+    let entries = versions
+        .iter()
+        .map(|version| crate::registry::IndexEntry {
+            version: version.parse().unwrap(),
+            checksum: String::new(),
+            yanked: false,
+        })
+        .collect();
+    index.packages.insert("foo".to_string(), entries);
+    let registry = crate::registry::Registry::new(dir.join("registry"));
+    fs::create_dir_all(registry.path()).unwrap();
+    fs::write(
+        registry.index_path(),
+        serde_json::to_string(&index).unwrap(),
+    )
+    .unwrap();
+    registry
+}
+
+/// Construct the following graph, in which `foo` is a registry package pinned to the given version
+/// and all other packages are path dependencies of the `app` member:
+///
+/// ```ignore
+///      app
+///     / | \
+///    a  c  |
+///     \ /  |
+///      b   |
+///       \  |
+///        foo
+/// ```
+///
+/// Returns the graph along with the manifest of each package and the index of the `foo` node.
+#[cfg(test)]
+fn test_diamond_graph(
+    dir: &Path,
+    foo_version: &str,
+    app_foo_req: &str,
+    b_foo_req: &str,
+) -> (Graph, ManifestMap, NodeIx) {
+    let path = |name: &str| format!("{{ path = \"../{}\" }}", name);
+    let manifests = [
+        test_pkg_manifest(
+            dir,
+            "app",
+            None,
+            &[("a", &path("a")), ("c", &path("c")), ("foo", app_foo_req)],
+        ),
+        test_pkg_manifest(dir, "a", None, &[("b", &path("b"))]),
+        test_pkg_manifest(dir, "c", None, &[("b", &path("b"))]),
+        test_pkg_manifest(dir, "b", None, &[("foo", b_foo_req)]),
+    ];
+    let mut graph = Graph::default();
+    let mut manifest_map = ManifestMap::default();
+    let app = graph.add_node(Pinned {
+        name: "app".to_string(),
+        source: SourcePinned::Member,
+    });
+    let path_root = graph[app].id();
+    let mut nodes = vec![app];
+    for name in ["a", "c", "b"] {
+        let source = SourcePinned::Path(SourcePathPinned { path_root });
+        let name = name.to_string();
+        nodes.push(graph.add_node(Pinned { name, source }));
+    }
+    for (&node, manifest) in nodes.iter().zip(manifests) {
+        manifest_map.insert(graph[node].id(), manifest);
+    }
+    let foo = graph.add_node(Pinned {
+        name: "foo".to_string(),
+        source: SourcePinned::Registry(SourceRegistryPinned {
+            version: foo_version.parse().unwrap(),
+            checksum: None,
+        }),
+    });
+    let [app, a, c, b] = [nodes[0], nodes[1], nodes[2], nodes[3]];
+    for (parent, dep) in [(app, a), (app, c), (a, b), (c, b), (app, foo), (b, foo)] {
+        let name = graph[dep].name.clone();
+        graph.add_edge(parent, dep, Edge::new(name, DepKind::Library));
+    }
+    (graph, manifest_map, foo)
+}
+
+#[test]
+fn test_dependent_chains_of_diamond() {
+    let dir = tempfile::tempdir().unwrap();
+    let (graph, _, foo) = test_diamond_graph(dir.path(), "1.0.0", "\"1\"", "\"1\"");
+    let b = graph
+        .node_indices()
+        .find(|&n| graph[n].name == "b")
+        .unwrap();
+    assert_eq!(
+        dependent_chains(&graph, b),
+        ["app -> a -> b", "app -> c -> b"]
+    );
+    assert_eq!(
+        dependent_chains(&graph, foo),
+        ["app -> a -> b -> foo", "app -> c -> b -> foo", "app -> foo"]
+    );
+}
+
+#[test]
+fn test_resolve_versions_reselects_registry_package() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = test_registry(dir.path(), &["1.0.0", "1.1.0", "1.2.0", "2.0.0"]);
+    let (graph, manifest_map, foo) =
+        test_diamond_graph(dir.path(), "1.0.0", "\"^1.1\"", "\"<1.2\"");
+    let mut registry_versions = registry_versions(&graph);
+    let mut attempted = HashSet::default();
+    let reselected = resolve_versions(
+        &graph,
+        &manifest_map,
+        &registry,
+        &mut registry_versions,
+        &mut attempted,
+    )
+    .unwrap();
+    let expected: BTreeSet<_> = graph
+        .edges_directed(foo, Direction::Incoming)
+        .map(|edge| edge.id())
+        .collect();
+    assert_eq!(reselected, expected);
+    assert_eq!(registry_versions["foo"], semver::Version::new(1, 1, 0));
+
+    // A graph that satisfies all requirements is left as is.
+    let (graph, manifest_map, _) = test_diamond_graph(dir.path(), "1.1.0", "\"^1.1\"", "\"<1.2\"");
+    let reselected = resolve_versions(
+        &graph,
+        &manifest_map,
+        &registry,
+        &mut registry_versions,
+        &mut attempted,
+    )
+    .unwrap();
+    assert!(reselected.is_empty());
+}
+
+#[test]
+fn test_resolve_versions_reports_conflicting_chains() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = test_registry(dir.path(), &["1.0.0", "2.0.0"]);
+    let (graph, manifest_map, _) = test_diamond_graph(dir.path(), "1.0.0", "\"2\"", "\"1\"");
+    let err = resolve_versions(
+        &graph,
+        &manifest_map,
+        &registry,
+        &mut registry_versions(&graph),
+        &mut HashSet::default(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "failed to select a version of `foo` that satisfies the requirements of all dependents:\n    \
+        `^1` required by app -> a -> b\n    \
+        `^1` required by app -> c -> b\n    \
+        `^2` required by app"
+    );
+}
+
+#[test]
+fn test_resolve_versions_reports_unsatisfied_path_version() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = test_registry(dir.path(), &["1.0.0"]);
+    let (graph, mut manifest_map, _) = test_diamond_graph(dir.path(), "1.0.0", "\"1\"", "\"1\"");
+    let a = graph
+        .node_indices()
+        .find(|&n| graph[n].name == "a")
+        .unwrap();
+    let b = graph
+        .node_indices()
+        .find(|&n| graph[n].name == "b")
+        .unwrap();
+    manifest_map.insert(
+        graph[a].id(),
+        test_pkg_manifest(
+            dir.path(),
+            "a",
+            None,
+            &[("b", "{ path = \"../b\", version = \"2\" }")],
+        ),
+    );
+    manifest_map.insert(
+        graph[b].id(),
+        test_pkg_manifest(dir.path(), "b", Some("1.0.0"), &[("foo", "\"1\"")]),
+    );
+    let err = resolve_versions(
+        &graph,
+        &manifest_map,
+        &registry,
+        &mut registry_versions(&graph),
+        &mut HashSet::default(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "package `b` version 1.0.0 does not satisfy the requirements of all dependents:\n    \
+        `^2` required by app -> a"
+    );
+}
+
+#[test]
+fn test_validate_source_conflicts_reports_chains() {
+    let dir = tempfile::tempdir().unwrap();
+    let (mut graph, mut manifest_map, _) =
+        test_diamond_graph(dir.path(), "1.0.0", "\"1\"", "\"1\"");
+    validate_source_conflicts(&graph, &manifest_map).unwrap();
+
+    // `a` additionally depends on a `foo` located at a path.
+    let find = |graph: &Graph, name: &str| {
+        graph
+            .node_indices()
+            .find(|&n| graph[n].name == name)
+            .unwrap()
+    };
+    let (app, a) = (find(&graph, "app"), find(&graph, "a"));
+    let source = SourcePinned::Path(SourcePathPinned {
+        path_root: graph[app].id(),
+    });
+    let foo = graph.add_node(Pinned {
+        name: "foo".to_string(),
+        source,
+    });
+    let foo_manifest = test_pkg_manifest(dir.path(), "foo", None, &[]);
+    let foo_dir = foo_manifest.dir().to_path_buf();
+    manifest_map.insert(graph[foo].id(), foo_manifest);
+    graph.add_edge(a, foo, Edge::new("foo".to_string(), DepKind::Library));
+    let err = validate_source_conflicts(&graph, &manifest_map).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "package `foo` resolves to multiple sources within the dependencies of `app`:\n    \
+            path `{}` required by app -> a\n    \
+            registry version 1.0.0 required by app\n    \
+            registry version 1.0.0 required by app -> a -> b\n    \
+            registry version 1.0.0 required by app -> c -> b",
+            foo_dir.display()
+        )
+    );
+}
+
+#[test]
+fn test_build_kinds_reuse_std_after_member_edit() {
+    // app -> lib -> std -> core, where app also depends on std and on the contract counter.
+    let mut graph = Graph::default();
+    let mut add = |name: &str| {
+        graph.add_node(Pinned {
+            name: name.to_string(),
+            source: SourcePinned::Member,
+        })
+    };
+    let [core, std, counter, lib, app] = ["core", "std", "counter", "lib", "app"].map(&mut add);
+    let library = |name: &str| Edge::new(name.to_string(), DepKind::Library);
+    graph.add_edge(std, core, library("core"));
+    graph.add_edge(lib, std, library("std"));
+    graph.add_edge(counter, std, library("std"));
+    graph.add_edge(app, lib, library("lib"));
+    graph.add_edge(app, std, library("std"));
+    let salt = fuel_tx::Salt::zeroed();
+    graph.add_edge(
+        app,
+        counter,
+        Edge::new("counter".to_string(), DepKind::Contract { salt }),
+    );
+    let order = [core, std, counter, lib, app];
+    let required = HashSet::from(order);
+    let kinds = |cached: &[NodeIx]| {
+        let cached = cached.iter().cloned().collect();
+        let kinds = build_kinds(&graph, &order, &required, &cached);
+        order.map(|node| kinds[&node])
+    };
+
+    // Nothing is compiled when all packages are unchanged.
+    use BuildKind::*;
+    assert_eq!(kinds(&order), [Cached; 5]);
+
+    // After editing the member, its library dependencies are only type-checked, while the contract
+    // dependency is reused as is.
+    assert_eq!(
+        kinds(&[core, std, counter, lib]),
+        [
+            CachedWithNamespace,
+            CachedWithNamespace,
+            Cached,
+            CachedWithNamespace,
+            Compile
+        ]
+    );
+
+    // Editing the contract dependency changes the key of its dependent, so both are compiled.
+    assert_eq!(
+        kinds(&[core, std, lib]),
+        [
+            CachedWithNamespace,
+            CachedWithNamespace,
+            Compile,
+            CachedWithNamespace,
+            Compile
+        ]
+    );
+}
+
+#[test]
+fn test_select_features_propagates_to_dependencies() {
+    let dir = tempfile::tempdir().unwrap();
+    let write_pkg = |name: &str, deps: &str, features: &str| {
+        let pkg_dir = dir.path().join(name);
+        fs::create_dir_all(pkg_dir.join(constants::SRC_DIR)).unwrap();
+        fs::write(
+            pkg_dir.join(constants::SRC_DIR).join("lib.sw"),
+            format!("library {};\n", name),
+        )
+        .unwrap();
+        let toml = format!(
+            "[project]\nname = \"{}\"\nlicense = \"Apache-2.0\"\nentry = \"lib.sw\"\n\
+            implicit-std = false\n\n[dependencies]\n{}\n[features]\n{}",
+            name, deps, features
+        );
+        fs::write(pkg_dir.join(constants::MANIFEST_FILE_NAME), toml).unwrap();
+        PackageManifestFile::from_dir(&pkg_dir).unwrap()
+    };
+    let app = write_pkg(
+        "app",
+        "lib = { path = \"../lib\", features = [\"a\"] }\n\
+        util = { path = \"../util\", default-features = false }\n",
+        "default = [\"fast\"]\nfast = [\"lib/b\"]\nextra = [\"util/x\"]\n\
+        broken = [\"util/missing\"]\n",
+    );
+    write_pkg("lib", "", "default = [\"c\"]\na = []\nb = []\nc = []\n");
+    write_pkg("util", "", "default = [\"d\"]\nd = []\nx = []\n");
+    let members = MemberManifestFiles::from([("app".to_string(), app)]);
+    let mut plan = BuildPlan::from_manifests(&members, false).unwrap();
+
+    let mut features = |opts: FeatureOpts| {
+        plan.select_features(&opts)?;
+        let features = ["app", "lib", "util"].map(|name| {
+            let node = plan
+                .graph()
+                .node_indices()
+                .find(|&n| plan.graph()[n].name == name)
+                .unwrap();
+            plan.features(node)
+                .iter()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(",")
+        });
+        Ok::<_, Error>(features)
+    };
+    let opts = |features: &[&str], all_features, no_default_features| FeatureOpts {
+        features: features.iter().map(|s| s.to_string()).collect(),
+        all_features,
+        no_default_features,
+    };
+
+    // The dependency declarations of `app` request `a` of `lib` and none of the `default` features
+    // of `util`, while `app`'s own default feature enables `lib/b`.
+    assert_eq!(
+        features(opts(&[], false, false)).unwrap(),
+        ["default,fast", "a,b,c,default", ""]
+    );
+    assert_eq!(
+        features(opts(&["extra"], false, false)).unwrap(),
+        ["default,extra,fast", "a,b,c,default", "x"]
+    );
+    // Disabling the default features of the member does not affect the dependency declarations.
+    assert_eq!(
+        features(opts(&[], false, true)).unwrap(),
+        ["", "a,c,default", ""]
+    );
+    assert_eq!(
+        features(opts(&[], true, true)).unwrap_err().to_string(),
+        "package `util` does not declare the feature `missing`, required by app -> util"
+    );
+    assert_eq!(
+        features(opts(&["unknown"], false, false))
+            .unwrap_err()
+            .to_string(),
+        "the feature `unknown` is not declared under `[features]` by any of the packages being built"
+    );
+}
+
 /// Format an error message for an absent `Forc.toml`.
 pub fn manifest_file_missing(dir: &Path) -> anyhow::Error {
     let message = format!(
@@ -0,0 +1,539 @@
+//! Package registries backed by an index on the local file system.
+//!
+//! A registry is a directory with the following layout:
+//!
+//! ```ignore
+//! <registry>/
+//!     index.json
+//!     packages/
+//!         <name>-<version>.tar.gz
+//! ```
+//!
+//! The index lists the available versions of each package along with the SHA-256 checksum of each
+//! version's archive. Each archive contains the package's sources under a single top-level
+//! `<name>-<version>` directory, with a `Forc.toml` at its root. Archives are produced from a
+//! package's sources via [package_archive].
+//!
+//! The registry in use is determined by the `FORC_REGISTRY` environment variable, falling back to
+//! `$HOME/.forc/registry/local`.
+
+use crate::manifest::PackageManifestFile;
+use anyhow::{anyhow, bail, Context, Result};
+use forc_util::user_forc_directory;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use sway_utils::constants;
+
+/// The environment variable used to specify the path to the registry.
+pub const REGISTRY_ENV_VAR: &str = "FORC_REGISTRY";
+/// The name of the index file at the root of a registry.
+pub const INDEX_FILE_NAME: &str = "index.json";
+/// The name of the directory within a registry containing package archives.
+pub const PACKAGES_DIR_NAME: &str = "packages";
+/// The file extension of package archives.
+pub const ARCHIVE_EXTENSION: &str = "tar.gz";
+/// The name of the file recording the checksum of the archive from which a package was extracted.
+pub const CHECKSUM_FILE_NAME: &str = ".forc-checksum";
+
+/// A package registry located on the local file system.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Registry {
+    path: PathBuf,
+    src_root: PathBuf,
+}
+
+/// The index of all packages published to a registry.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct RegistryIndex {
+    /// The published versions of each package, by package name.
+    pub packages: BTreeMap<String, Vec<IndexEntry>>,
+}
+
+/// A single published version of a package.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IndexEntry {
+    pub version: semver::Version,
+    /// The hex-encoded SHA-256 checksum of the package archive.
+    pub checksum: String,
+    /// Yanked versions are never selected when resolving a version requirement, though they
+    /// remain available to lock files that already depend on them.
+    #[serde(default)]
+    pub yanked: bool,
+}
+
+impl Registry {
+    /// The registry at the given path.
+    ///
+    /// Packages fetched from the registry are extracted under `$HOME/.forc/registry/src`.
+    pub fn new(path: PathBuf) -> Self {
+        let src_root = user_forc_directory().join("registry").join("src");
+        Self { path, src_root }
+    }
+
+    /// Extract packages fetched from the registry under the given directory rather than under
+    /// `$HOME/.forc/registry/src`.
+    pub fn with_src_root(self, src_root: PathBuf) -> Self {
+        Self { src_root, ..self }
+    }
+
+    /// The registry specified by the `FORC_REGISTRY` environment variable, or the default local
+    /// registry under the user's forc directory.
+    pub fn from_env() -> Self {
+        match std::env::var_os(REGISTRY_ENV_VAR) {
+            Some(path) => Self::new(PathBuf::from(path)),
+            None => Self::new(user_forc_directory().join("registry").join("local")),
+        }
+    }
+
+    /// The path to the root of the registry.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The path to the registry's index file.
+    pub fn index_path(&self) -> PathBuf {
+        self.path.join(INDEX_FILE_NAME)
+    }
+
+    /// The path to the archive for the given version of the given package.
+    pub fn archive_path(&self, name: &str, version: &semver::Version) -> PathBuf {
+        self.path.join(PACKAGES_DIR_NAME).join(format!(
+            "{}.{}",
+            archive_dir_name(name, version),
+            ARCHIVE_EXTENSION
+        ))
+    }
+
+    /// Load the registry's index.
+    ///
+    /// A registry without an index file is treated as empty.
+    pub fn index(&self) -> Result<RegistryIndex> {
+        let path = self.index_path();
+        if !path.exists() {
+            return Ok(RegistryIndex::default());
+        }
+        let string = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read registry index {}", path.display()))?;
+        serde_json::from_str(&string)
+            .with_context(|| format!("failed to parse registry index {}", path.display()))
+    }
+
+    /// Select the greatest version of the named package that satisfies all of the given
+    /// requirements.
+    pub fn resolve(&self, name: &str, reqs: &[semver::VersionReq]) -> Result<IndexEntry> {
+        let index = self.index()?;
+        let entries = index.entries(name);
+        if entries.is_empty() {
+            bail!(
+                "no package named `{}` found in registry {}",
+                name,
+                self.path.display()
+            );
+        }
+        entries
+            .iter()
+            .filter(|entry| !entry.yanked && reqs.iter().all(|req| req.matches(&entry.version)))
+            .max_by(|a, b| a.version.cmp(&b.version))
+            .cloned()
+            .ok_or_else(|| {
+                let reqs: Vec<_> = reqs.iter().map(|req| format!("`{}`", req)).collect();
+                let available: Vec<_> = entries
+                    .iter()
+                    .filter(|entry| !entry.yanked)
+                    .map(|entry| entry.version.to_string())
+                    .collect();
+                anyhow!(
+                    "no version of `{}` matching {} found in registry {}; available versions: {}",
+                    name,
+                    reqs.join(" and "),
+                    self.path.display(),
+                    available.join(", ")
+                )
+            })
+    }
+
+    /// The index entry for the given version of the named package.
+    pub fn entry(&self, name: &str, version: &semver::Version) -> Result<IndexEntry> {
+        let index = self.index()?;
+        index
+            .entries(name)
+            .iter()
+            .find(|entry| entry.version == *version)
+            .cloned()
+            .ok_or_else(|| {
+                anyhow!(
+                    "version {} of `{}` not found in registry {}",
+                    version,
+                    name,
+                    self.path.display()
+                )
+            })
+    }
+
+    /// The path to the extracted sources of the given version of the given package, extracting
+    /// the package's archive if it has not already been extracted.
+    ///
+    /// The archive's checksum is verified against the given `checksum`, i.e. the checksum that
+    /// was recorded when the package was pinned, prior to extraction. The checksum is written
+    /// alongside the extracted sources so that sources extracted from a different archive of the
+    /// same version are extracted again.
+    ///
+    /// The resulting directory is:
+    ///
+    /// ```ignore
+    /// $HOME/.forc/registry/src/<registry_dir_name>/<name>-<version>
+    /// ```
+    ///
+    /// where `$HOME/.forc/registry/src` may be overridden via [Registry::with_src_root].
+    pub fn fetch(&self, name: &str, version: &semver::Version, checksum: &str) -> Result<PathBuf> {
+        let src_dir = self.src_directory();
+        let path = src_dir.join(archive_dir_name(name, version));
+        // Co-ordinate access to the extracted sources using an advisory file lock.
+        let mut lock = crate::pkg::path_lock(&path)?;
+        let _guard = lock.write()?;
+        let extracted_checksum = fs::read_to_string(path.join(CHECKSUM_FILE_NAME)).ok();
+        if path.join(constants::MANIFEST_FILE_NAME).exists()
+            && extracted_checksum.as_deref() == Some(checksum)
+        {
+            return Ok(path);
+        }
+
+        let archive_path = self.archive_path(name, version);
+        let archive = fs::read(&archive_path)
+            .with_context(|| format!("failed to read archive {}", archive_path.display()))?;
+        let found = self::checksum(&archive);
+        if found != checksum {
+            bail!(
+                "checksum mismatch for archive {}: expected {}, found {}",
+                archive_path.display(),
+                checksum,
+                found
+            );
+        }
+
+        // Extract into a temporary directory first so that an interrupted extraction never
+        // leaves behind a partially populated package directory.
+        let tmp_dir = src_dir.join("tmp").join(archive_dir_name(name, version));
+        if tmp_dir.exists() {
+            let _ = fs::remove_dir_all(&tmp_dir);
+        }
+        let extracted = unpack_archive(&archive, name, version, &tmp_dir)
+            .with_context(|| format!("failed to extract archive {}", archive_path.display()))?;
+        if path.exists() {
+            let _ = fs::remove_dir_all(&path);
+        }
+        fs::write(extracted.join(CHECKSUM_FILE_NAME), checksum)?;
+        fs::rename(&extracted, &path)?;
+        let _ = fs::remove_dir_all(&tmp_dir);
+        Ok(path)
+    }
+
+    /// Add the given archive to the registry as the given version of the named package.
+    ///
+    /// Returns an error if the version has already been published.
+    pub fn publish(
+        &self,
+        name: &str,
+        version: &semver::Version,
+        archive: &[u8],
+    ) -> Result<IndexEntry> {
+        fs::create_dir_all(&self.path)?;
+        // Co-ordinate access to the index using an advisory file lock.
+        let mut lock = crate::pkg::path_lock(&self.path)?;
+        let _guard = lock.write()?;
+        let mut index = self.index()?;
+        if index.entries(name).iter().any(|e| e.version == *version) {
+            bail!(
+                "`{}` v{} has already been published to registry {}",
+                name,
+                version,
+                self.path.display()
+            );
+        }
+
+        let archive_path = self.archive_path(name, version);
+        fs::create_dir_all(self.path.join(PACKAGES_DIR_NAME))?;
+        fs::write(&archive_path, archive)
+            .with_context(|| format!("failed to write archive {}", archive_path.display()))?;
+
+        let entry = IndexEntry {
+            version: version.clone(),
+            checksum: checksum(archive),
+            yanked: false,
+        };
+        let entries = index.packages.entry(name.to_string()).or_default();
+        entries.push(entry.clone());
+        entries.sort_by(|a, b| a.version.cmp(&b.version));
+        let index_path = self.index_path();
+        fs::write(&index_path, serde_json::to_string_pretty(&index)?)
+            .with_context(|| format!("failed to write registry index {}", index_path.display()))?;
+        Ok(entry)
+    }
+
+    /// The directory into which packages from this registry are extracted.
+    ///
+    /// The directory name is derived from the SHA-256 of the registry's path, so that it remains
+    /// stable across toolchain versions.
+    fn src_directory(&self) -> PathBuf {
+        let hash = checksum(self.path.to_string_lossy().as_bytes());
+        let hash = &hash[..16];
+        let dir_name = match self.path.file_name().and_then(|s| s.to_str()) {
+            None => hash.to_string(),
+            Some(name) => format!("{}-{}", name, hash),
+        };
+        self.src_root.join(dir_name)
+    }
+}
+
+impl RegistryIndex {
+    /// All published versions of the named package.
+    pub fn entries(&self, name: &str) -> &[IndexEntry] {
+        self.packages.get(name).map(|v| &v[..]).unwrap_or(&[])
+    }
+}
+
+/// The files of the given package that are included within its archive, relative to the package
+/// directory and in a deterministic order.
+///
+/// This includes the manifest, all Sway source files under `src` and any license files.
+pub fn archive_files(manifest: &PackageManifestFile) -> Result<Vec<PathBuf>> {
+    let dir = manifest.dir();
+    let mut files = vec![PathBuf::from(constants::MANIFEST_FILE_NAME)];
+    for entry in fs::read_dir(dir)? {
+        let file_name = entry?.file_name();
+        let upper = match file_name.to_str() {
+            Some(name) => name.to_uppercase(),
+            None => continue,
+        };
+        let is_license = ["LICENSE", "LICENCE", "COPYING"]
+            .iter()
+            .any(|prefix| upper.starts_with(prefix));
+        if is_license && dir.join(&file_name).is_file() {
+            files.push(PathBuf::from(file_name));
+        }
+    }
+    for entry in walkdir::WalkDir::new(dir.join(constants::SRC_DIR)) {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type().is_file()
+            && path.extension().and_then(|ext| ext.to_str()) == Some(constants::SWAY_EXTENSION)
+        {
+            files.push(path.strip_prefix(dir)?.to_path_buf());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Produce the gzipped tarball of the given package.
+///
+/// The archive is reproducible: files are added in a deterministic order and all metadata other
+/// than file contents, such as timestamps and ownership, is normalized.
+pub fn package_archive(manifest: &PackageManifestFile) -> Result<Vec<u8>> {
+    let version = manifest.project.version.as_ref().ok_or_else(|| {
+        anyhow!(
+            "the `version` of package `{}` must be specified under `[project]` in order to \
+            package it",
+            manifest.project.name
+        )
+    })?;
+    let archive_dir = PathBuf::from(archive_dir_name(&manifest.project.name, version));
+    let mut tar = tar::Builder::new(Vec::new());
+    for file in archive_files(manifest)? {
+        let contents = fs::read(manifest.dir().join(&file))?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(0);
+        header.set_uid(0);
+        header.set_gid(0);
+        header.set_entry_type(tar::EntryType::Regular);
+        tar.append_data(&mut header, archive_dir.join(&file), &contents[..])?;
+    }
+    let tar = tar.into_inner()?;
+    let mut gz = flate2::GzBuilder::new()
+        .mtime(0)
+        .write(Vec::new(), flate2::Compression::default());
+    gz.write_all(&tar)?;
+    Ok(gz.finish()?)
+}
+
+/// Extract the given archive of the given package version into the given directory.
+///
+/// Returns the path to the extracted package, i.e. `<dir>/<name>-<version>`.
+pub fn unpack_archive(
+    archive: &[u8],
+    name: &str,
+    version: &semver::Version,
+    dir: &Path,
+) -> Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let decoder = flate2::read::GzDecoder::new(archive);
+    tar::Archive::new(decoder).unpack(dir)?;
+    let path = dir.join(archive_dir_name(name, version));
+    if !path.join(constants::MANIFEST_FILE_NAME).exists() {
+        bail!(
+            "archive does not contain `{}/{}`",
+            archive_dir_name(name, version),
+            constants::MANIFEST_FILE_NAME
+        );
+    }
+    Ok(path)
+}
+
+/// The hex-encoded SHA-256 checksum of the given bytes.
+pub fn checksum(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// The name of the top-level directory within the archive of the given package version.
+pub fn archive_dir_name(name: &str, version: &semver::Version) -> String {
+    format!("{}-{}", name, version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(version: &str, yanked: bool) -> IndexEntry {
+        IndexEntry {
+            version: version.parse().unwrap(),
+            checksum: String::new(),
+            yanked,
+        }
+    }
+
+    /// A registry with the given index within the given temporary directory, extracting fetched
+    /// packages within the same directory.
+    fn test_registry(dir: &tempfile::TempDir, index: &RegistryIndex) -> Registry {
+        let path = dir.path().join("registry");
+        fs::create_dir_all(&path).unwrap();
+        let registry = Registry::new(path).with_src_root(dir.path().join("src"));
+        fs::write(registry.index_path(), serde_json::to_string(index).unwrap()).unwrap();
+        registry
+    }
+
+    #[test]
+    fn resolve_greatest_matching_version() {
+        let mut index = RegistryIndex::default();
+        index.packages.insert(
+            "foo".into(),
+            vec![
+                entry("1.1.0", false),
+                entry("1.2.0", false),
+                entry("1.3.0", true),
+                entry("2.0.0", false),
+            ],
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let registry = test_registry(&dir, &index);
+        let req = |s: &str| semver::VersionReq::parse(s).unwrap();
+        let resolved = |s: &str| {
+            registry
+                .resolve("foo", &[req(s)])
+                .unwrap()
+                .version
+                .to_string()
+        };
+        assert_eq!(resolved("1.1"), "1.2.0");
+        assert_eq!(resolved("=1.1.0"), "1.1.0");
+        assert_eq!(resolved("*"), "2.0.0");
+        assert!(registry.resolve("foo", &[req("3")]).is_err());
+        assert!(registry.resolve("bar", &[req("1")]).is_err());
+        let resolved_all = |reqs: &[&str]| {
+            let reqs: Vec<_> = reqs.iter().map(|s| req(s)).collect();
+            registry
+                .resolve("foo", &reqs)
+                .map(|entry| entry.version.to_string())
+        };
+        assert_eq!(resolved_all(&["1", "<1.2"]).unwrap(), "1.1.0");
+        assert_eq!(resolved_all(&["*", ">=1.0.5", "1.1"]).unwrap(), "1.2.0");
+        assert!(resolved_all(&["1.1", "2"]).is_err());
+    }
+
+    #[test]
+    fn package_and_publish() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("foo");
+        fs::create_dir_all(dir.join("src").join("bar")).unwrap();
+        let manifest = "[project]\nname = \"foo\"\nversion = \"0.1.0\"\nlicense = \"Apache-2.0\"\nentry = \"lib.sw\"\nimplicit-std = false\n";
+        fs::write(dir.join("Forc.toml"), manifest).unwrap();
+        fs::write(dir.join("LICENSE"), "Apache-2.0").unwrap();
+        fs::write(dir.join("README.md"), "foo").unwrap();
+        fs::write(
+            dir.join("src").join("lib.sw"),
+            "library foo;\ndep bar/baz;\n",
+        )
+        .unwrap();
+        fs::write(dir.join("src").join("bar").join("baz.sw"), "library baz;\n").unwrap();
+        fs::write(dir.join("src").join("notes.txt"), "").unwrap();
+        let manifest = PackageManifestFile::from_dir(&dir).unwrap();
+
+        let files = archive_files(&manifest).unwrap();
+        let expected: Vec<PathBuf> = ["Forc.toml", "LICENSE", "src/bar/baz.sw", "src/lib.sw"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        assert_eq!(files, expected);
+
+        // Packaging is reproducible.
+        let archive = package_archive(&manifest).unwrap();
+        assert_eq!(archive, package_archive(&manifest).unwrap());
+
+        let registry = test_registry(&tmp, &RegistryIndex::default());
+        let version = semver::Version::new(0, 1, 0);
+        let entry = registry.publish("foo", &version, &archive).unwrap();
+        assert_eq!(entry.checksum, checksum(&archive));
+        assert!(registry.publish("foo", &version, &archive).is_err());
+        let req = semver::VersionReq::parse("0.1").unwrap();
+        assert_eq!(registry.resolve("foo", &[req]).unwrap().version, version);
+    }
+
+    #[test]
+    fn fetch_verifies_checksum() {
+        let version = semver::Version::new(0, 1, 0);
+        let mut tar = tar::Builder::new(Vec::new());
+        let manifest = b"[project]\nname = \"foo\"\nlicense = \"Apache-2.0\"\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(manifest.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tar.append_data(&mut header, "foo-0.1.0/Forc.toml", &manifest[..])
+            .unwrap();
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        std::io::Write::write_all(&mut gz, &tar.into_inner().unwrap()).unwrap();
+        let archive = gz.finish().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let registry = test_registry(&dir, &RegistryIndex::default());
+        let archive_path = registry.archive_path("foo", &version);
+        fs::create_dir_all(archive_path.parent().unwrap()).unwrap();
+        fs::write(&archive_path, &archive).unwrap();
+        assert!(registry.fetch("foo", &version, &"00".repeat(32)).is_err());
+
+        let path = registry
+            .fetch("foo", &version, &checksum(&archive))
+            .unwrap();
+        assert!(path.starts_with(dir.path().join("src")));
+        assert_eq!(fs::read(path.join("Forc.toml")).unwrap(), manifest);
+
+        // Sources that were already extracted are not reused for a different checksum.
+        assert!(registry.fetch("foo", &version, &"00".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn src_directory_is_stable() {
+        let registry =
+            Registry::new(PathBuf::from("/path/to/registry")).with_src_root(PathBuf::from("/src"));
+        assert_eq!(
+            registry.src_directory(),
+            PathBuf::from("/src/registry-95339c1674a30d6a")
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use clap::Parser;
+use fuel_gql_client::fuel_crypto::SecretKey;
+
+/// Call a method of a deployed contract.
+/// Crafts a script transaction that calls the contract, then sends it to a running node.
+///
+/// Arguments are encoded and the return value and logs are decoded using the contract's JSON ABI.
+#[derive(Debug, Default, Parser)]
+#[clap(bin_name = "forc call", version)]
+pub struct CallCommand {
+    /// The 32-byte ID of the contract to call.
+    pub contract_id: String,
+
+    /// The name of the ABI method to call.
+    pub method: String,
+
+    /// Arguments to the method, encoded according to the contract's JSON ABI.
+    ///
+    /// Values are written with a Sway-like syntax, e.g. `42`, `true`, `0x<64 hex digits>` for a
+    /// `b256`, `"fuel"` for a string, `[1, 2]` for an array, `(1, true)` for a tuple,
+    /// `Point { x: 1, y: 2 }` for a struct and `Option::Some(1)` for an enum variant.
+    pub args: Vec<String>,
+
+    /// Path to the contract's JSON ABI, e.g. `out/debug/<project>-abi.json`.
+    #[clap(long)]
+    pub abi: String,
+
+    /// The node url to call, if not specified uses DEFAULT_NODE_URL.
+    #[clap(long, env = "FUEL_NODE_URL")]
+    pub node_url: Option<String>,
+
+    /// The amount of coins to forward to the contract with the call.
+    #[clap(long, default_value = "0")]
+    pub amount: u64,
+
+    /// The 32-byte ID of the asset to forward to the contract with the call. Defaults to the base
+    /// asset.
+    #[clap(long)]
+    pub asset_id: Option<String>,
+
+    /// 32-byte ID of an additional contract that will be called during the transaction.
+    #[clap(long = "contract")]
+    pub contract: Option<Vec<String>>,
+
+    /// Only craft transaction and print it out.
+    #[clap(long)]
+    pub dry_run: bool,
+
+    /// Execute the transaction and return the final mutated transaction along with receipts
+    /// (which includes whether the transaction reverted or not). The transaction is not inserted
+    /// in the node's view of the blockchain, (i.e. it does not affect the chain state).
+    #[clap(long)]
+    pub simulate: bool,
+
+    /// Pretty-print the outputs from the node.
+    #[clap(long = "pretty-print", short = 'r')]
+    pub pretty_print: bool,
+
+    /// Set the transaction gas limit. Defaults to the maximum gas limit.
+    #[clap(long)]
+    pub gas_limit: Option<u64>,
+
+    /// Set the transaction gas price. Defaults to 0.
+    #[clap(long)]
+    pub gas_price: Option<u64>,
+
+    /// Do not sign the transaction
+    #[clap(long)]
+    pub unsigned: bool,
+
+    /// Set the key to be used for signing.
+    #[clap(long)]
+    pub signing_key: Option<SecretKey>,
+
+    /// Sign the transaction with the account at the given index of the wallet created by
+    /// `forc wallet`, rather than prompting for a signature.
+    ///
+    /// The wallet's password is read from the `FORC_WALLET_PASSWORD` environment variable if set,
+    /// or is otherwise prompted for.
+    #[clap(long, conflicts_with_all = &["signing-key", "unsigned"])]
+    pub account: Option<usize>,
+
+    /// Path to the wallet keystore used by `--account`. Defaults to `~/.fuel/wallets/.wallet`.
+    #[clap(long, requires = "account")]
+    pub wallet_path: Option<String>,
+}
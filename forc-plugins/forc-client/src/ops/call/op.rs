@@ -0,0 +1,344 @@
+use anyhow::{anyhow, Context, Result};
+use forc_util::abi::{decode_logs, AbiFn};
+use fuel_gql_client::{
+    client::FuelClient,
+    fuel_vm::{
+        consts::{REG_CGAS, REG_ONE},
+        fuel_types::bytes::padded_len_usize,
+        prelude::Opcode,
+    },
+};
+use fuel_tx::{field::Script as ScriptField, AssetId, ConsensusParameters, ContractId, Script};
+use fuels_core::{code_gen::function_selector::resolve_fn_selector, constants::BASE_ASSET_ID};
+use fuels_types::{param_types::ParamType, ProgramABI};
+use std::{fs, path::Path, str::FromStr};
+use sway_utils::constants::DEFAULT_NODE_URL;
+use tracing::{info, warn};
+
+use crate::ops::run::op::try_send_tx;
+use crate::ops::tx_util::{TransactionBuilderExt, TxParameters};
+use crate::ops::wallet_util::resolve_signing_key;
+
+use super::cmd::CallCommand;
+
+pub struct CalledContract {
+    pub receipts: Vec<fuel_tx::Receipt>,
+    /// The value returned by the called method, decoded using the JSON ABI.
+    pub return_value: Option<String>,
+    /// The ID and value of each log emitted by the called contract, decoded using the JSON ABI.
+    pub logs: Vec<(u64, String)>,
+}
+
+/// Calls a method of a deployed contract.
+///
+/// The method's arguments are encoded and its return value and logs are decoded using the
+/// contract's JSON ABI. Upon success, returns the receipts of the call.
+pub async fn call(command: CallCommand) -> Result<CalledContract> {
+    let contract_id = parse_id::<ContractId>(&command.contract_id, "contract ID")?;
+    let asset_id = match &command.asset_id {
+        Some(asset_id) => parse_id::<AssetId>(asset_id, "asset ID")?,
+        None => BASE_ASSET_ID,
+    };
+    let contract_ids = command
+        .contract
+        .iter()
+        .flatten()
+        .map(|contract| parse_id::<ContractId>(contract, "contract ID"))
+        .collect::<Result<Vec<ContractId>>>()?;
+
+    let program_abi = read_abi(Path::new(&command.abi))?;
+    let method = AbiFn::from_abi(&program_abi, &command.method)?;
+    let (script, script_data) = call_script(
+        &method,
+        &command.args,
+        contract_id,
+        command.amount,
+        asset_id,
+    )?;
+
+    let node_url = command.node_url.as_deref().unwrap_or(DEFAULT_NODE_URL);
+    let client = FuelClient::new(node_url)?;
+    let signing_key = resolve_signing_key(
+        command.signing_key,
+        command.account,
+        command.wallet_path.as_deref(),
+    )?;
+    let coins = match command.amount {
+        0 => vec![],
+        amount => vec![(asset_id, amount)],
+    };
+    let tx = fuel_tx::TransactionBuilder::script(script, script_data)
+        .params(TxParameters::new(command.gas_limit, command.gas_price))
+        .add_contract(contract_id)
+        .add_contracts(contract_ids)
+        .finalize_signed_with_coins(client, command.unsigned, signing_key, &coins)
+        .await?;
+    if command.dry_run {
+        info!("{:?}", tx);
+        return Ok(CalledContract {
+            receipts: vec![],
+            return_value: None,
+            logs: vec![],
+        });
+    }
+
+    let receipts =
+        try_send_tx(node_url, &tx.into(), command.pretty_print, command.simulate).await?;
+    // The call has already been submitted and its receipts printed, so values that cannot be
+    // decoded are only reported as warnings.
+    let return_value = method
+        .decode_output(&receipts, &contract_id)
+        .unwrap_or_else(|e| {
+            warn!("{:#}", e);
+            None
+        });
+    if let Some(value) = &return_value {
+        info!("Return value: {}", value);
+    }
+    let logs = decode_logs(&program_abi, &receipts, &contract_id).unwrap_or_else(|e| {
+        warn!("{:#}", e);
+        vec![]
+    });
+    for (log_id, value) in &logs {
+        info!("Log {}: {}", log_id, value);
+    }
+    Ok(CalledContract {
+        receipts,
+        return_value,
+        logs,
+    })
+}
+
+fn parse_id<T: FromStr>(id: &str, kind: &str) -> Result<T>
+where
+    T::Err: std::fmt::Display,
+{
+    T::from_str(id).map_err(|e| anyhow!("failed to parse {} `{}`: {}", kind, id, e))
+}
+
+fn read_abi(path: &Path) -> Result<ProgramABI> {
+    let json = fs::read_to_string(path)
+        .with_context(|| format!("failed to read JSON ABI from {}", path.display()))?;
+    serde_json::from_str(&json)
+        .with_context(|| format!("failed to parse JSON ABI from {}", path.display()))
+}
+
+/// Produce the bytecode and script data of a script that calls the given method, forwarding
+/// `amount` coins of `asset_id`.
+///
+/// The script data is laid out as follows:
+///
+///   [amount: u64][asset_id: b256][contract_id: b256][selector: u64][args: u64][encoded args]
+///
+/// where `contract_id`, `selector` and `args` form the call frame parameters read by `CALL`. In
+/// the case that the method takes a single argument of a copy type, `args` is the argument
+/// itself. Otherwise `args` points to the encoded arguments that follow it.
+fn call_script(
+    method: &AbiFn,
+    args: &[String],
+    contract_id: ContractId,
+    amount: u64,
+    asset_id: AssetId,
+) -> Result<(Vec<u8>, Vec<u8>)> {
+    const AMOUNT_OFFSET: usize = 0;
+    const ASSET_ID_OFFSET: usize = AMOUNT_OFFSET + 8;
+    const CALL_DATA_OFFSET: usize = ASSET_ID_OFFSET + AssetId::LEN;
+    const ARGS_OFFSET: usize = CALL_DATA_OFFSET + ContractId::LEN + 8 + 8;
+
+    let encoded_args = method.encode_args(args)?;
+    let param_types: Vec<ParamType> = method
+        .inputs
+        .iter()
+        .map(|input| input.param_type.clone())
+        .collect();
+    let selector = resolve_fn_selector(&method.name, &param_types);
+
+    // The script is of a fixed size, so the offset of the script data is known upfront.
+    let script_len = 6 * Opcode::LEN;
+    let data_offset = ConsensusParameters::DEFAULT.tx_offset()
+        + Script::script_offset_static()
+        + padded_len_usize(script_len);
+    let imm = |offset: usize| -> Result<u32> {
+        u32::try_from(data_offset + offset).context("script data offset out of range")
+    };
+    let script: Vec<u8> = [
+        Opcode::MOVI(0x10, imm(CALL_DATA_OFFSET)?),
+        Opcode::MOVI(0x11, imm(AMOUNT_OFFSET)?),
+        Opcode::LW(0x11, 0x11, 0),
+        Opcode::MOVI(0x12, imm(ASSET_ID_OFFSET)?),
+        Opcode::CALL(0x10, 0x11, 0x12, REG_CGAS),
+        Opcode::RET(REG_ONE),
+    ]
+    .into_iter()
+    .collect();
+
+    let (args_word, encoded_args) = match param_types.as_slice() {
+        [] => (0, vec![]),
+        [ParamType::Bool
+        | ParamType::Byte
+        | ParamType::U8
+        | ParamType::U16
+        | ParamType::U32
+        | ParamType::U64] => {
+            let word: [u8; 8] = encoded_args
+                .as_slice()
+                .try_into()
+                .context("expected the argument to be encoded as a single word")?;
+            (u64::from_be_bytes(word), vec![])
+        }
+        _ => ((data_offset + ARGS_OFFSET) as u64, encoded_args),
+    };
+    let mut script_data = vec![];
+    script_data.extend(amount.to_be_bytes());
+    script_data.extend(asset_id.iter());
+    script_data.extend(contract_id.iter());
+    script_data.extend(selector);
+    script_data.extend(args_word.to_be_bytes());
+    script_data.extend(encoded_args);
+    Ok((script, script_data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use forc_util::abi::AbiParam;
+
+    const CONTRACT_ID: [u8; 32] = [1; 32];
+    const ASSET_ID: [u8; 32] = [2; 32];
+
+    fn method(name: &str, inputs: Vec<ParamType>) -> AbiFn {
+        let inputs = inputs
+            .into_iter()
+            .enumerate()
+            .map(|(ix, param_type)| AbiParam {
+                name: format!("arg{}", ix),
+                type_name: String::new(),
+                param_type,
+            })
+            .collect();
+        AbiFn {
+            name: name.to_string(),
+            inputs,
+            output: ParamType::Unit,
+        }
+    }
+
+    fn call(method: &AbiFn, args: &[&str]) -> (Vec<u8>, Vec<u8>) {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        call_script(
+            method,
+            &args,
+            ContractId::from(CONTRACT_ID),
+            42,
+            AssetId::from(ASSET_ID),
+        )
+        .unwrap()
+    }
+
+    fn data_offset() -> u32 {
+        let offset = ConsensusParameters::DEFAULT.tx_offset()
+            + Script::script_offset_static()
+            + padded_len_usize(6 * Opcode::LEN);
+        offset as u32
+    }
+
+    /// The fields of the script data produced by `call_script`.
+    struct ScriptData<'a> {
+        amount: u64,
+        asset_id: &'a [u8],
+        contract_id: &'a [u8],
+        selector: &'a [u8],
+        args: u64,
+        encoded_args: &'a [u8],
+    }
+
+    fn split(script_data: &[u8]) -> ScriptData<'_> {
+        let word = |bytes: &[u8]| u64::from_be_bytes(bytes.try_into().unwrap());
+        ScriptData {
+            amount: word(&script_data[..8]),
+            asset_id: &script_data[8..40],
+            contract_id: &script_data[40..72],
+            selector: &script_data[72..80],
+            args: word(&script_data[80..88]),
+            encoded_args: &script_data[88..],
+        }
+    }
+
+    #[test]
+    fn script_reads_call_frame_from_script_data() {
+        let (script, _) = call(&method("increment", vec![]), &[]);
+        let offset = data_offset();
+        assert_eq!(
+            Opcode::from_bytes_iter(script.iter().copied()),
+            [
+                Opcode::MOVI(0x10, offset + 40),
+                Opcode::MOVI(0x11, offset),
+                Opcode::LW(0x11, 0x11, 0),
+                Opcode::MOVI(0x12, offset + 8),
+                Opcode::CALL(0x10, 0x11, 0x12, REG_CGAS),
+                Opcode::RET(REG_ONE),
+            ]
+        );
+    }
+
+    #[test]
+    fn method_without_args() {
+        let (_, script_data) = call(&method("increment", vec![]), &[]);
+        let data = split(&script_data);
+        assert_eq!(data.amount, 42);
+        assert_eq!(data.asset_id, ASSET_ID);
+        assert_eq!(data.contract_id, CONTRACT_ID);
+        // The first four bytes of `sha256("increment()")`.
+        assert_eq!(data.selector, [0, 0, 0, 0, 0x58, 0x42, 0xf1, 0xbe]);
+        assert_eq!(data.args, 0);
+        assert!(data.encoded_args.is_empty());
+    }
+
+    #[test]
+    fn single_copy_arg_is_passed_by_value() {
+        let (_, script_data) = call(&method("increment", vec![ParamType::U64]), &["7"]);
+        let data = split(&script_data);
+        // The first four bytes of `sha256("increment(u64)")`.
+        assert_eq!(data.selector, [0, 0, 0, 0, 0xe5, 0x43, 0xc6, 0x66]);
+        assert_eq!(data.args, 7);
+        assert!(data.encoded_args.is_empty());
+
+        let (_, script_data) = call(&method("set_flag", vec![ParamType::Byte]), &["0xff"]);
+        let data = split(&script_data);
+        // The first four bytes of `sha256("set_flag(byte)")`.
+        assert_eq!(data.selector, [0, 0, 0, 0, 0xe7, 0x3a, 0x68, 0x03]);
+        assert_eq!(data.args, 0xff);
+        assert!(data.encoded_args.is_empty());
+    }
+
+    #[test]
+    fn other_args_are_passed_by_reference() {
+        let point = ParamType::Struct {
+            name: "Point".to_string(),
+            fields: vec![
+                ("x".to_string(), ParamType::U64),
+                ("on".to_string(), ParamType::Bool),
+            ],
+            generics: vec![],
+        };
+        let (_, script_data) = call(&method("mint", vec![point]), &["Point { x: 3, on: true }"]);
+        let data = split(&script_data);
+        // The first four bytes of `sha256("mint(s(u64,bool))")`.
+        assert_eq!(data.selector, [0, 0, 0, 0, 0x82, 0xb0, 0xfe, 0x0e]);
+        assert_eq!(data.args, data_offset() as u64 + 88);
+        let mut expected = 3u64.to_be_bytes().to_vec();
+        expected.extend(1u64.to_be_bytes());
+        assert_eq!(data.encoded_args, expected);
+
+        let (_, script_data) = call(
+            &method("add", vec![ParamType::U64, ParamType::U64]),
+            &["1", "2"],
+        );
+        let data = split(&script_data);
+        assert_eq!(data.args, data_offset() as u64 + 88);
+        assert_eq!(
+            data.encoded_args,
+            [1u64.to_be_bytes(), 2u64.to_be_bytes()].concat()
+        );
+    }
+}
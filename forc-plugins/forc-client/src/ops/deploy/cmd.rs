@@ -73,6 +73,20 @@ pub struct DeployCommand {
     /// Output the time elapsed over each part of the compilation process.
     #[clap(long)]
     pub time_phases: bool,
+    /// Compile every package from source, rather than reusing unchanged packages from the build
+    /// cache.
+    #[clap(long)]
+    pub no_cache: bool,
+    /// Comma separated list of features to enable, in addition to the package's default
+    /// features.
+    #[clap(long, value_delimiter = ',')]
+    pub features: Vec<String>,
+    /// Enable all features declared by the package.
+    #[clap(long)]
+    pub all_features: bool,
+    /// Do not enable the package's `default` features.
+    #[clap(long)]
+    pub no_default_features: bool,
     /// Do not sign the transaction
     #[clap(long)]
     pub unsigned: bool,
@@ -84,4 +98,14 @@ pub struct DeployCommand {
     pub gas_price: Option<u64>,
     /// Set the key to be used for signing.
     pub signing_key: Option<SecretKey>,
+    /// Sign the transaction with the account at the given index of the wallet created by
+    /// `forc wallet`, rather than prompting for a signature.
+    ///
+    /// The wallet's password is read from the `FORC_WALLET_PASSWORD` environment variable if set,
+    /// or is otherwise prompted for.
+    #[clap(long, conflicts_with_all = &["signing-key", "unsigned"])]
+    pub account: Option<usize>,
+    /// Path to the wallet keystore used by `--account`. Defaults to `~/.fuel/wallets/.wallet`.
+    #[clap(long, requires = "account")]
+    pub wallet_path: Option<String>,
 }
@@ -16,6 +16,7 @@ use tracing::info;
 
 use crate::ops::pkg_util::built_pkgs_with_manifest;
 use crate::ops::tx_util::{TransactionBuilderExt, TxParameters, TX_SUBMIT_TIMEOUT_MS};
+use crate::ops::wallet_util::resolve_signing_key;
 
 use super::cmd::DeployCommand;
 
@@ -29,7 +30,7 @@ pub struct DeployedContract {
 /// Upon success, returns the ID of each deployed contract in order of deployment.
 ///
 /// When deploying a single contract, only that contract's ID is returned.
-pub async fn deploy(command: DeployCommand) -> Result<Vec<DeployedContract>> {
+pub async fn deploy(mut command: DeployCommand) -> Result<Vec<DeployedContract>> {
     let mut contract_ids = Vec::new();
     let curr_dir = if let Some(ref path) = command.path {
         PathBuf::from(path)
@@ -38,6 +39,12 @@ pub async fn deploy(command: DeployCommand) -> Result<Vec<DeployedContract>> {
     };
     let build_opts = build_opts_from_cmd(&command);
     let built_pkgs_with_manifest = built_pkgs_with_manifest(&curr_dir, build_opts)?;
+    // Resolve the signing key upfront so that the wallet's password is only prompted for once.
+    command.signing_key = resolve_signing_key(
+        command.signing_key,
+        command.account,
+        command.wallet_path.as_deref(),
+    )?;
     for (member_manifest, built_pkg) in built_pkgs_with_manifest {
         if member_manifest
             .check_program_type(vec![TreeType::Contract])
@@ -138,9 +145,15 @@ fn build_opts_from_cmd(cmd: &DeployCommand) -> pkg::BuildOpts {
             json_abi: cmd.minify_json_abi,
             json_storage_slots: cmd.minify_json_storage_slots,
         },
+        features: pkg::FeatureOpts {
+            features: cmd.features.clone(),
+            all_features: cmd.all_features,
+            no_default_features: cmd.no_default_features,
+        },
         build_profile: cmd.build_profile.clone(),
         release: cmd.release,
         time_phases: cmd.time_phases,
+        no_cache: cmd.no_cache,
         binary_outfile: cmd.binary_outfile.clone(),
         debug_outfile: cmd.debug_outfile.clone(),
         tests: false,
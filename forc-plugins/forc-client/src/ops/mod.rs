@@ -1,4 +1,6 @@
+pub mod call;
 pub mod deploy;
 pub mod pkg_util;
 pub mod run;
 pub mod tx_util;
+pub mod wallet_util;
@@ -10,6 +10,15 @@ pub struct RunCommand {
     #[clap(short, long)]
     pub data: Option<String>,
 
+    /// Arguments to the script's `main` function, encoded as script data according to the
+    /// package's JSON ABI.
+    ///
+    /// Values are written with a Sway-like syntax, e.g. `42`, `true`, `0x<64 hex digits>` for a
+    /// `b256`, `"fuel"` for a string, `[1, 2]` for an array, `(1, true)` for a tuple,
+    /// `Point { x: 1, y: 2 }` for a struct and `Option::Some(1)` for an enum variant.
+    #[clap(long, multiple_values = true, conflicts_with = "data")]
+    pub args: Option<Vec<String>>,
+
     /// Path to the project, if not specified, current working directory will be used.
     #[clap(short, long)]
     pub path: Option<String>,
@@ -72,6 +81,24 @@ pub struct RunCommand {
     #[clap(long)]
     pub time_phases: bool,
 
+    /// Compile every package from source, rather than reusing unchanged packages from the build
+    /// cache.
+    #[clap(long)]
+    pub no_cache: bool,
+
+    /// Comma separated list of features to enable, in addition to the package's default
+    /// features.
+    #[clap(long, value_delimiter = ',')]
+    pub features: Vec<String>,
+
+    /// Enable all features declared by the package.
+    #[clap(long)]
+    pub all_features: bool,
+
+    /// Do not enable the package's `default` features.
+    #[clap(long)]
+    pub no_default_features: bool,
+
     /// Pretty-print the outputs from the node.
     #[clap(long = "pretty-print", short = 'r')]
     pub pretty_print: bool,
@@ -132,4 +159,16 @@ pub struct RunCommand {
 
     /// Set the key to be used for signing.
     pub signing_key: Option<SecretKey>,
+
+    /// Sign the transaction with the account at the given index of the wallet created by
+    /// `forc wallet`, rather than prompting for a signature.
+    ///
+    /// The wallet's password is read from the `FORC_WALLET_PASSWORD` environment variable if set,
+    /// or is otherwise prompted for.
+    #[clap(long, conflicts_with_all = &["signing-key", "unsigned"])]
+    pub account: Option<usize>,
+
+    /// Path to the wallet keystore used by `--account`. Defaults to `~/.fuel/wallets/.wallet`.
+    #[clap(long, requires = "account")]
+    pub wallet_path: Option<String>,
 }
@@ -1,5 +1,6 @@
 use anyhow::{anyhow, bail, Context, Result};
 use forc_pkg::{self as pkg, fuel_core_not_running, PackageManifestFile};
+use forc_util::abi::AbiFn;
 use fuel_gql_client::client::FuelClient;
 use fuel_tx::{ContractId, Transaction, TransactionBuilder, UniqueIdentifier};
 use futures::TryFutureExt;
@@ -7,11 +8,13 @@ use pkg::BuiltPackage;
 use std::time::Duration;
 use std::{path::PathBuf, str::FromStr};
 use sway_core::language::parsed::TreeType;
+use sway_types::constants::DEFAULT_ENTRY_POINT_FN_NAME;
 use tokio::time::timeout;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::ops::pkg_util::built_pkgs_with_manifest;
 use crate::ops::tx_util::{TransactionBuilderExt, TxParameters, TX_SUBMIT_TIMEOUT_MS};
+use crate::ops::wallet_util::resolve_signing_key;
 
 use super::cmd::RunCommand;
 
@@ -19,6 +22,8 @@ pub const NODE_URL: &str = "http://127.0.0.1:4000";
 
 pub struct RanScript {
     pub receipts: Vec<fuel_tx::Receipt>,
+    /// The value returned by the script's `main` function, decoded using the JSON ABI.
+    pub return_value: Option<String>,
 }
 
 /// Builds and runs script(s). If given path corresponds to a workspace, all runnable members will
@@ -27,7 +32,7 @@ pub struct RanScript {
 /// Upon success, returns the receipts of each script in the order they are executed.
 ///
 /// When running a single script, only that script's receipts are returned.
-pub async fn run(command: RunCommand) -> Result<Vec<RanScript>> {
+pub async fn run(mut command: RunCommand) -> Result<Vec<RanScript>> {
     let mut receipts = Vec::new();
     let curr_dir = if let Some(path) = &command.path {
         PathBuf::from(path)
@@ -36,6 +41,12 @@ pub async fn run(command: RunCommand) -> Result<Vec<RanScript>> {
     };
     let build_opts = build_opts_from_cmd(&command);
     let built_pkgs_with_manifest = built_pkgs_with_manifest(&curr_dir, build_opts)?;
+    // Resolve the signing key upfront so that the wallet's password is only prompted for once.
+    command.signing_key = resolve_signing_key(
+        command.signing_key,
+        command.account,
+        command.wallet_path.as_deref(),
+    )?;
     for (member_manifest, built_pkg) in built_pkgs_with_manifest {
         if member_manifest
             .check_program_type(vec![TreeType::Script])
@@ -54,9 +65,16 @@ pub async fn run_pkg(
     manifest: &PackageManifestFile,
     compiled: &BuiltPackage,
 ) -> Result<RanScript> {
-    let input_data = command.data.as_deref().unwrap_or("");
-    let data = format_hex_data(input_data);
-    let script_data = hex::decode(data).expect("Invalid hex");
+    let main_fn = AbiFn::from_abi(&compiled.json_abi_program, DEFAULT_ENTRY_POINT_FN_NAME);
+    let script_data = match (&command.args, &main_fn) {
+        (Some(args), Ok(main_fn)) => main_fn.encode_args(args)?,
+        (Some(_), Err(e)) => bail!("failed to encode the arguments of `main`: {}", e),
+        (None, _) => {
+            let input_data = command.data.as_deref().unwrap_or("");
+            let data = format_hex_data(input_data);
+            hex::decode(data).expect("Invalid hex")
+        }
+    };
 
     let node_url = command
         .node_url
@@ -81,15 +99,35 @@ pub async fn run_pkg(
         .await?;
     if command.dry_run {
         info!("{:?}", tx);
-        Ok(RanScript { receipts: vec![] })
+        Ok(RanScript {
+            receipts: vec![],
+            return_value: None,
+        })
     } else {
         let receipts =
             try_send_tx(node_url, &tx.into(), command.pretty_print, command.simulate).await?;
-        Ok(RanScript { receipts })
+        // The transaction has already been submitted and its receipts printed, so a value that
+        // cannot be decoded is only reported as a warning.
+        let return_value = match &main_fn {
+            Ok(main_fn) => main_fn
+                .decode_output(&receipts, &ContractId::zeroed())
+                .unwrap_or_else(|e| {
+                    warn!("{:#}", e);
+                    None
+                }),
+            Err(_) => None,
+        };
+        if let Some(value) = &return_value {
+            info!("Return value: {}", value);
+        }
+        Ok(RanScript {
+            receipts,
+            return_value,
+        })
     }
 }
 
-async fn try_send_tx(
+pub(crate) async fn try_send_tx(
     node_url: &str,
     tx: &Transaction,
     pretty_print: bool,
@@ -131,10 +169,12 @@ async fn send_tx(
 
     match outputs {
         Ok(logs) => {
-            print_receipt_output(&logs, pretty_print)?;
+            if let Err(e) = print_receipt_output(&logs, pretty_print) {
+                warn!("failed to print the transaction receipts: {}", e);
+            }
             Ok(logs)
         }
-        Err(e) => bail!("{e}"),
+        Err(e) => bail!("{}", e),
     }
 }
 
@@ -194,9 +234,15 @@ fn build_opts_from_cmd(cmd: &RunCommand) -> pkg::BuildOpts {
             json_abi: cmd.minify_json_abi,
             json_storage_slots: cmd.minify_json_storage_slots,
         },
+        features: pkg::FeatureOpts {
+            features: cmd.features.clone(),
+            all_features: cmd.all_features,
+            no_default_features: cmd.no_default_features,
+        },
         build_profile: cmd.build_profile.clone(),
         release: cmd.release,
         time_phases: cmd.time_phases,
+        no_cache: cmd.no_cache,
         binary_outfile: cmd.binary_outfile.clone(),
         debug_outfile: cmd.debug_outfile.clone(),
         tests: false,
@@ -1,11 +1,11 @@
-use std::{io::Write, str::FromStr};
+use std::{collections::BTreeMap, io::Write, str::FromStr};
 
 use anyhow::{Error, Result};
 use async_trait::async_trait;
 use fuel_gql_client::{
     client::FuelClient,
     fuel_crypto::{Message, SecretKey, Signature},
-    fuel_tx::{Address, ContractId, Input, Output, TransactionBuilder, Witness},
+    fuel_tx::{Address, AssetId, ContractId, Input, Output, TransactionBuilder, Witness},
     prelude::SerializableVec,
 };
 use fuel_tx::{field, Buildable};
@@ -70,6 +70,7 @@ pub trait TransactionBuilderExt<Tx> {
         address: Address,
         provider: Provider,
         signature_witness_index: u8,
+        coins: &[(AssetId, u64)],
     ) -> Result<&mut Self>;
     async fn finalize_signed(
         &mut self,
@@ -77,6 +78,15 @@ pub trait TransactionBuilderExt<Tx> {
         unsigned: bool,
         signing_key: Option<SecretKey>,
     ) -> Result<Tx>;
+    /// Like `finalize_signed`, but additionally funds the transaction with the given coins, e.g.
+    /// those forwarded to a called contract.
+    async fn finalize_signed_with_coins(
+        &mut self,
+        client: FuelClient,
+        unsigned: bool,
+        signing_key: Option<SecretKey>,
+        coins: &[(AssetId, u64)],
+    ) -> Result<Tx>;
 }
 
 #[async_trait]
@@ -122,17 +132,23 @@ impl<Tx: Buildable + SerializableVec + field::Witnesses + Send> TransactionBuild
         address: Address,
         provider: Provider,
         signature_witness_index: u8,
+        coins: &[(AssetId, u64)],
     ) -> Result<&mut Self> {
         let wallet = Wallet::from_address(Bech32Address::from(address), Some(provider));
 
-        let amount = 1_000_000;
-        let asset_id = BASE_ASSET_ID;
-        let inputs = wallet
-            .get_asset_inputs_for_amount(asset_id, amount, signature_witness_index)
-            .await?;
-        let output = Output::change(wallet.address().into(), 0, asset_id);
+        // The base asset is always required in order to pay for gas.
+        let mut amounts = BTreeMap::from([(BASE_ASSET_ID, 1_000_000)]);
+        for (asset_id, amount) in coins {
+            *amounts.entry(*asset_id).or_default() += amount;
+        }
+        for (asset_id, amount) in amounts {
+            let inputs = wallet
+                .get_asset_inputs_for_amount(asset_id, amount, signature_witness_index)
+                .await?;
+            let output = Output::change(wallet.address().into(), 0, asset_id);
 
-        self.add_inputs(inputs).add_output(output);
+            self.add_inputs(inputs).add_output(output);
+        }
 
         Ok(self)
     }
@@ -141,6 +157,16 @@ impl<Tx: Buildable + SerializableVec + field::Witnesses + Send> TransactionBuild
         client: FuelClient,
         unsigned: bool,
         signing_key: Option<SecretKey>,
+    ) -> Result<Tx> {
+        self.finalize_signed_with_coins(client, unsigned, signing_key, &[])
+            .await
+    }
+    async fn finalize_signed_with_coins(
+        &mut self,
+        client: FuelClient,
+        unsigned: bool,
+        signing_key: Option<SecretKey>,
+        coins: &[(AssetId, u64)],
     ) -> Result<Tx> {
         let mut signature_witness_index = 0u8;
         if !unsigned {
@@ -156,8 +182,13 @@ impl<Tx: Buildable + SerializableVec + field::Witnesses + Send> TransactionBuild
             self.add_witness(Witness::default());
 
             // Add input coin and output change
-            self.fund(address, Provider::new(client), signature_witness_index)
-                .await?;
+            self.fund(
+                address,
+                Provider::new(client),
+                signature_witness_index,
+                coins,
+            )
+            .await?;
         }
 
         let mut tx = self._finalize_without_signature();
@@ -0,0 +1,162 @@
+//! Signing with the accounts of an encrypted wallet keystore, as created by `forc wallet init`.
+//!
+//! The keystore holds the wallet's mnemonic phrase, encrypted with the user's password. The signing
+//! key of each account is derived from the phrase using the account's index, matching the accounts
+//! listed by `forc wallet list`.
+
+use anyhow::{anyhow, bail, Context, Result};
+use fuel_gql_client::fuel_crypto::SecretKey;
+use fuels_signers::wallet::DEFAULT_DERIVATION_PATH_PREFIX;
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// The environment variable from which the wallet's password is read, if set, rather than
+/// prompting for it.
+pub const WALLET_PASSWORD_ENV: &str = "FORC_WALLET_PASSWORD";
+
+/// The location of the wallet keystore created by `forc wallet`, `$HOME/.fuel/wallets/.wallet`.
+pub fn default_wallet_path() -> PathBuf {
+    dirs::home_dir()
+        .expect("unable to find the user home directory")
+        .join(".fuel")
+        .join("wallets")
+        .join(".wallet")
+}
+
+/// Resolve the key with which to sign a transaction.
+///
+/// In the case that an `account` index is provided, its key is decrypted from the wallet keystore
+/// at `wallet_path`, or the default wallet path if `None`. Otherwise, the given `signing_key` is
+/// returned as is.
+pub(crate) fn resolve_signing_key(
+    signing_key: Option<SecretKey>,
+    account: Option<usize>,
+    wallet_path: Option<&str>,
+) -> Result<Option<SecretKey>> {
+    let account = match account {
+        Some(account) => account,
+        None => return Ok(signing_key),
+    };
+    let wallet_path = wallet_path
+        .map(PathBuf::from)
+        .unwrap_or_else(default_wallet_path);
+    if !wallet_path.exists() {
+        bail!(
+            "no wallet found at {}, create one with `forc wallet init` or specify its location \
+            with `--wallet-path`",
+            wallet_path.display()
+        );
+    }
+    let password = match std::env::var(WALLET_PASSWORD_ENV) {
+        Ok(password) => password,
+        Err(_) => read_password(&format!(
+            "Please provide the password of the wallet at {}: ",
+            wallet_path.display()
+        ))?,
+    };
+    let secret_key = account_secret_key(&wallet_path, account, &password)?;
+    Ok(Some(secret_key))
+}
+
+/// Decrypt the wallet keystore at the given path and derive the secret key of the account with
+/// the given index.
+fn account_secret_key(wallet_path: &Path, account: usize, password: &str) -> Result<SecretKey> {
+    let phrase = eth_keystore::decrypt_key(wallet_path, password).map_err(|e| {
+        anyhow!(
+            "failed to decrypt the wallet at {}: {}",
+            wallet_path.display(),
+            e
+        )
+    })?;
+    let phrase = String::from_utf8(phrase)
+        .context("the wallet keystore does not contain a valid mnemonic phrase")?;
+    let derivation_path = format!("{}/{}'/0/0", DEFAULT_DERIVATION_PATH_PREFIX, account);
+    SecretKey::new_from_mnemonic_phrase_with_path(&phrase, &derivation_path)
+        .map_err(|e| anyhow!("failed to derive the key of account {}: {}", account, e))
+}
+
+/// Prompt for a password, without echoing it to the terminal where supported.
+fn read_password(prompt: &str) -> Result<String> {
+    print!("{}", prompt);
+    std::io::stdout().flush()?;
+    let mut buf = String::new();
+    {
+        let _echo_guard = EchoGuard::disable();
+        std::io::stdin().read_line(&mut buf)?;
+    }
+    println!();
+    Ok(buf.trim_end_matches(&['\r', '\n'][..]).to_string())
+}
+
+/// Disables terminal echo on construction and restores it on drop.
+struct EchoGuard {
+    #[cfg(unix)]
+    original: Option<libc::termios>,
+}
+
+impl EchoGuard {
+    #[cfg(unix)]
+    fn disable() -> Self {
+        // Safety: `termios` is a plain C struct that is fully initialised by `tcgetattr` before
+        // it is read, and the file descriptor is that of stdin for the lifetime of the process.
+        unsafe {
+            let mut termios = std::mem::zeroed::<libc::termios>();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut termios) != 0 {
+                // Stdin is not a terminal, e.g. the password is piped in.
+                return Self { original: None };
+            }
+            let original = termios;
+            termios.c_lflag &= !libc::ECHO;
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios);
+            Self {
+                original: Some(original),
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn disable() -> Self {
+        Self {}
+    }
+}
+
+impl Drop for EchoGuard {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Some(original) = &self.original {
+            // Safety: restores the settings previously retrieved by `tcgetattr`.
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, original);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHRASE: &str =
+        "oblige salon price punch saddle immune slogan rare snap desert retire surprise";
+
+    #[test]
+    fn derive_account_keys_from_keystore() {
+        let dir = std::env::temp_dir().join(format!("forc-client-wallet-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut rng = rand::thread_rng();
+        let name = eth_keystore::encrypt_key(&dir, &mut rng, PHRASE, "password").unwrap();
+        let wallet_path = dir.join(name);
+
+        for account in 0..2 {
+            let path = format!("{}/{}'/0/0", DEFAULT_DERIVATION_PATH_PREFIX, account);
+            let expected = SecretKey::new_from_mnemonic_phrase_with_path(PHRASE, &path).unwrap();
+            let secret_key = account_secret_key(&wallet_path, account, "password").unwrap();
+            assert_eq!(secret_key, expected);
+        }
+        assert!(account_secret_key(&wallet_path, 0, "wrong password").is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,206 @@
+//! Source line coverage of executed tests.
+//!
+//! The instructions executed by each test are mapped back to the lines of Sway source from which
+//! they were generated using the package's source map. Only lines within the tested package's own
+//! source files are reported.
+
+use crate::{TestResult, Tested, TestedPackage};
+use anyhow::{Context, Result};
+use fuel_tx as tx;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+use sway_core::source_map::SourceMap;
+
+/// The location of an executed instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InstructionLocation {
+    /// The contract within which the instruction was executed, or `None` for the test script.
+    pub contract_id: Option<tx::ContractId>,
+    /// The byte offset of the instruction within the executed bytecode.
+    pub offset: u64,
+}
+
+/// The number of tests that executed each line of each source file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Coverage {
+    files: BTreeMap<PathBuf, BTreeMap<usize, u64>>,
+}
+
+/// The source map of some bytecode, along with the context within which it is executed.
+struct MappedBytecode<'a> {
+    contract_id: Option<tx::ContractId>,
+    bytecode_len: usize,
+    source_map: &'a SourceMap,
+}
+
+impl Coverage {
+    /// Collect the coverage of all executed tests.
+    ///
+    /// Returns an error if any test was executed without collecting the instructions it executed.
+    pub fn from_tested(tested: &Tested) -> Result<Self> {
+        let pkgs: Vec<&TestedPackage> = match tested {
+            Tested::Package(pkg) => vec![pkg],
+            Tested::Workspace(pkgs) => pkgs.iter().collect(),
+        };
+        let mut coverage = Self::default();
+        let mut line_starts = LineStarts::default();
+        for pkg in pkgs {
+            coverage.add_pkg(pkg, &mut line_starts)?;
+        }
+        Ok(coverage)
+    }
+
+    /// The number of tests that executed the given line, or `None` if the line is not
+    /// instrumented.
+    pub fn hits(&self, file: &Path, line: usize) -> Option<u64> {
+        self.files.get(file)?.get(&line).copied()
+    }
+
+    fn add_pkg(&mut self, pkg: &TestedPackage, line_starts: &mut LineStarts) -> Result<()> {
+        let built = &pkg.built;
+        let mut mapped = vec![MappedBytecode {
+            contract_id: None,
+            bytecode_len: built.bytecode.len(),
+            source_map: built.source_map(),
+        }];
+        // Tests call into the package's own contract as it would be deployed, i.e. without tests.
+        if let (Some(bytecode), Some(source_map)) = (
+            &built.bytecode_without_tests,
+            &built.source_map_without_tests,
+        ) {
+            mapped.push(MappedBytecode {
+                contract_id: Some(crate::contract_id(
+                    bytecode,
+                    &built.storage_slots,
+                    &tx::Salt::zeroed(),
+                )),
+                bytecode_len: bytecode.len(),
+                source_map,
+            });
+        }
+
+        // Collect the lines associated with each instruction.
+        let mut instruction_lines = HashMap::new();
+        for mapped in &mapped {
+            let instruction_count = mapped.bytecode_len / fuel_vm::prelude::Opcode::LEN;
+            for ix in 0..instruction_count {
+                let (path, range) = match mapped.source_map.addr_to_span(ix) {
+                    Some((path, range)) if path.starts_with(&built.manifest_dir) => (path, range),
+                    _ => continue,
+                };
+                let line = line_starts.line(&path, range.start)?;
+                self.files
+                    .entry(path.clone())
+                    .or_default()
+                    .entry(line)
+                    .or_default();
+                let offset = (ix * fuel_vm::prelude::Opcode::LEN) as u64;
+                let location = InstructionLocation {
+                    contract_id: mapped.contract_id,
+                    offset,
+                };
+                instruction_lines.insert(location, (path, line));
+            }
+        }
+
+        // Count the number of tests that executed each line.
+        for test in &pkg.tests {
+            let executed = executed_instructions(test)?;
+            let lines: HashSet<_> = executed
+                .iter()
+                .filter_map(|location| instruction_lines.get(location))
+                .collect();
+            for (path, line) in lines {
+                *self
+                    .files
+                    .entry(path.clone())
+                    .or_default()
+                    .entry(*line)
+                    .or_default() += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Serialize the coverage in the LCOV tracefile format.
+    pub fn to_lcov(&self) -> String {
+        let mut lcov = String::new();
+        for (path, lines) in &self.files {
+            writeln!(lcov, "TN:").unwrap();
+            writeln!(lcov, "SF:{}", path.display()).unwrap();
+            for (line, hits) in lines {
+                writeln!(lcov, "DA:{line},{hits}").unwrap();
+            }
+            let hit = lines.values().filter(|&&hits| hits > 0).count();
+            writeln!(lcov, "LH:{hit}").unwrap();
+            writeln!(lcov, "LF:{}", lines.len()).unwrap();
+            writeln!(lcov, "end_of_record").unwrap();
+        }
+        lcov
+    }
+}
+
+fn executed_instructions(test: &TestResult) -> Result<&HashSet<InstructionLocation>> {
+    test.executed_instructions.as_ref().ok_or_else(|| {
+        anyhow::anyhow!(
+            "coverage was not collected while executing test `{}`",
+            test.name
+        )
+    })
+}
+
+/// The byte offsets at which each line of each source file starts.
+#[derive(Default)]
+struct LineStarts {
+    files: HashMap<PathBuf, Vec<usize>>,
+}
+
+impl LineStarts {
+    /// The line, starting from 1, containing the given byte offset within the given file.
+    fn line(&mut self, path: &Path, offset: usize) -> Result<usize> {
+        if !self.files.contains_key(path) {
+            let src = fs::read_to_string(path)
+                .with_context(|| format!("failed to read source file {}", path.display()))?;
+            let starts = std::iter::once(0)
+                .chain(src.match_indices('\n').map(|(ix, _)| ix + 1))
+                .collect();
+            self.files.insert(path.to_path_buf(), starts);
+        }
+        let starts = &self.files[path];
+        Ok(starts.partition_point(|&start| start <= offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lcov() {
+        let mut coverage = Coverage::default();
+        let lines = coverage
+            .files
+            .entry(PathBuf::from("/pkg/src/main.sw"))
+            .or_default();
+        lines.insert(3, 2);
+        lines.insert(4, 0);
+        lines.insert(7, 1);
+        let expected = "\
+TN:
+SF:/pkg/src/main.sw
+DA:3,2
+DA:4,0
+DA:7,1
+LH:2
+LF:3
+end_of_record
+";
+        assert_eq!(coverage.to_lcov(), expected);
+        assert_eq!(coverage.hits(Path::new("/pkg/src/main.sw"), 4), Some(0));
+        assert_eq!(coverage.hits(Path::new("/pkg/src/main.sw"), 5), None);
+    }
+}
@@ -0,0 +1,249 @@
+//! The transaction and chain state within which each unit test is executed.
+//!
+//! These are configured via the `[test]` section of the package manifest, see
+//! [forc_pkg::manifest::TestEnvironment].
+
+use forc_pkg::manifest::{TestEnvironment, TestInput, TestOutput};
+use fuel_tx as tx;
+use fuel_vm::{
+    self as vm,
+    fuel_storage::Mappable,
+    prelude::{IntoChecked, MerkleRoot, MerkleRootStorage, StorageInspect, StorageMutate, Word},
+    storage::{
+        ContractsAssets, ContractsInfo, ContractsRawCode, ContractsState, InterpreterStorage,
+    },
+};
+use rand::{Rng, SeedableRng};
+use std::borrow::Cow;
+
+/// The block height at which tests are executed unless otherwise specified.
+const DEFAULT_BLOCK_HEIGHT: u32 = u32::MAX >> 1;
+/// The maturity of the test transaction unless otherwise specified.
+const DEFAULT_MATURITY: Word = 1;
+/// The number of seconds between blocks, matching the timestamps produced by `MemoryStorage`.
+const BLOCK_INTERVAL: Word = 10;
+
+/// The storage within which a test is executed.
+///
+/// Wraps the `MemoryStorage` into which the package's contracts are deployed in order to report
+/// the block height and timestamp specified by the test environment.
+pub(crate) struct TestStorage {
+    storage: vm::storage::MemoryStorage,
+    block_height: u32,
+    timestamp: Option<Word>,
+}
+
+type DataError = <vm::storage::MemoryStorage as InterpreterStorage>::DataError;
+
+impl TestStorage {
+    /// Prepare the given storage for executing a test within the given environment.
+    pub(crate) fn new(storage: vm::storage::MemoryStorage, env: &TestEnvironment) -> Self {
+        Self {
+            storage,
+            block_height: block_height(env),
+            timestamp: env.timestamp,
+        }
+    }
+}
+
+/// The block height at which tests within the given environment are executed.
+pub(crate) fn block_height(env: &TestEnvironment) -> u32 {
+    env.block_height.unwrap_or(DEFAULT_BLOCK_HEIGHT)
+}
+
+/// Construct the transaction that executes the given test script within the given environment.
+///
+/// All contracts with the given IDs are provided as inputs so that the test may call into them.
+pub(crate) fn test_tx(
+    script: Vec<u8>,
+    env: &TestEnvironment,
+    contract_ids: &[tx::ContractId],
+    params: &tx::ConsensusParameters,
+) -> anyhow::Result<tx::Checked<tx::Script>> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0x7E57u64);
+    let secret_key = rng.gen();
+    let mut tx_builder =
+        tx::TransactionBuilder::script(script, env.script_data.clone().unwrap_or_default());
+    tx_builder
+        .gas_limit(tx::ConsensusParameters::DEFAULT.max_gas_per_tx)
+        .maturity(env.maturity.unwrap_or(DEFAULT_MATURITY));
+
+    let default_inputs = [TestInput::Coin {
+        amount: 1,
+        asset_id: Default::default(),
+    }];
+    for input in env.inputs.as_deref().unwrap_or(&default_inputs) {
+        match input {
+            TestInput::Coin { amount, asset_id } => {
+                let utxo_id = rng.gen();
+                let tx_ptr = rng.gen();
+                tx_builder
+                    .add_unsigned_coin_input(secret_key, utxo_id, *amount, *asset_id, tx_ptr, 0);
+            }
+            TestInput::Message {
+                amount,
+                sender,
+                data,
+            } => {
+                let nonce = rng.gen();
+                tx_builder.add_unsigned_message_input(
+                    secret_key,
+                    *sender,
+                    nonce,
+                    *amount,
+                    data.clone(),
+                );
+            }
+        }
+    }
+
+    for contract_id in contract_ids {
+        // Each contract output refers to its associated contract input by index.
+        let input_index = u8::try_from(tx_builder.inputs().len())
+            .map_err(|_| anyhow::anyhow!("too many inputs for the test transaction"))?;
+        tx_builder
+            .add_input(tx::Input::contract(
+                rng.gen(),
+                tx::Bytes32::zeroed(),
+                tx::Bytes32::zeroed(),
+                rng.gen(),
+                *contract_id,
+            ))
+            .add_output(tx::Output::contract(
+                input_index,
+                tx::Bytes32::zeroed(),
+                tx::Bytes32::zeroed(),
+            ));
+    }
+
+    for output in env.outputs.iter().flatten() {
+        let output = match *output {
+            TestOutput::Coin {
+                to,
+                amount,
+                asset_id,
+            } => tx::Output::coin(to, amount, asset_id),
+            TestOutput::Change { to, asset_id } => tx::Output::change(to, 0, asset_id),
+            TestOutput::Variable => {
+                tx::Output::variable(tx::Address::zeroed(), 0, tx::AssetId::zeroed())
+            }
+        };
+        tx_builder.add_output(output);
+    }
+
+    tx_builder
+        .finalize()
+        .into_checked(block_height(env) as Word, params)
+        .map_err(|e| anyhow::anyhow!("invalid test transaction: {e:?}"))
+}
+
+impl InterpreterStorage for TestStorage {
+    type DataError = DataError;
+
+    fn block_height(&self) -> Result<u32, DataError> {
+        Ok(self.block_height)
+    }
+
+    fn timestamp(&self, height: u32) -> Result<Word, DataError> {
+        match self.timestamp {
+            // Preceding blocks are assumed to have been produced at a regular interval.
+            Some(timestamp) => {
+                let blocks_ago = self.block_height.saturating_sub(height);
+                Ok(timestamp.saturating_sub(blocks_ago as Word * BLOCK_INTERVAL))
+            }
+            None => self.storage.timestamp(height),
+        }
+    }
+
+    fn block_hash(&self, block_height: u32) -> Result<tx::Bytes32, DataError> {
+        self.storage.block_hash(block_height)
+    }
+
+    fn coinbase(&self) -> Result<tx::Address, DataError> {
+        self.storage.coinbase()
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &tx::ContractId,
+        start_key: &tx::Bytes32,
+        range: Word,
+    ) -> Result<Vec<Option<Cow<'_, tx::Bytes32>>>, DataError> {
+        self.storage
+            .merkle_contract_state_range(id, start_key, range)
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &tx::ContractId,
+        start_key: &tx::Bytes32,
+        values: &[tx::Bytes32],
+    ) -> Result<Option<()>, DataError> {
+        self.storage
+            .merkle_contract_state_insert_range(contract, start_key, values)
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &tx::ContractId,
+        start_key: &tx::Bytes32,
+        range: Word,
+    ) -> Result<Option<()>, DataError> {
+        self.storage
+            .merkle_contract_state_remove_range(contract, start_key, range)
+    }
+}
+
+/// Implement the storage traits for the given table by delegating to the inner `MemoryStorage`.
+macro_rules! delegate_storage {
+    ($table:ty) => {
+        impl StorageInspect<$table> for TestStorage {
+            type Error = DataError;
+
+            fn get(
+                &self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<Cow<'_, <$table as Mappable>::GetValue>>, DataError> {
+                StorageInspect::<$table>::get(&self.storage, key)
+            }
+
+            fn contains_key(&self, key: &<$table as Mappable>::Key) -> Result<bool, DataError> {
+                StorageInspect::<$table>::contains_key(&self.storage, key)
+            }
+        }
+
+        impl StorageMutate<$table> for TestStorage {
+            fn insert(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+                value: &<$table as Mappable>::SetValue,
+            ) -> Result<Option<<$table as Mappable>::GetValue>, DataError> {
+                StorageMutate::<$table>::insert(&mut self.storage, key, value)
+            }
+
+            fn remove(
+                &mut self,
+                key: &<$table as Mappable>::Key,
+            ) -> Result<Option<<$table as Mappable>::GetValue>, DataError> {
+                StorageMutate::<$table>::remove(&mut self.storage, key)
+            }
+        }
+    };
+}
+
+delegate_storage!(ContractsRawCode);
+delegate_storage!(ContractsInfo);
+delegate_storage!(ContractsAssets<'_>);
+delegate_storage!(ContractsState<'_>);
+
+impl MerkleRootStorage<tx::ContractId, ContractsAssets<'_>> for TestStorage {
+    fn root(&mut self, parent: &tx::ContractId) -> Result<MerkleRoot, DataError> {
+        MerkleRootStorage::<tx::ContractId, ContractsAssets>::root(&mut self.storage, parent)
+    }
+}
+
+impl MerkleRootStorage<tx::ContractId, ContractsState<'_>> for TestStorage {
+    fn root(&mut self, parent: &tx::ContractId) -> Result<MerkleRoot, DataError> {
+        MerkleRootStorage::<tx::ContractId, ContractsState>::root(&mut self.storage, parent)
+    }
+}
@@ -0,0 +1,419 @@
+//! Property-based testing of test functions that declare parameters.
+//!
+//! Such tests are executed many times, each time with inputs generated from a seeded RNG and
+//! ABI-encoded as the test transaction's script data. Upon failure, the failing inputs are
+//! repeatedly shrunk towards simpler values for as long as the test continues to fail, and the
+//! resulting minimal counterexample is reported.
+
+use crate::{InstructionLocation, TestExecution, TestPassCondition};
+use anyhow::Result;
+use forc_util::abi::format_value;
+use fuels_core::{abi_encoder::ABIEncoder, StringToken, Token};
+use fuels_types::param_types::ParamType;
+use rand::{distributions::Alphanumeric, rngs::StdRng, Rng, SeedableRng};
+use std::fmt;
+
+/// The number of times each test with parameters is executed unless otherwise specified.
+pub const DEFAULT_FUZZ_RUNS: usize = 256;
+
+/// The maximum number of executions spent shrinking the inputs of a failing test.
+const MAX_SHRINK_RUNS: usize = 1024;
+
+/// Configures the execution of tests that declare parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FuzzConfig {
+    /// The number of times each test is executed with generated inputs.
+    pub runs: usize,
+    /// The seed from which all inputs are generated. Each test derives its own RNG from this seed
+    /// and its name, so the inputs of a test do not depend on which other tests are selected.
+    pub seed: u64,
+}
+
+/// The outcome of executing a test with generated inputs.
+#[derive(Clone, Debug)]
+pub struct FuzzResult {
+    /// The number of times the test was executed with generated inputs, excluding the executions
+    /// spent shrinking a counterexample.
+    pub runs: usize,
+    /// The minimal inputs found for which the test fails. `None` if the test passed.
+    pub counterexample: Option<Counterexample>,
+}
+
+/// The inputs for which a test with parameters fails.
+#[derive(Clone, Debug)]
+pub struct Counterexample {
+    /// The name of each of the test's parameters along with its failing value.
+    pub inputs: Vec<(String, String)>,
+    /// The number of times the originally generated inputs were successfully shrunk.
+    pub shrinks: usize,
+}
+
+/// A parameter of a test function.
+#[derive(Clone, Debug)]
+pub(crate) struct TestParam {
+    pub(crate) name: String,
+    /// The type of the parameter, including the names of its types, fields and variants.
+    pub(crate) param_type: ParamType,
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        Self {
+            runs: DEFAULT_FUZZ_RUNS,
+            seed: 0,
+        }
+    }
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let inputs: Vec<_> = self
+            .inputs
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, value))
+            .collect();
+        write!(f, "({})", inputs.join(", "))
+    }
+}
+
+/// Execute a test with parameters using inputs generated according to the given `config`.
+///
+/// `exec` executes the test with the given script data. Returns the execution of the minimal
+/// counterexample if the test fails, or otherwise the execution of the final run. In both cases,
+/// the returned duration, gas used and executed instructions are aggregated across all runs, with
+/// the gas used being the mean of all runs for passing tests.
+pub(crate) fn fuzz(
+    test_name: &str,
+    params: &[TestParam],
+    condition: &TestPassCondition,
+    config: FuzzConfig,
+    mut exec: impl FnMut(Vec<u8>) -> Result<TestExecution>,
+) -> Result<(TestExecution, FuzzResult)> {
+    let mut rng = StdRng::seed_from_u64(config.seed ^ fnv1a(test_name.as_bytes()));
+    let mut total = Aggregate::default();
+    let mut execute = |inputs: &[Token], total: &mut Aggregate| -> Result<TestExecution> {
+        let script_data = ABIEncoder::encode(inputs)
+            .map_err(|e| anyhow::anyhow!("failed to encode inputs of test {}: {}", test_name, e))?
+            .resolve(0);
+        let execution = exec(script_data)?;
+        total.add(&execution);
+        Ok(execution)
+    };
+
+    let runs = config.runs.max(1);
+    let mut last = None;
+    for run in 1..=runs {
+        let inputs: Vec<Token> = params
+            .iter()
+            .map(|param| generate(&param.param_type, &mut rng))
+            .collect();
+        let execution = execute(&inputs, &mut total)?;
+        if condition.is_satisfied_by(execution.revert_code()) {
+            last = Some(execution);
+            continue;
+        }
+
+        // Greedily accept the first simpler candidate that still fails until none remain.
+        let (mut inputs, mut execution, mut shrinks) = (inputs, execution, 0);
+        let mut budget = MAX_SHRINK_RUNS;
+        'shrinking: while budget > 0 {
+            for candidate in shrink_all(&inputs) {
+                if budget == 0 {
+                    break 'shrinking;
+                }
+                budget -= 1;
+                let candidate_execution = execute(&candidate, &mut total)?;
+                if !condition.is_satisfied_by(candidate_execution.revert_code()) {
+                    inputs = candidate;
+                    execution = candidate_execution;
+                    shrinks += 1;
+                    continue 'shrinking;
+                }
+            }
+            break;
+        }
+
+        let inputs = params
+            .iter()
+            .zip(&inputs)
+            .map(|(param, token)| (param.name.clone(), format_value(&param.param_type, token)))
+            .collect();
+        let result = FuzzResult {
+            runs: run,
+            counterexample: Some(Counterexample { inputs, shrinks }),
+        };
+        return Ok((total.apply(execution, None), result));
+    }
+
+    let execution = last.expect("at least one run is always executed");
+    let result = FuzzResult {
+        runs,
+        counterexample: None,
+    };
+    Ok((total.apply(execution, Some(runs)), result))
+}
+
+/// The duration, gas used and executed instructions accumulated over all executions of a test.
+#[derive(Default)]
+struct Aggregate {
+    duration: std::time::Duration,
+    gas_used: u64,
+    executed_instructions: Option<std::collections::HashSet<InstructionLocation>>,
+}
+
+impl Aggregate {
+    fn add(&mut self, execution: &TestExecution) {
+        self.duration += execution.duration;
+        self.gas_used = self.gas_used.saturating_add(execution.gas_used);
+        if let Some(executed) = &execution.executed_instructions {
+            self.executed_instructions
+                .get_or_insert_with(Default::default)
+                .extend(executed.iter().copied());
+        }
+    }
+
+    /// Replace the duration and executed instructions of the given execution with the totals.
+    ///
+    /// If `runs` is provided, the gas used is replaced with the mean gas used over all runs.
+    fn apply(self, mut execution: TestExecution, runs: Option<usize>) -> TestExecution {
+        execution.duration = self.duration;
+        execution.executed_instructions = self.executed_instructions;
+        if let Some(runs) = runs {
+            execution.gas_used = self.gas_used / runs as u64;
+        }
+        execution
+    }
+}
+
+/// Generate an arbitrary value of the given type.
+fn generate(param_type: &ParamType, rng: &mut StdRng) -> Token {
+    match param_type {
+        ParamType::U8 => Token::U8(generate_int(rng, u8::MAX.into()) as u8),
+        ParamType::U16 => Token::U16(generate_int(rng, u16::MAX.into()) as u16),
+        ParamType::U32 => Token::U32(generate_int(rng, u32::MAX.into()) as u32),
+        ParamType::U64 => Token::U64(generate_int(rng, u64::MAX)),
+        ParamType::Byte => Token::Byte(generate_int(rng, u8::MAX.into()) as u8),
+        ParamType::Bool => Token::Bool(rng.gen()),
+        ParamType::B256 => match rng.gen_ratio(1, 8) {
+            true => Token::B256([0; 32]),
+            false => Token::B256(rng.gen()),
+        },
+        ParamType::Unit => Token::Unit,
+        ParamType::String(len) => {
+            let data = (0..*len)
+                .map(|_| rng.sample(Alphanumeric) as char)
+                .collect();
+            Token::String(StringToken::new(data, *len))
+        }
+        ParamType::Array(elem, len) => {
+            Token::Array((0..*len).map(|_| generate(elem, rng)).collect())
+        }
+        ParamType::Struct { fields, .. } => {
+            Token::Struct(fields.iter().map(|(_, ty)| generate(ty, rng)).collect())
+        }
+        ParamType::Enum { variants, .. } => {
+            let ix = rng.gen_range(0..variants.variants().len());
+            let (_, variant_type) = &variants.variants()[ix];
+            let value = generate(variant_type, rng);
+            Token::Enum(Box::new((ix as u8, value, variants.clone())))
+        }
+        ParamType::Tuple(elems) => Token::Tuple(elems.iter().map(|ty| generate(ty, rng)).collect()),
+        ParamType::Vector(_) => unreachable!("unsupported parameter types are rejected up front"),
+    }
+}
+
+/// Generate an integer no greater than `max`.
+fn generate_int(rng: &mut StdRng, max: u64) -> u64 {
+    // Boundary values are far more likely to expose bugs than uniformly distributed values.
+    match rng.gen_ratio(1, 4) {
+        true => [0, 1, max - 1, max][rng.gen_range(0..4)],
+        false => rng.gen_range(0..=max),
+    }
+}
+
+/// The simplest value of the given type.
+fn minimal(param_type: &ParamType) -> Token {
+    match param_type {
+        ParamType::U8 => Token::U8(0),
+        ParamType::U16 => Token::U16(0),
+        ParamType::U32 => Token::U32(0),
+        ParamType::U64 => Token::U64(0),
+        ParamType::Byte => Token::Byte(0),
+        ParamType::Bool => Token::Bool(false),
+        ParamType::B256 => Token::B256([0; 32]),
+        ParamType::Unit => Token::Unit,
+        ParamType::String(len) => Token::String(StringToken::new("a".repeat(*len), *len)),
+        ParamType::Array(elem, len) => Token::Array(vec![minimal(elem); *len]),
+        ParamType::Struct { fields, .. } => {
+            Token::Struct(fields.iter().map(|(_, ty)| minimal(ty)).collect())
+        }
+        ParamType::Enum { variants, .. } => {
+            let (_, variant_type) = &variants.variants()[0];
+            Token::Enum(Box::new((0, minimal(variant_type), variants.clone())))
+        }
+        ParamType::Tuple(elems) => Token::Tuple(elems.iter().map(minimal).collect()),
+        ParamType::Vector(_) => unreachable!("unsupported parameter types are rejected up front"),
+    }
+}
+
+/// Candidates for the given inputs in which a single value has been replaced with a simpler one.
+fn shrink_all(tokens: &[Token]) -> Vec<Vec<Token>> {
+    let mut candidates = vec![];
+    for (ix, token) in tokens.iter().enumerate() {
+        for simpler in shrink(token) {
+            let mut candidate = tokens.to_vec();
+            candidate[ix] = simpler;
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+/// Candidates that are strictly simpler than the given value, simplest first.
+fn shrink(token: &Token) -> Vec<Token> {
+    match token {
+        Token::U8(v) => shrink_int(*v as u64).map(|v| Token::U8(v as u8)).collect(),
+        Token::U16(v) => shrink_int(*v as u64)
+            .map(|v| Token::U16(v as u16))
+            .collect(),
+        Token::U32(v) => shrink_int(*v as u64)
+            .map(|v| Token::U32(v as u32))
+            .collect(),
+        Token::U64(v) => shrink_int(*v).map(Token::U64).collect(),
+        Token::Byte(v) => shrink_int(*v as u64)
+            .map(|v| Token::Byte(v as u8))
+            .collect(),
+        Token::Bool(true) => vec![Token::Bool(false)],
+        Token::B256(bytes) if *bytes != [0; 32] => {
+            let mut candidates = vec![Token::B256([0; 32])];
+            for ix in (0..32).filter(|&ix| bytes[ix] != 0) {
+                let mut simpler = *bytes;
+                simpler[ix] = 0;
+                candidates.push(Token::B256(simpler));
+            }
+            candidates
+        }
+        Token::String(string) => match string.get_encodable_str() {
+            Ok(s) if s.chars().any(|c| c != 'a') => {
+                vec![Token::String(StringToken::new(
+                    "a".repeat(s.len()),
+                    s.len(),
+                ))]
+            }
+            _ => vec![],
+        },
+        Token::Array(elems) => shrink_all(elems).into_iter().map(Token::Array).collect(),
+        Token::Struct(fields) => shrink_all(fields).into_iter().map(Token::Struct).collect(),
+        Token::Tuple(elems) => shrink_all(elems).into_iter().map(Token::Tuple).collect(),
+        Token::Enum(selector) => {
+            let (ix, value, variants) = &**selector;
+            let earlier_variants = variants.variants()[..*ix as usize]
+                .iter()
+                .enumerate()
+                .map(|(earlier, (_, ty))| (earlier as u8, minimal(ty)));
+            let simpler_values = shrink(value).into_iter().map(|simpler| (*ix, simpler));
+            earlier_variants
+                .chain(simpler_values)
+                .map(|(ix, value)| Token::Enum(Box::new((ix, value, variants.clone()))))
+                .collect()
+        }
+        _ => vec![],
+    }
+}
+
+/// Integers strictly smaller than `v`, from the smallest.
+///
+/// Besides zero, the candidates approach `v` by successively halving the distance from `v`, such
+/// that the boundary at which a test begins to fail is found within a logarithmic number of steps.
+fn shrink_int(v: u64) -> impl Iterator<Item = u64> {
+    let mut candidates = vec![0];
+    candidates.extend((1..u64::BITS).map(|shift| v - (v >> shift)));
+    candidates.push(v.saturating_sub(1));
+    candidates.retain(|&c| c < v);
+    candidates.dedup();
+    candidates.into_iter()
+}
+
+/// The 64-bit FNV-1a hash of the given bytes, which unlike `DefaultHasher` is stable across
+/// platforms and releases.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_vm::state::ProgramState;
+    use fuels_types::enum_variants::EnumVariants;
+
+    #[test]
+    fn shrink_int_candidates() {
+        assert_eq!(shrink_int(0).count(), 0);
+        assert_eq!(shrink_int(1).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(shrink_int(10).collect::<Vec<_>>(), vec![0, 5, 8, 9]);
+    }
+
+    #[test]
+    fn shrink_enum_prefers_earlier_variants() {
+        let variants = EnumVariants::new(vec![
+            ("A".into(), ParamType::Unit),
+            ("B".into(), ParamType::U64),
+        ])
+        .unwrap();
+        let token = Token::Enum(Box::new((1, Token::U64(4), variants.clone())));
+        let candidates = shrink(&token);
+        assert_eq!(
+            candidates[0],
+            Token::Enum(Box::new((0, Token::Unit, variants.clone())))
+        );
+        assert_eq!(
+            candidates[1],
+            Token::Enum(Box::new((1, Token::U64(0), variants)))
+        );
+    }
+
+    #[test]
+    fn shrinks_to_minimal_counterexample() {
+        let param = |name: &str, param_type| TestParam {
+            name: name.into(),
+            param_type,
+        };
+        let params = [param("x", ParamType::U64), param("flag", ParamType::Bool)];
+        // Simulates a test that reverts for any `x >= 100`.
+        let exec = |script_data: Vec<u8>| {
+            let x = u64::from_be_bytes(script_data[..8].try_into().unwrap());
+            let state = match x >= 100 {
+                true => ProgramState::Revert(1),
+                false => ProgramState::Return(0),
+            };
+            Ok(TestExecution {
+                state,
+                receipts: vec![],
+                duration: Default::default(),
+                gas_used: 0,
+                executed_instructions: None,
+            })
+        };
+        let (execution, result) = fuzz(
+            "test_x",
+            &params,
+            &TestPassCondition::ShouldNotRevert,
+            FuzzConfig::default(),
+            exec,
+        )
+        .unwrap();
+        let counterexample = result
+            .counterexample
+            .expect("the test fails for most inputs");
+        assert_eq!(
+            counterexample.inputs,
+            [
+                ("x".to_string(), "100".to_string()),
+                ("flag".to_string(), "false".to_string())
+            ]
+        );
+        assert_eq!(execution.revert_code(), Some(1));
+    }
+}
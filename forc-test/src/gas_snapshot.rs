@@ -0,0 +1,218 @@
+//! Recording and comparing the gas used by unit tests.
+//!
+//! A gas snapshot is a plain-text file with one line per test in the form
+//! `<package>::<test> (gas: <gas_used>)`. Lines are sorted by test path so that snapshots committed
+//! alongside a project produce minimal diffs.
+
+use crate::{Tested, TestedPackage};
+use anyhow::{anyhow, bail, Context, Result};
+use std::{collections::BTreeMap, fmt, fs, path::Path, str::FromStr};
+
+/// The default name of the file in which the gas snapshot of a project is stored.
+pub const GAS_SNAPSHOT_FILE_NAME: &str = ".gas-snapshot";
+
+/// The gas used by each test, keyed by the test's path, i.e. `<package>::<test>`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GasSnapshot {
+    entries: BTreeMap<String, u64>,
+}
+
+/// A test whose gas usage has increased beyond the permitted threshold.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GasRegression {
+    /// The path of the test, i.e. `<package>::<test>`.
+    pub test_path: String,
+    /// The gas used by the test as recorded in the previous snapshot.
+    pub previous: u64,
+    /// The gas used by the test in the current run.
+    pub current: u64,
+}
+
+impl GasSnapshot {
+    /// Collect the gas used by each of the executed tests.
+    pub fn from_tested(tested: &Tested) -> Self {
+        let pkgs: Vec<&TestedPackage> = match tested {
+            Tested::Package(pkg) => vec![pkg],
+            Tested::Workspace(pkgs) => pkgs.iter().collect(),
+        };
+        let entries = pkgs
+            .into_iter()
+            .flat_map(|pkg| {
+                pkg.tests.iter().map(|test| {
+                    let test_path = format!("{}::{}", pkg.built.pkg_name, test.name);
+                    (test_path, test.gas_used)
+                })
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Load the snapshot from the file at the given path.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read gas snapshot {}", path.display()))?;
+        contents
+            .parse()
+            .with_context(|| format!("failed to parse gas snapshot {}", path.display()))
+    }
+
+    /// Write the snapshot to the file at the given path, replacing any existing snapshot.
+    pub fn write_to_file(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_string())
+            .with_context(|| format!("failed to write gas snapshot {}", path.display()))
+    }
+
+    /// Update this snapshot with the entries of the `newer` snapshot, retaining the entries of all
+    /// tests absent from `newer`, e.g. those that were filtered out.
+    pub fn merge(&mut self, newer: GasSnapshot) {
+        self.entries.extend(newer.entries);
+    }
+
+    /// The gas used by the test with the given path, if it was recorded.
+    pub fn gas_used(&self, test_path: &str) -> Option<u64> {
+        self.entries.get(test_path).copied()
+    }
+
+    /// Compare the `current` snapshot against this one, returning all tests whose gas usage has
+    /// increased by more than `threshold_percent` percent.
+    ///
+    /// Tests that are absent from either snapshot are not considered regressions.
+    pub fn regressions(&self, current: &GasSnapshot, threshold_percent: f64) -> Vec<GasRegression> {
+        current
+            .entries
+            .iter()
+            .filter_map(|(test_path, &current)| {
+                let previous = self.gas_used(test_path)?;
+                let permitted = previous as f64 * (1.0 + threshold_percent / 100.0);
+                (current as f64 > permitted).then(|| GasRegression {
+                    test_path: test_path.clone(),
+                    previous,
+                    current,
+                })
+            })
+            .collect()
+    }
+}
+
+impl GasRegression {
+    /// The increase in gas usage as a percentage of the previously recorded gas usage.
+    pub fn percent_increase(&self) -> f64 {
+        let increase = self.current as f64 - self.previous as f64;
+        match self.previous {
+            0 => f64::INFINITY,
+            previous => increase * 100.0 / previous as f64,
+        }
+    }
+}
+
+impl fmt::Display for GasSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (test_path, gas_used) in &self.entries {
+            writeln!(f, "{test_path} (gas: {gas_used})")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GasSnapshot {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let mut entries = BTreeMap::new();
+        for (ix, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (test_path, gas_used) = line
+                .strip_suffix(')')
+                .and_then(|line| line.rsplit_once(" (gas: "))
+                .ok_or_else(|| {
+                    anyhow!(
+                        "line {}: expected `<package>::<test> (gas: <gas_used>)`",
+                        ix + 1
+                    )
+                })?;
+            let gas_used = gas_used
+                .parse()
+                .map_err(|e| anyhow!("line {}: invalid gas value `{gas_used}`: {e}", ix + 1))?;
+            if entries.insert(test_path.to_string(), gas_used).is_some() {
+                bail!("line {}: duplicate entry for test `{test_path}`", ix + 1);
+            }
+        }
+        Ok(Self { entries })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SNAPSHOT: &str = "\
+pkg_a::test_add (gas: 120)
+pkg_a::test_sub (gas: 0)
+pkg_b::test_mul (gas: 1000)
+";
+
+    #[test]
+    fn snapshot_roundtrip() {
+        let snapshot: GasSnapshot = SNAPSHOT.parse().unwrap();
+        assert_eq!(snapshot.gas_used("pkg_a::test_add"), Some(120));
+        assert_eq!(snapshot.gas_used("pkg_b::test_mul"), Some(1000));
+        assert_eq!(snapshot.gas_used("pkg_b::test_div"), None);
+        assert_eq!(snapshot.to_string(), SNAPSHOT);
+    }
+
+    #[test]
+    fn snapshot_invalid_lines() {
+        assert!("pkg_a::test_add 120".parse::<GasSnapshot>().is_err());
+        assert!("pkg_a::test_add (gas: lots)"
+            .parse::<GasSnapshot>()
+            .is_err());
+        assert!("a::b (gas: 1)\na::b (gas: 2)"
+            .parse::<GasSnapshot>()
+            .is_err());
+    }
+
+    #[test]
+    fn snapshot_merge() {
+        let mut snapshot: GasSnapshot = SNAPSHOT.parse().unwrap();
+        let filtered: GasSnapshot = "\
+pkg_a::test_add (gas: 130)
+pkg_a::test_new (gas: 5000)
+"
+        .parse()
+        .unwrap();
+        snapshot.merge(filtered);
+        assert_eq!(
+            snapshot.to_string(),
+            "\
+pkg_a::test_add (gas: 130)
+pkg_a::test_new (gas: 5000)
+pkg_a::test_sub (gas: 0)
+pkg_b::test_mul (gas: 1000)
+"
+        );
+    }
+
+    #[test]
+    fn snapshot_regressions() {
+        let previous: GasSnapshot = SNAPSHOT.parse().unwrap();
+        let current: GasSnapshot = "\
+pkg_a::test_add (gas: 126)
+pkg_a::test_sub (gas: 1)
+pkg_a::test_new (gas: 5000)
+pkg_b::test_mul (gas: 900)
+"
+        .parse()
+        .unwrap();
+
+        let regressions = previous.regressions(&current, 0.0);
+        let regressed: Vec<_> = regressions.iter().map(|r| r.test_path.as_str()).collect();
+        assert_eq!(regressed, ["pkg_a::test_add", "pkg_a::test_sub"]);
+        assert_eq!(regressions[0].percent_increase(), 5.0);
+
+        let regressions = previous.regressions(&current, 5.0);
+        let regressed: Vec<_> = regressions.iter().map(|r| r.test_path.as_str()).collect();
+        assert_eq!(regressed, ["pkg_a::test_sub"]);
+    }
+}
@@ -1,13 +1,32 @@
-use std::{collections::HashSet, fs, path::PathBuf, sync::Arc};
+mod coverage;
+mod environment;
+mod fuzz;
+mod gas_snapshot;
+mod report;
 
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::PathBuf,
+    sync::Arc,
+};
+
+use environment::TestStorage;
 use forc_pkg as pkg;
+use forc_util::abi;
 use fuel_tx as tx;
-use fuel_vm::{self as vm, prelude::Opcode};
-use pkg::BuiltPackage;
-use rand::{Rng, SeedableRng};
-use sway_core::{language::ty::TyFunctionDeclaration, transform::AttributeKind};
+use fuel_vm::{self as vm, prelude::Opcode, storage::InterpreterStorage};
+use pkg::{manifest::TestEnvironment, BuiltPackage};
+use rayon::prelude::*;
+use sway_ast::Literal;
+use sway_core::{language::ty::TyFunctionDeclaration, transform::AttributeKind, FinalizedEntry};
 use sway_types::{Span, Spanned};
 
+pub use coverage::{Coverage, InstructionLocation};
+pub use fuzz::{Counterexample, FuzzConfig, FuzzResult, DEFAULT_FUZZ_RUNS};
+pub use gas_snapshot::{GasRegression, GasSnapshot, GAS_SNAPSHOT_FILE_NAME};
+pub use report::{PackageReport, TestCaseReport, TestReport};
+
 /// The result of a `forc test` invocation.
 #[derive(Debug)]
 pub enum Tested {
@@ -21,13 +40,17 @@ pub struct TestedPackage {
     pub built: Box<pkg::BuiltPackage>,
     /// The resulting `ProgramState` after executing the test.
     pub tests: Vec<TestResult>,
+    /// The names of the tests that were skipped due to the `#[test(ignore)]` attribute.
+    pub ignored: Vec<String>,
+    /// The number of tests that were not executed as they did not match the test filter.
+    pub filtered_out: usize,
 }
 
 #[derive(Debug)]
 pub struct TestDetails {
     /// The file that contains the test function.
     pub file_path: Arc<PathBuf>,
-    /// The line number for the test declaration.
+    /// The line number for the test declaration, starting from 1.
     pub line_number: usize,
 }
 
@@ -44,15 +67,121 @@ pub struct TestResult {
     pub state: vm::state::ProgramState,
     /// The required state of the VM for this test to pass.
     pub condition: TestPassCondition,
+    /// The receipts produced by executing the test function.
+    pub receipts: Vec<tx::Receipt>,
+    /// The amount of gas consumed by executing the test function.
+    ///
+    /// For tests with parameters, this is the mean gas used over all runs if the test passed, or
+    /// the gas used by the counterexample if it failed.
+    pub gas_used: u64,
+    /// The locations of all instructions executed by the test function.
+    ///
+    /// Only `Some` if coverage was collected while running the tests.
+    pub executed_instructions: Option<HashSet<InstructionLocation>>,
+    /// The outcome of executing the test with generated inputs. Only `Some` for tests that
+    /// declare parameters.
+    pub fuzz: Option<FuzzResult>,
+}
+
+/// A value logged during the execution of a test, decoded using the package's JSON ABI.
+#[derive(Debug)]
+pub struct DecodedLog {
+    /// The log ID associating the logged value with its type within the JSON ABI.
+    pub log_id: u64,
+    /// The human-readable representation of the decoded value.
+    pub value: String,
 }
 
 /// The possible conditions for a test result to be considered "passing".
 #[derive(Debug)]
 pub enum TestPassCondition {
-    ShouldRevert,
+    /// The test must revert. If a revert code is specified, the test must revert with that code.
+    ShouldRevert(Option<u64>),
     ShouldNotRevert,
 }
 
+/// The number of threads used to run tests concurrently.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TestRunnerCount {
+    /// Use the given number of threads.
+    Manual(usize),
+    /// Let the test runner decide, i.e. one thread per available CPU.
+    #[default]
+    Auto,
+}
+
+/// Describes how tests declared with `#[test(ignore)]` are treated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IgnoredTests {
+    /// Ignored tests are not executed.
+    #[default]
+    Skip,
+    /// Only ignored tests are executed.
+    Only,
+    /// Ignored tests are executed along with all other tests.
+    Include,
+}
+
+/// Describes how the names of tests are matched against a filter.
+#[derive(Clone, Debug)]
+pub enum TestNameFilter {
+    /// Tests whose names contain the given phrase are selected.
+    Substring(String),
+    /// Only the test whose name is exactly the given phrase is selected.
+    Exact(String),
+    /// Tests whose names match the given regular expression are selected.
+    Regex(regex::Regex),
+}
+
+/// Selects which of the built tests are executed.
+#[derive(Clone, Debug, Default)]
+pub struct TestFilter {
+    /// If set, only tests whose names match this filter are executed.
+    pub name_filter: Option<TestNameFilter>,
+    /// How to treat tests declared with `#[test(ignore)]`.
+    pub ignored: IgnoredTests,
+}
+
+/// The contracts deployed within the test environment of a package, along with the storage into
+/// which they were deployed.
+#[derive(Clone, Debug, Default)]
+struct TestDeployment {
+    /// The storage with all contracts deployed. Each test begins with its own copy.
+    storage: vm::storage::MemoryStorage,
+    /// The IDs of the deployed contracts, provided as inputs to each test's transaction.
+    contract_ids: Vec<tx::ContractId>,
+}
+
+/// Options that apply to the execution of every test.
+#[derive(Clone, Copy, Debug)]
+struct RunConfig {
+    collect_coverage: bool,
+    fuzz_config: FuzzConfig,
+}
+
+/// The arguments provided to a test's `#[test]` attribute.
+struct TestArgs {
+    condition: TestPassCondition,
+    ignore: bool,
+}
+
+/// A test entry point that has been selected for execution.
+struct TestSetup<'a> {
+    entry: &'a FinalizedEntry,
+    span: Span,
+    condition: TestPassCondition,
+    /// The parameters for which inputs are generated. Empty for tests without parameters.
+    params: Vec<fuzz::TestParam>,
+}
+
+/// The test entry points of a package, partitioned according to a `TestFilter`.
+#[derive(Default)]
+struct TestSetups<'a> {
+    selected: Vec<TestSetup<'a>>,
+    ignored: Vec<String>,
+    filtered_out: usize,
+}
+
 /// A package or a workspace that has been built, ready for test execution.
 pub enum BuiltTests {
     Package(Box<pkg::BuiltPackage>),
@@ -65,6 +194,7 @@ pub struct Opts {
     pub pkg: pkg::PkgOpts,
     pub print: pkg::PrintOpts,
     pub minify: pkg::MinifyOpts,
+    pub features: pkg::FeatureOpts,
     /// If set, outputs a binary file representing the script bytes.
     pub binary_outfile: Option<String>,
     /// If set, outputs source file mapping in JSON format
@@ -78,6 +208,9 @@ pub struct Opts {
     pub release: bool,
     /// Output the time elapsed over each part of the compilation process.
     pub time_phases: bool,
+    /// Compile every package from source, rather than reusing unchanged dependencies from the
+    /// build cache.
+    pub no_cache: bool,
 }
 
 impl Opts {
@@ -87,11 +220,15 @@ impl Opts {
             pkg: self.pkg,
             print: self.print,
             minify: self.minify,
+            features: self.features,
             binary_outfile: self.binary_outfile,
             debug_outfile: self.debug_outfile,
             build_profile: self.build_profile,
             release: self.release,
             time_phases: self.time_phases,
+            // The tested packages themselves are always compiled, though their dependencies may be
+            // reused from the build cache.
+            no_cache: self.no_cache,
             tests: true,
         }
     }
@@ -100,14 +237,44 @@ impl Opts {
 impl TestResult {
     /// Whether or not the test passed.
     pub fn passed(&self) -> bool {
-        match &self.condition {
-            TestPassCondition::ShouldRevert => {
-                matches!(self.state, vm::state::ProgramState::Revert(_))
-            }
-            TestPassCondition::ShouldNotRevert => {
-                !matches!(self.state, vm::state::ProgramState::Revert(_))
+        self.condition.is_satisfied_by(self.revert_code())
+    }
+
+    /// The revert code with which the test reverted, if it reverted.
+    pub fn revert_code(&self) -> Option<u64> {
+        revert_code(&self.state)
+    }
+
+    /// Describe the expected outcome of the test along with its actual outcome.
+    pub fn failure_reason(&self) -> String {
+        let expected = match self.condition {
+            TestPassCondition::ShouldNotRevert => "not to revert".to_string(),
+            TestPassCondition::ShouldRevert(None) => "to revert".to_string(),
+            TestPassCondition::ShouldRevert(Some(code)) => {
+                format!("to revert with code {} ({:#x})", code, code)
             }
-        }
+        };
+        let actual = match self.revert_code() {
+            Some(code) => format!("reverted with code {} ({:#x})", code, code),
+            None => "did not revert".to_string(),
+        };
+        let counterexample = self
+            .fuzz
+            .as_ref()
+            .and_then(|fuzz| fuzz.counterexample.as_ref())
+            .map(|counterexample| format!("; counterexample: {}", counterexample))
+            .unwrap_or_default();
+        format!("expected {}, but {}{}", expected, actual, counterexample)
+    }
+
+    /// The `Log` and `LogData` receipts emitted while executing the test, in order of emission.
+    pub fn log_receipts(&self) -> impl Iterator<Item = &tx::Receipt> {
+        self.receipts.iter().filter(|receipt| {
+            matches!(
+                receipt,
+                tx::Receipt::Log { .. } | tx::Receipt::LogData { .. }
+            )
+        })
     }
 
     /// Return `TestDetails` from the span of the function declaring this test.
@@ -123,7 +290,8 @@ impl TestResult {
             .chars()
             .into_iter()
             .filter(|&c| c == '\n')
-            .count();
+            .count()
+            + 1;
         Ok(TestDetails {
             file_path,
             line_number,
@@ -131,21 +299,104 @@ impl TestResult {
     }
 }
 
+impl TestPassCondition {
+    /// Whether a test that reverted with the given code, or did not revert, meets the condition.
+    fn is_satisfied_by(&self, revert_code: Option<u64>) -> bool {
+        match (self, revert_code) {
+            (TestPassCondition::ShouldRevert(None), Some(_)) => true,
+            (TestPassCondition::ShouldRevert(Some(expected)), Some(actual)) => *expected == actual,
+            (TestPassCondition::ShouldRevert(_), None) => false,
+            (TestPassCondition::ShouldNotRevert, revert_code) => revert_code.is_none(),
+        }
+    }
+}
+
+impl TestedPackage {
+    /// Decode the values logged by the given test using the logged types declared within this
+    /// package's JSON ABI.
+    pub fn decoded_logs(&self, test: &TestResult) -> anyhow::Result<Vec<DecodedLog>> {
+        let program_abi = &self.built.json_abi_program;
+        test.log_receipts()
+            .map(|receipt| match receipt {
+                tx::Receipt::Log { ra, rb, .. } => (*rb, ra.to_be_bytes().to_vec()),
+                tx::Receipt::LogData { rb, data, .. } => (*rb, data.clone()),
+                _ => unreachable!("`log_receipts` only yields `Log` and `LogData` receipts"),
+            })
+            .map(|(log_id, data)| {
+                let value = abi::decode_log(program_abi, log_id, &data)?;
+                Ok(DecodedLog { log_id, value })
+            })
+            .collect()
+    }
+}
+
 impl BuiltTests {
-    /// The total number of tests.
-    pub fn test_count(&self) -> usize {
+    /// The total number of tests that will be executed given the `test_filter`.
+    pub fn test_count(&self, test_filter: &TestFilter) -> anyhow::Result<usize> {
         let pkgs: Vec<&BuiltPackage> = match self {
             BuiltTests::Package(pkg) => vec![pkg],
             BuiltTests::Workspace(workspace) => workspace.iter().collect(),
         };
-        pkgs.iter()
-            .map(|pkg| pkg.entries.iter().filter(|e| e.is_test()).count())
-            .sum()
+        let mut count = 0;
+        for pkg in pkgs {
+            count += test_setups(pkg, test_filter)?.selected.len();
+        }
+        Ok(count)
     }
 
-    /// Run all built tests, return the result.
-    pub fn run(self) -> anyhow::Result<Tested> {
-        run_tests(self)
+    /// Run all built tests that match the given `test_filter`, return the result.
+    ///
+    /// Tests are executed concurrently on a thread pool sized according to `test_runner_count`.
+    /// The order of the resulting `TestResult`s always matches the order of the test entries.
+    ///
+    /// If `collect_coverage` is `true`, the instructions executed by each test are retained so
+    /// that a [Coverage] report may be produced. Tests that declare parameters are executed with
+    /// inputs generated according to the `fuzz_config`.
+    pub fn run(
+        self,
+        test_runner_count: TestRunnerCount,
+        test_filter: &TestFilter,
+        collect_coverage: bool,
+        fuzz_config: FuzzConfig,
+    ) -> anyhow::Result<Tested> {
+        if collect_coverage && !cfg!(feature = "coverage") {
+            anyhow::bail!("collecting coverage requires the `coverage` feature of `forc-test`");
+        }
+        let test_runners = match test_runner_count {
+            TestRunnerCount::Manual(runner_count) => rayon::ThreadPoolBuilder::new()
+                .num_threads(runner_count)
+                .build(),
+            TestRunnerCount::Auto => rayon::ThreadPoolBuilder::new().build(),
+        }?;
+        let run_config = RunConfig {
+            collect_coverage,
+            fuzz_config,
+        };
+        run_tests(self, &test_runners, test_filter, run_config)
+    }
+}
+
+impl TestFilter {
+    /// Whether or not this filter may exclude tests other than those declared with
+    /// `#[test(ignore)]`.
+    pub fn is_partial(&self) -> bool {
+        self.name_filter.is_some() || self.ignored == IgnoredTests::Only
+    }
+
+    /// Whether or not the test with the given name and `ignore` argument should be executed.
+    fn selects(&self, test_name: &str, ignore: bool) -> bool {
+        let name_matches = match &self.name_filter {
+            None => true,
+            Some(TestNameFilter::Substring(phrase)) => test_name.contains(phrase.as_str()),
+            Some(TestNameFilter::Exact(phrase)) => test_name == phrase,
+            Some(TestNameFilter::Regex(regex)) => regex.is_match(test_name),
+        };
+        let ignore_matches = match self.ignored {
+            IgnoredTests::Skip => !ignore,
+            IgnoredTests::Only => ignore,
+            IgnoredTests::Include => true,
+        };
+        name_matches && ignore_matches
     }
 }
 
@@ -161,82 +412,340 @@ pub fn build(opts: Opts) -> anyhow::Result<BuiltTests> {
     Ok(built_tests)
 }
 
-fn test_pass_condition(
+/// Parse the arguments of the `#[test]` attribute for the given test function declaration.
+///
+/// Revert codes may refer to the `std::error_signals` of the given package by name.
+fn test_args(
+    built_pkg: &BuiltPackage,
     test_function_decl: &TyFunctionDeclaration,
-) -> anyhow::Result<TestPassCondition> {
-    let test_args: HashSet<String> = test_function_decl
+) -> anyhow::Result<TestArgs> {
+    let test_args = test_function_decl
         .attributes
         .get(&AttributeKind::Test)
         .expect("test declaration is missing test attribute")
         .iter()
-        .flat_map(|attr| attr.args.iter().map(|arg| arg.to_string()))
-        .collect();
+        .flat_map(|attr| attr.args.iter());
     let test_name = &test_function_decl.name;
-    if test_args.is_empty() {
-        Ok(TestPassCondition::ShouldNotRevert)
-    } else if test_args.get("should_revert").is_some() {
-        Ok(TestPassCondition::ShouldRevert)
-    } else {
-        anyhow::bail!("Invalid test argument(s) for test: {test_name}.")
+    let mut condition = TestPassCondition::ShouldNotRevert;
+    let mut ignore = false;
+    for arg in test_args {
+        match (arg.name.as_str(), &arg.value) {
+            ("should_revert", None) => condition = TestPassCondition::ShouldRevert(None),
+            ("should_revert", Some(value)) => {
+                let revert_code = revert_code_from_literal(value, &built_pkg.error_signals)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Invalid revert code `{}` for test: {}. Expected a `u64` or \
+                        the name of a signal declared within `std::error_signals`.",
+                            value.span().as_str(),
+                            test_name
+                        )
+                    })?;
+                condition = TestPassCondition::ShouldRevert(Some(revert_code));
+            }
+            ("ignore", None) => ignore = true,
+            _ => anyhow::bail!("Invalid test argument(s) for test: {test_name}."),
+        }
+    }
+    Ok(TestArgs { condition, ignore })
+}
+
+/// Parse the expected revert code provided via `#[test(should_revert = "...")]`.
+///
+/// The code may be an integer literal, a string containing a decimal or `0x`-prefixed hexadecimal
+/// integer, or a string containing the name of one of the given `error_signals`.
+fn revert_code_from_literal(
+    literal: &Literal,
+    error_signals: &BTreeMap<String, u64>,
+) -> Option<u64> {
+    match literal {
+        Literal::Int(lit_int) => u64::try_from(&lit_int.parsed).ok(),
+        Literal::String(lit_string) => {
+            let code = lit_string.parsed.trim();
+            let signal_name = code.trim_start_matches("std::error_signals::");
+            if let Some(signal) = error_signals.get(signal_name) {
+                return Some(*signal);
+            }
+            let code = code.replace('_', "");
+            match code.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16).ok(),
+                None => code.parse().ok(),
+            }
+        }
+        Literal::Char(_) | Literal::Bool(_) => None,
+    }
+}
+
+/// Collect the test entries of the given package, partitioned by whether or not they are
+/// selected by the `test_filter`.
+fn test_setups<'a>(
+    built_pkg: &'a BuiltPackage,
+    test_filter: &TestFilter,
+) -> anyhow::Result<TestSetups<'a>> {
+    let mut setups = TestSetups::default();
+    for entry in built_pkg.entries.iter().filter(|entry| entry.is_test()) {
+        let test_decl_id = entry
+            .test_decl_id
+            .clone()
+            .expect("test entry point is missing declaration id");
+        let span = test_decl_id.span();
+        let test_function_decl = built_pkg
+            .declaration_engine
+            .get_function(test_decl_id, &span)
+            .expect("declaration engine is missing function declaration for test");
+        let TestArgs { condition, ignore } = test_args(built_pkg, &test_function_decl)?;
+        if test_filter.selects(&entry.fn_name, ignore) {
+            let params = test_params(built_pkg, &test_function_decl)?;
+            setups.selected.push(TestSetup {
+                entry,
+                span,
+                condition,
+                params,
+            });
+        } else if ignore && test_filter.selects(&entry.fn_name, false) {
+            setups.ignored.push(entry.fn_name.clone());
+        } else {
+            setups.filtered_out += 1;
+        }
     }
+    Ok(setups)
+}
+
+/// The parameters of the given test function, along with the types from which their inputs are
+/// generated.
+fn test_params(
+    built_pkg: &BuiltPackage,
+    test_function_decl: &TyFunctionDeclaration,
+) -> anyhow::Result<Vec<fuzz::TestParam>> {
+    if test_function_decl.parameters.is_empty() {
+        return Ok(vec![]);
+    }
+    let test_name = test_function_decl.name.as_str();
+    let param_names: Vec<&str> = test_function_decl
+        .parameters
+        .iter()
+        .map(|param| param.name.as_str())
+        .collect();
+    let test_abi = &built_pkg.json_abi_tests;
+    let abi_function = test_abi
+        .functions
+        .iter()
+        .find(|function| {
+            function.name == test_name
+                && function
+                    .inputs
+                    .iter()
+                    .map(|input| input.name.as_str())
+                    .eq(param_names.iter().copied())
+        })
+        .ok_or_else(|| anyhow::anyhow!("missing JSON ABI for test function {}", test_name))?;
+    let type_lookup = abi::type_lookup(test_abi);
+    abi_function
+        .inputs
+        .iter()
+        .map(|input| {
+            let param_type = abi::resolve_param_type(input, &type_lookup)?;
+            if abi::contains_heap_type(&param_type) {
+                anyhow::bail!(
+                    "Unsupported type for parameter `{}` of test: {}. Inputs cannot be \
+                    generated for heap types such as `Vec`.",
+                    input.name,
+                    test_name
+                );
+            }
+            Ok(fuzz::TestParam {
+                name: input.name.clone(),
+                param_type,
+            })
+        })
+        .collect()
 }
 
 /// Build the the given package and run its tests, returning the results.
-fn run_tests(built: BuiltTests) -> anyhow::Result<Tested> {
+fn run_tests(
+    built: BuiltTests,
+    test_runners: &rayon::ThreadPool,
+    test_filter: &TestFilter,
+    run_config: RunConfig,
+) -> anyhow::Result<Tested> {
     match built {
         BuiltTests::Package(pkg) => {
-            let tested_pkg = run_pkg_tests(*pkg)?;
+            let tested_pkg = run_pkg_tests(*pkg, test_runners, test_filter, run_config)?;
             Ok(Tested::Package(Box::new(tested_pkg)))
         }
         BuiltTests::Workspace(workspace) => {
             let tested_pkgs = workspace
                 .into_iter()
-                .map(run_pkg_tests)
+                .map(|pkg| run_pkg_tests(pkg, test_runners, test_filter, run_config))
                 .collect::<anyhow::Result<Vec<TestedPackage>>>()?;
             Ok(Tested::Workspace(tested_pkgs))
         }
     }
 }
 
-fn run_pkg_tests(built_pkg: BuiltPackage) -> anyhow::Result<TestedPackage> {
-    // Run all tests and collect their results.
-    // TODO: We can easily parallelise this, but let's wait until testing is stable first.
-    let tests = built_pkg
-        .entries
-        .iter()
-        .filter(|entry| entry.is_test())
-        .map(|entry| {
-            let offset = u32::try_from(entry.imm).expect("test instruction offset out of range");
-            let name = entry.fn_name.clone();
-            let (state, duration) = exec_test(&built_pkg.bytecode, offset);
-            let test_decl_id = entry
-                .test_decl_id
-                .clone()
-                .expect("test entry point is missing declaration id");
-            let span = test_decl_id.span();
-            let test_function_decl = built_pkg
-                .declaration_engine
-                .get_function(test_decl_id, &span)
-                .expect("declaration engine is missing function declaration for test");
-            let condition = test_pass_condition(&test_function_decl)?;
-            Ok(TestResult {
-                name,
-                duration,
-                span,
-                state,
-                condition,
-            })
-        })
-        .collect::<anyhow::Result<_>>()?;
+fn run_pkg_tests(
+    built_pkg: BuiltPackage,
+    test_runners: &rayon::ThreadPool,
+    test_filter: &TestFilter,
+    run_config: RunConfig,
+) -> anyhow::Result<TestedPackage> {
+    let TestSetups {
+        selected,
+        ignored,
+        filtered_out,
+    } = test_setups(&built_pkg, test_filter)?;
+
+    // Deploy the package's contract and its contract dependencies so that tests may call them.
+    let deployment = TestDeployment::from_built_pkg(&built_pkg)?;
+
+    // Run all selected tests concurrently and collect their results. Each test executes within its
+    // own interpreter and storage, and `collect` preserves the order of the test entries.
+    let tests = test_runners.install(|| {
+        selected
+            .into_par_iter()
+            .map(|setup| run_test(setup, &built_pkg, &deployment, run_config))
+            .collect::<anyhow::Result<_>>()
+    })?;
 
     let tested_pkg = TestedPackage {
         built: Box::new(built_pkg),
         tests,
+        ignored,
+        filtered_out,
     };
 
     Ok(tested_pkg)
 }
 
+/// Run a single selected test of the given package, returning its result.
+///
+/// Tests that declare parameters are executed repeatedly with generated inputs.
+fn run_test(
+    setup: TestSetup,
+    built_pkg: &BuiltPackage,
+    deployment: &TestDeployment,
+    run_config: RunConfig,
+) -> anyhow::Result<TestResult> {
+    let TestSetup {
+        entry,
+        span,
+        condition,
+        params,
+    } = setup;
+    let offset = u32::try_from(entry.imm).expect("test instruction offset out of range");
+    let name = entry.fn_name.clone();
+    let exec = |test_env: &TestEnvironment| {
+        exec_test(
+            &built_pkg.bytecode,
+            offset,
+            deployment,
+            test_env,
+            run_config.collect_coverage,
+        )
+    };
+    let test_env = built_pkg.test_environment.for_test(&name);
+    let (execution, fuzz) = match params.is_empty() {
+        true => (exec(&test_env)?, None),
+        false => {
+            // The generated inputs are provided in place of any configured script data.
+            let (execution, fuzz) = fuzz::fuzz(
+                &name,
+                &params,
+                &condition,
+                run_config.fuzz_config,
+                |script_data| {
+                    exec(&TestEnvironment {
+                        script_data: Some(script_data),
+                        ..test_env.clone()
+                    })
+                },
+            )?;
+            (execution, Some(fuzz))
+        }
+    };
+    let TestExecution {
+        state,
+        receipts,
+        duration,
+        gas_used,
+        executed_instructions,
+    } = execution;
+    Ok(TestResult {
+        name,
+        duration,
+        span,
+        state,
+        condition,
+        receipts,
+        gas_used,
+        executed_instructions,
+        fuzz,
+    })
+}
+
+impl TestDeployment {
+    /// Deploy the package's contract (if any) along with all of its contract dependencies.
+    ///
+    /// The package's own contract is deployed without its tests using a zeroed salt, matching the
+    /// `CONTRACT_ID` provided to its tests.
+    fn from_built_pkg(built_pkg: &BuiltPackage) -> anyhow::Result<Self> {
+        let mut deployment = Self::default();
+        for contract_dep in &built_pkg.contract_dependencies {
+            deployment.deploy(
+                &contract_dep.bytecode,
+                &contract_dep.storage_slots,
+                &contract_dep.salt,
+            )?;
+        }
+        if let Some(bytecode) = &built_pkg.bytecode_without_tests {
+            deployment.deploy(bytecode, &built_pkg.storage_slots, &tx::Salt::zeroed())?;
+        }
+        Ok(deployment)
+    }
+
+    /// Deploy the given contract into storage with its storage slots initialized.
+    fn deploy(
+        &mut self,
+        bytecode: &[u8],
+        storage_slots: &[tx::StorageSlot],
+        salt: &tx::Salt,
+    ) -> anyhow::Result<()> {
+        let contract = tx::Contract::from(bytecode.to_vec());
+        let root = contract.root();
+        let storage_slots = sorted_storage_slots(storage_slots);
+        let contract_id = contract_id(bytecode, &storage_slots, salt);
+        self.storage.deploy_contract_with_id(
+            salt,
+            &storage_slots,
+            &contract,
+            &root,
+            &contract_id,
+        )?;
+        self.contract_ids.push(contract_id);
+        Ok(())
+    }
+}
+
+/// Storage slots sorted by key, as required to compute the contract's initial state root.
+fn sorted_storage_slots(storage_slots: &[tx::StorageSlot]) -> Vec<tx::StorageSlot> {
+    let mut storage_slots = storage_slots.to_vec();
+    storage_slots.sort();
+    storage_slots
+}
+
+/// The ID of the given contract when deployed with the given storage slots and salt.
+fn contract_id(
+    bytecode: &[u8],
+    storage_slots: &[tx::StorageSlot],
+    salt: &tx::Salt,
+) -> tx::ContractId {
+    let contract = tx::Contract::from(bytecode.to_vec());
+    let root = contract.root();
+    let storage_slots = sorted_storage_slots(storage_slots);
+    let state_root = tx::Contract::initial_state_root(storage_slots.iter());
+    contract.id(salt, &root, &state_root)
+}
+
 /// Given some bytecode and an instruction offset for some test's desired entry point, patch the
 /// bytecode with a `JI` (jump) instruction to jump to the desired test.
 ///
@@ -274,36 +783,116 @@ fn patch_test_bytecode(bytecode: &[u8], test_offset: u32) -> std::borrow::Cow<[u
     std::borrow::Cow::Owned(patched)
 }
 
+/// The outcome of executing a single test.
+struct TestExecution {
+    state: vm::state::ProgramState,
+    receipts: Vec<tx::Receipt>,
+    duration: std::time::Duration,
+    gas_used: u64,
+    executed_instructions: Option<HashSet<InstructionLocation>>,
+}
+
+impl TestExecution {
+    /// The revert code with which the test reverted, if it reverted.
+    fn revert_code(&self) -> Option<u64> {
+        revert_code(&self.state)
+    }
+}
+
+/// The revert code of the given final program state, if the program reverted.
+fn revert_code(state: &vm::state::ProgramState) -> Option<u64> {
+    match state {
+        vm::state::ProgramState::Revert(revert_code) => Some(*revert_code),
+        _ => None,
+    }
+}
+
 // Execute the test whose entry point is at the given instruction offset as if it were a script.
-fn exec_test(bytecode: &[u8], test_offset: u32) -> (vm::state::ProgramState, std::time::Duration) {
+//
+// The test is executed within the transaction and block described by the package's test
+// environment, with all contracts within the deployment provided as inputs. If `collect_coverage`
+// is `true`, the locations of all executed instructions are also returned.
+fn exec_test(
+    bytecode: &[u8],
+    test_offset: u32,
+    deployment: &TestDeployment,
+    test_env: &TestEnvironment,
+    #[cfg_attr(not(feature = "coverage"), allow(unused_variables))] collect_coverage: bool,
+) -> anyhow::Result<TestExecution> {
     // Patch the bytecode to jump to the relevant test.
     let bytecode = patch_test_bytecode(bytecode, test_offset).into_owned();
 
     // Create a transaction to execute the test function.
-    let script_input_data = vec![];
-    let mut rng = rand::rngs::StdRng::seed_from_u64(0x7E57u64);
-    let maturity = 1;
-    let block_height = (u32::MAX >> 1) as u64;
-    let secret_key = rng.gen();
-    let utxo_id = rng.gen();
-    let amount = 1;
-    let asset_id = Default::default();
-    let tx_ptr = rng.gen();
     let params = tx::ConsensusParameters::default();
-    let tx = tx::TransactionBuilder::script(bytecode, script_input_data)
-        .add_unsigned_coin_input(secret_key, utxo_id, amount, asset_id, tx_ptr, 0)
-        .gas_limit(tx::ConsensusParameters::DEFAULT.max_gas_per_tx)
-        .maturity(maturity)
-        .finalize_checked(block_height as tx::Word, &params);
-
-    // Setup the interpreter.
-    let storage = vm::storage::MemoryStorage::default();
+    let tx = environment::test_tx(bytecode, test_env, &deployment.contract_ids, &params)?;
+
+    // Setup the interpreter. Each test gets its own copy of the deployment's storage so that
+    // concurrently executing tests cannot observe each other's side effects.
+    let storage = TestStorage::new(deployment.storage.clone(), test_env);
     let mut interpreter = vm::interpreter::Interpreter::with_storage(storage, params);
 
     // Execute and return the result.
     let start = std::time::Instant::now();
-    let transition = interpreter.transact(tx).unwrap();
+    let state = *interpreter.transact(tx)?.state();
     let duration = start.elapsed();
-    let state = *transition.state();
-    (state, duration)
+    let receipts = interpreter.receipts().to_vec();
+    let gas_used = receipts
+        .iter()
+        .find_map(tx::Receipt::gas_used)
+        .expect("script execution must produce a `ScriptResult` receipt");
+    // The interpreter records the location of every instruction for which gas is charged.
+    #[cfg(feature = "coverage")]
+    let executed_instructions = collect_coverage.then(|| {
+        interpreter
+            .profiler()
+            .data()
+            .coverage()
+            .iter()
+            .map(|location| InstructionLocation {
+                contract_id: location.context(),
+                offset: location.offset(),
+            })
+            .collect()
+    });
+    #[cfg(not(feature = "coverage"))]
+    let executed_instructions = None;
+    Ok(TestExecution {
+        state,
+        receipts,
+        duration,
+        gas_used,
+        executed_instructions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(name_filter: TestNameFilter) -> TestFilter {
+        TestFilter {
+            name_filter: Some(name_filter),
+            ignored: IgnoredTests::Skip,
+        }
+    }
+
+    #[test]
+    fn test_filter_selects_by_name() {
+        let substring = filter(TestNameFilter::Substring("add".to_string()));
+        assert!(substring.selects("test_add", false));
+        assert!(substring.selects("test_add_overflow", false));
+        assert!(!substring.selects("test_sub", false));
+
+        let exact = filter(TestNameFilter::Exact("test_add".to_string()));
+        assert!(exact.selects("test_add", false));
+        assert!(!exact.selects("test_add_overflow", false));
+
+        let regex = filter(TestNameFilter::Regex(
+            regex::Regex::new("^test_(add|sub)$").unwrap(),
+        ));
+        assert!(regex.selects("test_add", false));
+        assert!(regex.selects("test_sub", false));
+        assert!(!regex.selects("test_add_overflow", false));
+        assert!(!regex.selects("test_add", true));
+    }
 }
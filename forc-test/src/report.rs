@@ -0,0 +1,310 @@
+//! Machine-readable reports of executed tests.
+//!
+//! A [TestReport] may be serialized as JSON or as JUnit XML so that test results can be consumed
+//! by CI tooling.
+
+use crate::{TestPassCondition, TestResult, Tested, TestedPackage};
+use anyhow::Result;
+use serde::Serialize;
+use std::{fmt::Write, path::PathBuf};
+
+/// A report of all executed tests, grouped by package.
+#[derive(Clone, Debug, Serialize)]
+pub struct TestReport {
+    pub packages: Vec<PackageReport>,
+}
+
+/// A report of the tests executed for a single package.
+#[derive(Clone, Debug, Serialize)]
+pub struct PackageReport {
+    /// The name of the package.
+    pub name: String,
+    /// The executed tests, in order of declaration.
+    pub tests: Vec<TestCaseReport>,
+    /// The names of the tests that were skipped due to the `#[test(ignore)]` attribute.
+    pub ignored: Vec<String>,
+    /// The number of tests that were not executed as they did not match the test filter.
+    pub filtered_out: usize,
+}
+
+/// A report of a single executed test.
+#[derive(Clone, Debug, Serialize)]
+pub struct TestCaseReport {
+    /// The name of the test function.
+    pub name: String,
+    /// The file in which the test is declared.
+    pub file: PathBuf,
+    /// The line at which the test is declared.
+    pub line: usize,
+    /// Whether or not the test passed.
+    pub passed: bool,
+    /// Whether the test was required to revert.
+    pub should_revert: bool,
+    /// The revert code the test was required to revert with, if any.
+    pub expected_revert_code: Option<u64>,
+    /// The revert code with which the test reverted, if it reverted.
+    pub revert_code: Option<u64>,
+    /// Describes why the test failed. `None` if the test passed.
+    pub failure_reason: Option<String>,
+    /// The time taken for the test to execute, in nanoseconds.
+    pub duration_nanos: u64,
+    /// The amount of gas consumed by the test.
+    pub gas_used: u64,
+}
+
+impl TestReport {
+    /// Produce a report of all tests executed within the given package or workspace.
+    pub fn from_tested(tested: &Tested) -> Result<Self> {
+        let pkgs: Vec<&TestedPackage> = match tested {
+            Tested::Package(pkg) => vec![pkg],
+            Tested::Workspace(pkgs) => pkgs.iter().collect(),
+        };
+        let packages = pkgs
+            .into_iter()
+            .map(PackageReport::from_tested_pkg)
+            .collect::<Result<_>>()?;
+        Ok(Self { packages })
+    }
+
+    /// Serialize the report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Serialize the report as JUnit XML.
+    ///
+    /// Each package is represented by a `testsuite`, and each test by a `testcase`. Ignored tests
+    /// are reported as `skipped`.
+    pub fn to_junit_xml(&self) -> String {
+        let mut xml = String::new();
+        let tests: usize = self.packages.iter().map(PackageReport::test_count).sum();
+        let failures: usize = self.packages.iter().map(PackageReport::failure_count).sum();
+        let skipped: usize = self.packages.iter().map(|pkg| pkg.ignored.len()).sum();
+        let time: f64 = self.packages.iter().map(PackageReport::duration_secs).sum();
+        writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+        writeln!(
+            xml,
+            r#"<testsuites name="forc test" tests="{tests}" failures="{failures}" skipped="{skipped}" time="{time}">"#,
+        )
+        .unwrap();
+        for pkg in &self.packages {
+            pkg.write_junit_testsuite(&mut xml);
+        }
+        writeln!(xml, "</testsuites>").unwrap();
+        xml
+    }
+}
+
+impl PackageReport {
+    fn from_tested_pkg(pkg: &TestedPackage) -> Result<Self> {
+        let tests = pkg
+            .tests
+            .iter()
+            .map(TestCaseReport::from_test_result)
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            name: pkg.built.pkg_name.clone(),
+            tests,
+            ignored: pkg.ignored.clone(),
+            filtered_out: pkg.filtered_out,
+        })
+    }
+
+    /// The total number of tests, including those that were ignored.
+    fn test_count(&self) -> usize {
+        self.tests.len() + self.ignored.len()
+    }
+
+    fn failure_count(&self) -> usize {
+        self.tests.iter().filter(|test| !test.passed).count()
+    }
+
+    fn duration_secs(&self) -> f64 {
+        self.tests.iter().map(TestCaseReport::duration_secs).sum()
+    }
+
+    fn write_junit_testsuite(&self, xml: &mut String) {
+        writeln!(
+            xml,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" skipped="{}" time="{}">"#,
+            escape_xml(&self.name),
+            self.test_count(),
+            self.failure_count(),
+            self.ignored.len(),
+            self.duration_secs(),
+        )
+        .unwrap();
+        for test in &self.tests {
+            test.write_junit_testcase(&self.name, xml);
+        }
+        for ignored in &self.ignored {
+            writeln!(
+                xml,
+                r#"    <testcase name="{}" classname="{}">"#,
+                escape_xml(ignored),
+                escape_xml(&self.name),
+            )
+            .unwrap();
+            writeln!(xml, "      <skipped/>").unwrap();
+            writeln!(xml, "    </testcase>").unwrap();
+        }
+        writeln!(xml, "  </testsuite>").unwrap();
+    }
+}
+
+impl TestCaseReport {
+    fn from_test_result(test: &TestResult) -> Result<Self> {
+        let details = test.details()?;
+        let passed = test.passed();
+        let (should_revert, expected_revert_code) = match test.condition {
+            TestPassCondition::ShouldRevert(code) => (true, code),
+            TestPassCondition::ShouldNotRevert => (false, None),
+        };
+        Ok(Self {
+            name: test.name.clone(),
+            file: details.file_path.to_path_buf(),
+            line: details.line_number,
+            passed,
+            should_revert,
+            expected_revert_code,
+            revert_code: test.revert_code(),
+            failure_reason: (!passed).then(|| test.failure_reason()),
+            duration_nanos: u64::try_from(test.duration.as_nanos()).unwrap_or(u64::MAX),
+            gas_used: test.gas_used,
+        })
+    }
+
+    fn duration_secs(&self) -> f64 {
+        self.duration_nanos as f64 / 1e9
+    }
+
+    fn write_junit_testcase(&self, pkg_name: &str, xml: &mut String) {
+        writeln!(
+            xml,
+            r#"    <testcase name="{}" classname="{}" file="{}" line="{}" time="{}">"#,
+            escape_xml(&self.name),
+            escape_xml(pkg_name),
+            escape_xml(&self.file.display().to_string()),
+            self.line,
+            self.duration_secs(),
+        )
+        .unwrap();
+        writeln!(xml, "      <properties>").unwrap();
+        writeln!(
+            xml,
+            r#"        <property name="gas_used" value="{}"/>"#,
+            self.gas_used
+        )
+        .unwrap();
+        if let Some(revert_code) = self.revert_code {
+            writeln!(
+                xml,
+                r#"        <property name="revert_code" value="{revert_code}"/>"#
+            )
+            .unwrap();
+        }
+        writeln!(xml, "      </properties>").unwrap();
+        if let Some(reason) = &self.failure_reason {
+            writeln!(
+                xml,
+                r#"      <failure message="{}">{}:{}</failure>"#,
+                escape_xml(reason),
+                escape_xml(&self.file.display().to_string()),
+                self.line,
+            )
+            .unwrap();
+        }
+        writeln!(xml, "    </testcase>").unwrap();
+    }
+}
+
+/// Escape the given text for use within XML attribute values and text content.
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_case(name: &str, passed: bool) -> TestCaseReport {
+        TestCaseReport {
+            name: name.to_string(),
+            file: PathBuf::from("src/main.sw"),
+            line: 12,
+            passed,
+            should_revert: false,
+            expected_revert_code: None,
+            revert_code: (!passed).then_some(42),
+            failure_reason: (!passed)
+                .then(|| "expected not to revert, but reverted with code 42 (0x2a)".to_string()),
+            duration_nanos: 1_500_000,
+            gas_used: 1234,
+        }
+    }
+
+    #[test]
+    fn junit_xml() {
+        let report = TestReport {
+            packages: vec![PackageReport {
+                name: "my_pkg".to_string(),
+                tests: vec![test_case("test_ok", true), test_case("test_<bad>", false)],
+                ignored: vec!["test_ignored".to_string()],
+                filtered_out: 2,
+            }],
+        };
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="forc test" tests="3" failures="1" skipped="1" time="0.003">
+  <testsuite name="my_pkg" tests="3" failures="1" skipped="1" time="0.003">
+    <testcase name="test_ok" classname="my_pkg" file="src/main.sw" line="12" time="0.0015">
+      <properties>
+        <property name="gas_used" value="1234"/>
+      </properties>
+    </testcase>
+    <testcase name="test_&lt;bad&gt;" classname="my_pkg" file="src/main.sw" line="12" time="0.0015">
+      <properties>
+        <property name="gas_used" value="1234"/>
+        <property name="revert_code" value="42"/>
+      </properties>
+      <failure message="expected not to revert, but reverted with code 42 (0x2a)">src/main.sw:12</failure>
+    </testcase>
+    <testcase name="test_ignored" classname="my_pkg">
+      <skipped/>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#;
+        assert_eq!(report.to_junit_xml(), expected);
+    }
+
+    #[test]
+    fn json() {
+        let report = TestReport {
+            packages: vec![PackageReport {
+                name: "my_pkg".to_string(),
+                tests: vec![test_case("test_bad", false)],
+                ignored: vec![],
+                filtered_out: 0,
+            }],
+        };
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        let test = &json["packages"][0]["tests"][0];
+        assert_eq!(test["name"], "test_bad");
+        assert_eq!(test["line"], 12);
+        assert_eq!(test["passed"], false);
+        assert_eq!(test["revert_code"], 42);
+        assert_eq!(test["gas_used"], 1234);
+        assert_eq!(test["duration_nanos"], 1_500_000);
+    }
+}
@@ -0,0 +1,802 @@
+//! Encoding of typed arguments and decoding of typed return values and logs using a program's JSON
+//! ABI.
+//!
+//! Values are written with a Sway-like syntax:
+//!
+//! - Integers in decimal or hex, e.g. `42` or `0x2a`.
+//! - Booleans, e.g. `true`.
+//! - `b256` values as 32 bytes of hex, e.g. `0x0000...0001`.
+//! - Strings in quotes, e.g. `"fuel"`. The quotes may be omitted for a top-level argument.
+//! - Arrays, e.g. `[1, 2, 3]`.
+//! - Tuples, e.g. `(1, true)`.
+//! - Structs with named fields in any order, e.g. `Point { x: 1, y: 2 }`. The struct name is
+//!   optional.
+//! - Enum variants, e.g. `Option::None` or `Option::Some(42)`. The enum name is optional.
+//!
+//! Decoded values are formatted with the same syntax, such that the values returned and logged by
+//! `forc run` and `forc call` match the logs and counterexamples reported by `forc test`.
+
+use anyhow::{anyhow, bail, Context, Result};
+use fuels_core::{abi_decoder::ABIDecoder, abi_encoder::ABIEncoder, StringToken, Token};
+use fuels_types::{
+    enum_variants::EnumVariants,
+    param_types::{ParamType, ReturnLocation},
+    ProgramABI, TypeApplication, TypeDeclaration,
+};
+use std::collections::HashMap;
+use sway_core::fuel_prelude::fuel_tx::{self, ContractId, Receipt};
+
+/// The parameter and return types of a function declared within a program's JSON ABI.
+pub struct AbiFn {
+    pub name: String,
+    pub inputs: Vec<AbiParam>,
+    pub output: ParamType,
+}
+
+/// A single named parameter of an [AbiFn].
+pub struct AbiParam {
+    pub name: String,
+    /// The type as written in the JSON ABI, e.g. `struct Point`.
+    pub type_name: String,
+    pub param_type: ParamType,
+}
+
+impl AbiFn {
+    /// Find the function with the given name within the program's JSON ABI.
+    pub fn from_abi(program_abi: &ProgramABI, fn_name: &str) -> Result<Self> {
+        let function = program_abi
+            .functions
+            .iter()
+            .find(|function| function.name == fn_name)
+            .ok_or_else(|| anyhow!("no function named `{}` found in the JSON ABI", fn_name))?;
+        let type_lookup = type_lookup(program_abi);
+        let param_type = |application| resolve_param_type(application, &type_lookup);
+        let inputs = function
+            .inputs
+            .iter()
+            .map(|input| {
+                Ok(AbiParam {
+                    name: input.name.clone(),
+                    type_name: type_lookup
+                        .get(&input.type_id)
+                        .map(|decl| decl.type_field.clone())
+                        .unwrap_or_default(),
+                    param_type: param_type(input)?,
+                })
+            })
+            .collect::<Result<_>>()?;
+        let output = param_type(&function.output)?;
+        Ok(Self {
+            name: function.name.clone(),
+            inputs,
+            output,
+        })
+    }
+
+    /// Parse each of the given arguments according to the type of the respective parameter.
+    pub fn parse_args(&self, args: &[String]) -> Result<Vec<Token>> {
+        if args.len() != self.inputs.len() {
+            let params: Vec<String> = self
+                .inputs
+                .iter()
+                .map(|input| format!("{}: {}", input.name, input.type_name))
+                .collect();
+            bail!(
+                "`{}({})` expects {} argument(s), but {} were provided",
+                self.name,
+                params.join(", "),
+                self.inputs.len(),
+                args.len()
+            );
+        }
+        self.inputs
+            .iter()
+            .zip(args)
+            .map(|(input, arg)| {
+                parse_value(&input.param_type, arg).with_context(|| {
+                    format!(
+                        "invalid value `{}` for argument `{}` of type `{}`",
+                        arg, input.name, input.type_name
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Parse and ABI-encode the given arguments.
+    ///
+    /// Heap types such as `Vec` are not supported, as their encoding depends on the address at
+    /// which the data is loaded.
+    pub fn encode_args(&self, args: &[String]) -> Result<Vec<u8>> {
+        if let Some(input) = self
+            .inputs
+            .iter()
+            .find(|input| contains_heap_type(&input.param_type))
+        {
+            bail!(
+                "argument `{}` of type `{}` is not supported, as it contains a heap type such \
+                as `Vec`",
+                input.name,
+                input.type_name
+            );
+        }
+        let tokens = self.parse_args(args)?;
+        let bytes = ABIEncoder::encode(&tokens)
+            .map_err(|e| anyhow!("failed to encode arguments: {}", e))?
+            .resolve(0);
+        Ok(bytes)
+    }
+
+    /// Decode the value returned by this function within the context identified by `id`, i.e. the
+    /// contract ID of the called contract, or the zeroed ID for a script.
+    ///
+    /// Returns `None` in the case that no value was returned, e.g. due to a revert or a return type
+    /// of `()`.
+    pub fn decode_output(&self, receipts: &[Receipt], id: &ContractId) -> Result<Option<String>> {
+        if self.output == ParamType::Unit {
+            return Ok(None);
+        }
+        let bytes = receipts.iter().rev().find_map(|receipt| {
+            match (receipt, self.output.get_return_location()) {
+                (
+                    Receipt::Return {
+                        id: ret_id, val, ..
+                    },
+                    ReturnLocation::Return,
+                ) if ret_id == id => Some(val.to_be_bytes().to_vec()),
+                (
+                    Receipt::ReturnData {
+                        id: ret_id, data, ..
+                    },
+                    ReturnLocation::ReturnData,
+                ) if ret_id == id => Some(data.clone()),
+                _ => None,
+            }
+        });
+        let bytes = match bytes {
+            Some(bytes) => bytes,
+            None => return Ok(None),
+        };
+        let token = ABIDecoder::decode_single(&self.output, &bytes).map_err(|e| {
+            anyhow!(
+                "failed to decode the value returned by `{}`: {}",
+                self.name,
+                e
+            )
+        })?;
+        Ok(Some(format_value(&self.output, &token)))
+    }
+}
+
+/// Decode the values logged within the context identified by `id` using the types declared within
+/// the JSON ABI, returning the log ID and the value of each.
+pub fn decode_logs(
+    program_abi: &ProgramABI,
+    receipts: &[Receipt],
+    id: &ContractId,
+) -> Result<Vec<(u64, String)>> {
+    receipts
+        .iter()
+        .filter_map(|receipt| match receipt {
+            Receipt::Log {
+                id: log_id, ra, rb, ..
+            } if log_id == id => Some((*rb, ra.to_be_bytes().to_vec())),
+            Receipt::LogData {
+                id: log_id,
+                rb,
+                data,
+                ..
+            } if log_id == id => Some((*rb, data.clone())),
+            _ => None,
+        })
+        .map(|(log_id, data)| Ok((log_id, decode_log(program_abi, log_id, &data)?)))
+        .collect()
+}
+
+/// Decode the given logged data using the type associated with `log_id` within the JSON ABI.
+pub fn decode_log(program_abi: &ProgramABI, log_id: u64, data: &[u8]) -> Result<String> {
+    let logged_type = program_abi
+        .logged_types
+        .iter()
+        .flatten()
+        .find(|logged_type| logged_type.log_id == log_id)
+        .ok_or_else(|| anyhow!("missing logged type for log ID {} in JSON ABI", log_id))?;
+    let param_type = resolve_param_type(&logged_type.application, &type_lookup(program_abi))?;
+    let token = ABIDecoder::decode_single(&param_type, data)
+        .map_err(|e| anyhow!("failed to decode log with ID {} : {}", log_id, e))?;
+    Ok(format_value(&param_type, &token))
+}
+
+/// Map the ID of each type declared within the JSON ABI to its declaration.
+pub fn type_lookup(program_abi: &ProgramABI) -> HashMap<usize, TypeDeclaration> {
+    program_abi
+        .types
+        .iter()
+        .map(|decl| (decl.type_id, decl.clone()))
+        .collect()
+}
+
+/// Resolve the `ParamType` of the given type application, including the names of its types,
+/// fields and variants.
+pub fn resolve_param_type(
+    application: &TypeApplication,
+    type_lookup: &HashMap<usize, TypeDeclaration>,
+) -> Result<ParamType> {
+    let mut param_type = ParamType::try_from_type_application(application, type_lookup)
+        .map_err(|e| anyhow!("failed to resolve type in JSON ABI: {}", e))?;
+    apply_names(
+        &mut param_type,
+        application,
+        type_lookup,
+        &mut HashMap::new(),
+    )?;
+    Ok(param_type)
+}
+
+/// `ParamType` does not retain the names of types, fields and variants, so they are collected from
+/// the JSON ABI types in order to parse and format values by name.
+///
+/// `generics` maps the type IDs of generic parameters to the types with which they are
+/// instantiated.
+fn apply_names(
+    param_type: &mut ParamType,
+    application: &TypeApplication,
+    types: &HashMap<usize, TypeDeclaration>,
+    generics: &mut HashMap<usize, TypeApplication>,
+) -> Result<()> {
+    let application = match generics.get(&application.type_id) {
+        Some(arg) => arg.clone(),
+        None => application.clone(),
+    };
+    let decl = match types.get(&application.type_id) {
+        Some(decl) => decl,
+        None => return Ok(()),
+    };
+    let params = decl.type_parameters.iter().flatten();
+    let args = application.type_arguments.iter().flatten();
+    for (param, arg) in params.zip(args) {
+        generics.insert(*param, arg.clone());
+    }
+    let components: Vec<&TypeApplication> = decl.components.iter().flatten().collect();
+    let type_name = decl
+        .type_field
+        .trim_start_matches("struct ")
+        .trim_start_matches("enum ")
+        .to_string();
+    match param_type {
+        ParamType::Struct { name, fields, .. } => {
+            *name = type_name;
+            for ((field_name, field_type), component) in fields.iter_mut().zip(components) {
+                *field_name = component.name.clone();
+                apply_names(field_type, component, types, generics)?;
+            }
+        }
+        ParamType::Enum { name, variants, .. } => {
+            *name = type_name;
+            let mut named = variants.variants().clone();
+            for ((variant_name, variant_type), component) in named.iter_mut().zip(components) {
+                *variant_name = component.name.clone();
+                apply_names(variant_type, component, types, generics)?;
+            }
+            *variants = EnumVariants::new(named).map_err(|e| anyhow!("{}", e))?;
+        }
+        ParamType::Tuple(elems) => {
+            for (elem, component) in elems.iter_mut().zip(components) {
+                apply_names(elem, component, types, generics)?;
+            }
+        }
+        ParamType::Array(elem, _) => {
+            if let Some(component) = components.first() {
+                apply_names(elem, component, types, generics)?;
+            }
+        }
+        ParamType::Vector(elem) => {
+            if let Some(arg) = application.type_arguments.iter().flatten().next() {
+                apply_names(elem, arg, types, generics)?;
+            }
+        }
+        ParamType::Unit
+        | ParamType::U8
+        | ParamType::U16
+        | ParamType::U32
+        | ParamType::U64
+        | ParamType::Bool
+        | ParamType::Byte
+        | ParamType::B256
+        | ParamType::String(_) => (),
+    }
+    Ok(())
+}
+
+/// Whether or not the type is, or contains, a type whose data is stored on the heap.
+pub fn contains_heap_type(param_type: &ParamType) -> bool {
+    match param_type {
+        ParamType::Vector(_) => true,
+        ParamType::Array(elem, _) => contains_heap_type(elem),
+        ParamType::Struct { fields, .. } => fields.iter().any(|(_, ty)| contains_heap_type(ty)),
+        ParamType::Enum { variants, .. } => variants
+            .variants()
+            .iter()
+            .any(|(_, ty)| contains_heap_type(ty)),
+        ParamType::Tuple(elems) => elems.iter().any(contains_heap_type),
+        ParamType::Unit
+        | ParamType::U8
+        | ParamType::U16
+        | ParamType::U32
+        | ParamType::U64
+        | ParamType::Bool
+        | ParamType::Byte
+        | ParamType::B256
+        | ParamType::String(_) => false,
+    }
+}
+
+/// Parse a value of the given type from its Sway-like representation.
+pub fn parse_value(param_type: &ParamType, value: &str) -> Result<Token> {
+    // Allow omitting the quotes around a top-level string, as they are awkward to write in a shell.
+    if let ParamType::String(len) = param_type {
+        if !value.trim_start().starts_with('"') {
+            return string_token(value, *len);
+        }
+    }
+    let mut parser = ValueParser { src: value, pos: 0 };
+    let token = parser.value(param_type)?;
+    parser.skip_whitespace();
+    if !parser.rest().is_empty() {
+        bail!("unexpected trailing characters `{}`", parser.rest());
+    }
+    Ok(token)
+}
+
+fn string_token(value: &str, len: usize) -> Result<Token> {
+    if !value.is_ascii() {
+        bail!("strings may only contain ASCII characters");
+    }
+    if value.len() != len {
+        bail!(
+            "expected a string of length {}, found length {}",
+            len,
+            value.len()
+        );
+    }
+    Ok(Token::String(StringToken::new(value.to_string(), len)))
+}
+
+/// A simple recursive descent parser over the Sway-like representation of a value.
+struct ValueParser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> ValueParser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.rest().chars().next()
+    }
+
+    /// Consume the given character if it is next.
+    fn eat(&mut self, ch: char) -> bool {
+        if self.peek() == Some(ch) {
+            self.pos += ch.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, ch: char) -> Result<()> {
+        if !self.eat(ch) {
+            match self.peek() {
+                Some(found) => bail!("expected `{}`, found `{}`", ch, found),
+                None => bail!("expected `{}`, found the end of the value", ch),
+            }
+        }
+        Ok(())
+    }
+
+    /// Consume a word, i.e. a number, boolean or identifier.
+    fn word(&mut self) -> Result<&'a str> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            match rest.chars().next() {
+                Some(found) => bail!("expected a value, found `{}`", found),
+                None => bail!("expected a value, found the end of the value"),
+            }
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    /// Parse a sequence of comma-separated items up to and including the `close` delimiter,
+    /// allowing a trailing comma.
+    fn items(&mut self, close: char, mut item: impl FnMut(&mut Self) -> Result<()>) -> Result<()> {
+        while !self.eat(close) {
+            item(self)?;
+            if !self.eat(',') {
+                return self.expect(close);
+            }
+        }
+        Ok(())
+    }
+
+    fn value(&mut self, param_type: &ParamType) -> Result<Token> {
+        let token = match param_type {
+            ParamType::Unit => {
+                self.expect('(')?;
+                self.expect(')')?;
+                Token::Unit
+            }
+            ParamType::U8 => Token::U8(self.uint("u8")?),
+            ParamType::U16 => Token::U16(self.uint("u16")?),
+            ParamType::U32 => Token::U32(self.uint("u32")?),
+            ParamType::U64 => Token::U64(self.uint("u64")?),
+            ParamType::Byte => Token::Byte(self.uint("byte")?),
+            ParamType::Bool => match self.word()? {
+                "true" => Token::Bool(true),
+                "false" => Token::Bool(false),
+                word => bail!("expected `true` or `false`, found `{}`", word),
+            },
+            ParamType::B256 => {
+                let word = self.word()?;
+                let bytes = word
+                    .strip_prefix("0x")
+                    .and_then(|hex| hex::decode(hex).ok())
+                    .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                    .ok_or_else(|| {
+                        anyhow!("expected `0x` followed by 64 hex digits, found `{}`", word)
+                    })?;
+                Token::B256(bytes)
+            }
+            ParamType::String(len) => {
+                let string = self.string()?;
+                string_token(&string, *len)?
+            }
+            ParamType::Array(elem, len) => {
+                self.expect('[')?;
+                let mut elems = vec![];
+                self.items(']', |parser| {
+                    elems.push(parser.value(elem)?);
+                    Ok(())
+                })?;
+                if elems.len() != *len {
+                    bail!(
+                        "expected an array of {} elements, found {}",
+                        len,
+                        elems.len()
+                    );
+                }
+                Token::Array(elems)
+            }
+            ParamType::Vector(elem) => {
+                self.expect('[')?;
+                let mut elems = vec![];
+                self.items(']', |parser| {
+                    elems.push(parser.value(elem)?);
+                    Ok(())
+                })?;
+                Token::Vector(elems)
+            }
+            ParamType::Tuple(elem_types) => {
+                self.expect('(')?;
+                let mut elems = vec![];
+                self.items(')', |parser| {
+                    let elem_type = elem_types.get(elems.len()).ok_or_else(|| {
+                        anyhow!("expected a tuple of {} elements", elem_types.len())
+                    })?;
+                    elems.push(parser.value(elem_type)?);
+                    Ok(())
+                })?;
+                if elems.len() != elem_types.len() {
+                    bail!(
+                        "expected a tuple of {} elements, found {}",
+                        elem_types.len(),
+                        elems.len()
+                    );
+                }
+                Token::Tuple(elems)
+            }
+            ParamType::Struct { name, fields, .. } => {
+                if self.peek() != Some('{') {
+                    let found = self.word()?;
+                    if found != name {
+                        bail!("expected a `{}` struct, found `{}`", name, found);
+                    }
+                }
+                self.expect('{')?;
+                let mut values: HashMap<&str, Token> = HashMap::new();
+                self.items('}', |parser| {
+                    let name = parser.word()?;
+                    let (_, field_type) = fields
+                        .iter()
+                        .find(|(field_name, _)| field_name == name)
+                        .ok_or_else(|| anyhow!("unknown struct field `{}`", name))?;
+                    parser.expect(':')?;
+                    let value = parser
+                        .value(field_type)
+                        .with_context(|| format!("invalid value for field `{}`", name))?;
+                    if values.insert(name, value).is_some() {
+                        bail!("the field `{}` is specified more than once", name);
+                    }
+                    Ok(())
+                })?;
+                let tokens = fields
+                    .iter()
+                    .map(|(name, _)| {
+                        values
+                            .remove(name.as_str())
+                            .ok_or_else(|| anyhow!("missing value for struct field `{}`", name))
+                    })
+                    .collect::<Result<_>>()?;
+                Token::Struct(tokens)
+            }
+            ParamType::Enum {
+                name: enum_name,
+                variants,
+                ..
+            } => {
+                let mut name = self.word()?;
+                if self.rest().starts_with("::") {
+                    if name != enum_name {
+                        bail!("expected a `{}` variant, found `{}`", enum_name, name);
+                    }
+                    self.pos += "::".len();
+                    name = self.word()?;
+                }
+                let (discriminant, (_, variant_type)) = variants
+                    .variants()
+                    .iter()
+                    .enumerate()
+                    .find(|(_, (variant_name, _))| variant_name == name)
+                    .ok_or_else(|| anyhow!("unknown enum variant `{}`", name))?;
+                let token = match variant_type {
+                    ParamType::Unit if self.peek() != Some('(') => Token::Unit,
+                    _ => {
+                        self.expect('(')?;
+                        let token = self
+                            .value(variant_type)
+                            .with_context(|| format!("invalid value for variant `{}`", name))?;
+                        self.expect(')')?;
+                        token
+                    }
+                };
+                Token::Enum(Box::new((discriminant as u8, token, variants.clone())))
+            }
+        };
+        Ok(token)
+    }
+
+    /// Parse an unsigned integer in decimal or `0x`-prefixed hex.
+    fn uint<T: TryFrom<u64>>(&mut self, type_name: &str) -> Result<T> {
+        let word = self.word()?;
+        let digits = word.replace('_', "");
+        let value = match digits.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16),
+            None => digits.parse::<u64>(),
+        }
+        .map_err(|_| anyhow!("expected an integer, found `{}`", word))?;
+        T::try_from(value).map_err(|_| anyhow!("`{}` is out of range for `{}`", word, type_name))
+    }
+
+    /// Parse a quoted string, supporting the escape sequences produced when formatting strings.
+    fn string(&mut self) -> Result<String> {
+        self.expect('"')?;
+        let mut string = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((ix, ch)) = chars.next() {
+            match ch {
+                '"' => {
+                    self.pos += ix + 1;
+                    return Ok(string);
+                }
+                '\\' => match chars.next() {
+                    Some((_, escaped @ ('"' | '\\' | '\''))) => string.push(escaped),
+                    Some((_, 'n')) => string.push('\n'),
+                    Some((_, 'r')) => string.push('\r'),
+                    Some((_, 't')) => string.push('\t'),
+                    Some((_, '0')) => string.push('\0'),
+                    _ => bail!("invalid escape sequence in string"),
+                },
+                ch => string.push(ch),
+            }
+        }
+        bail!("unterminated string")
+    }
+}
+
+/// Format the given value using the same Sway-like syntax accepted by [parse_value].
+pub fn format_value(param_type: &ParamType, token: &Token) -> String {
+    let join = |items: Vec<String>| items.join(", ");
+    match (param_type, token) {
+        (_, Token::Unit) => "()".to_string(),
+        (_, Token::U8(n)) | (_, Token::Byte(n)) => n.to_string(),
+        (_, Token::U16(n)) => n.to_string(),
+        (_, Token::U32(n)) => n.to_string(),
+        (_, Token::U64(n)) => n.to_string(),
+        (_, Token::Bool(b)) => b.to_string(),
+        (_, Token::B256(bytes)) => format!("{:#x}", fuel_tx::Bytes32::from(*bytes)),
+        (_, Token::String(string)) => match string.get_encodable_str() {
+            Ok(s) => format!("{:?}", s),
+            Err(_) => token.to_string(),
+        },
+        (ParamType::Array(elem, _), Token::Array(elems))
+        | (ParamType::Vector(elem), Token::Vector(elems)) => format!(
+            "[{}]",
+            join(elems.iter().map(|e| format_value(elem, e)).collect())
+        ),
+        (ParamType::Tuple(elem_types), Token::Tuple(elems)) => format!(
+            "({})",
+            join(
+                elem_types
+                    .iter()
+                    .zip(elems)
+                    .map(|(ty, e)| format_value(ty, e))
+                    .collect()
+            )
+        ),
+        (ParamType::Struct { name, fields, .. }, Token::Struct(values)) if !fields.is_empty() => {
+            format!(
+                "{} {{ {} }}",
+                name,
+                join(
+                    fields
+                        .iter()
+                        .zip(values)
+                        .map(|((name, ty), value)| format!("{}: {}", name, format_value(ty, value)))
+                        .collect()
+                )
+            )
+        }
+        (ParamType::Struct { name, .. }, Token::Struct(_)) => format!("{} {{}}", name),
+        (ParamType::Enum { name, .. }, Token::Enum(selector)) => {
+            let (discriminant, token, variants) = &**selector;
+            match variants.variants().get(*discriminant as usize) {
+                Some((variant, ParamType::Unit)) => format!("{}::{}", name, variant),
+                Some((variant, ty)) => {
+                    format!("{}::{}({})", name, variant, format_value(ty, token))
+                }
+                None => token.to_string(),
+            }
+        }
+        (_, token) => token.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point() -> ParamType {
+        ParamType::Struct {
+            name: "Point".to_string(),
+            fields: vec![
+                ("x".to_string(), ParamType::U64),
+                ("y".to_string(), ParamType::Bool),
+            ],
+            generics: vec![],
+        }
+    }
+
+    fn option_u8() -> ParamType {
+        let variants = vec![
+            ("None".to_string(), ParamType::Unit),
+            ("Some".to_string(), ParamType::U8),
+        ];
+        ParamType::Enum {
+            name: "Option".to_string(),
+            variants: EnumVariants::new(variants).unwrap(),
+            generics: vec![],
+        }
+    }
+
+    #[test]
+    fn parse_and_format_roundtrip() {
+        let b256 = format!("0x{}", "01".repeat(32));
+        let cases = [
+            (ParamType::U64, "42", "42"),
+            (ParamType::U8, "0xff", "255"),
+            (ParamType::U32, "1_000", "1000"),
+            (ParamType::Bool, "true", "true"),
+            (ParamType::B256, b256.as_str(), b256.as_str()),
+            (ParamType::String(4), "\"fuel\"", "\"fuel\""),
+            (ParamType::String(4), "fuel", "\"fuel\""),
+            (ParamType::String(3), r#""a\"b""#, r#""a\"b""#),
+            (
+                ParamType::Array(Box::new(ParamType::U16), 3),
+                "[1,2, 3,]",
+                "[1, 2, 3]",
+            ),
+            (
+                ParamType::Tuple(vec![ParamType::U8, ParamType::Bool]),
+                "( 1 , false )",
+                "(1, false)",
+            ),
+            (point(), "{y: true, x: 7}", "Point { x: 7, y: true }"),
+            (
+                point(),
+                "Point { x: 7, y: true }",
+                "Point { x: 7, y: true }",
+            ),
+            (option_u8(), "None", "Option::None"),
+            (option_u8(), "Option::Some(3)", "Option::Some(3)"),
+        ];
+        for (param_type, input, expected) in cases {
+            let token = parse_value(&param_type, input).unwrap();
+            assert_eq!(format_value(&param_type, &token), expected);
+            let bytes = ABIEncoder::encode(std::slice::from_ref(&token))
+                .unwrap()
+                .resolve(0);
+            let decoded = ABIDecoder::decode_single(&param_type, &bytes).unwrap();
+            assert_eq!(decoded, token);
+        }
+    }
+
+    #[test]
+    fn decode_output_from_receipts() {
+        let abi_fn = |output| AbiFn {
+            name: "main".to_string(),
+            inputs: vec![],
+            output,
+        };
+        let script = ContractId::zeroed();
+        let contract = ContractId::from([1u8; 32]);
+        let point_value = parse_value(&point(), "{ x: 3, y: false }").unwrap();
+        let point_bytes = ABIEncoder::encode(&[point_value]).unwrap().resolve(0);
+        let receipts = [
+            Receipt::ret(contract, 7, 0, 0),
+            Receipt::return_data(contract, 0, Default::default(), vec![0; 16], 0, 0),
+            Receipt::ret(script, 42, 0, 0),
+            Receipt::return_data(script, 0, Default::default(), point_bytes, 0, 0),
+        ];
+        let decoded = abi_fn(ParamType::U64).decode_output(&receipts, &script);
+        assert_eq!(decoded.unwrap().as_deref(), Some("42"));
+        let decoded = abi_fn(ParamType::U64).decode_output(&receipts, &contract);
+        assert_eq!(decoded.unwrap().as_deref(), Some("7"));
+        let decoded = abi_fn(point()).decode_output(&receipts, &script);
+        assert_eq!(
+            decoded.unwrap().as_deref(),
+            Some("Point { x: 3, y: false }")
+        );
+        let decoded = abi_fn(ParamType::U64).decode_output(&receipts[..2], &script);
+        assert_eq!(decoded.unwrap(), None);
+    }
+
+    #[test]
+    fn parse_invalid_values() {
+        let cases = [
+            (ParamType::U8, "256"),
+            (ParamType::U64, "-1"),
+            (ParamType::Bool, "yes"),
+            (ParamType::B256, "0x01"),
+            (ParamType::String(4), "\"fuels\""),
+            (point(), "Line { x: 1, y: true }"),
+            (ParamType::Array(Box::new(ParamType::U8), 2), "[1]"),
+            (point(), "{x: 1}"),
+            (point(), "{x: 1, y: true, z: 2}"),
+            (point(), "{x: 1, x: 2, y: true}"),
+            (option_u8(), "Some"),
+            (option_u8(), "Other(1)"),
+            (option_u8(), "Result::Some(1)"),
+            (ParamType::U64, "1 2"),
+        ];
+        for (param_type, input) in cases {
+            assert!(
+                parse_value(&param_type, input).is_err(),
+                "expected `{}` to be invalid",
+                input
+            );
+        }
+    }
+}
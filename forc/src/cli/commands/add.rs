@@ -0,0 +1,56 @@
+use crate::ops::forc_add;
+use anyhow::Result;
+use clap::Parser;
+
+/// Add a dependency to the package's `Forc.toml`.
+///
+/// The dependency is fetched and pinned in order to validate it before the manifest is updated,
+/// and the `Forc.lock` file is updated accordingly. The existing formatting and comments of the
+/// manifest are preserved.
+///
+/// Dependencies specified with neither `--path` nor `--git` are sourced from the package
+/// registry. In the case that no `--version` is specified, the latest version published to the
+/// registry is used.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The name of the dependency.
+    pub name: String,
+    /// Path to the project, if not specified, current working directory will be used.
+    #[clap(long, value_name = "PATH")]
+    pub manifest_path: Option<String>,
+    /// Path to the dependency's package directory, relative to the current working directory.
+    #[clap(long, conflicts_with = "git")]
+    pub path: Option<String>,
+    /// URL of the git repository containing the dependency.
+    #[clap(long)]
+    pub git: Option<String>,
+    /// Git branch from which the dependency is fetched.
+    #[clap(long, requires = "git", conflicts_with_all = &["tag", "rev"])]
+    pub branch: Option<String>,
+    /// Git tag from which the dependency is fetched.
+    #[clap(long, requires = "git", conflicts_with = "rev")]
+    pub tag: Option<String>,
+    /// Git commit from which the dependency is fetched.
+    #[clap(long, requires = "git")]
+    pub rev: Option<String>,
+    /// The name of the package, in the case that it differs from the name of the dependency.
+    #[clap(long)]
+    pub package: Option<String>,
+    /// The version requirement of the dependency, e.g. `0.1`.
+    #[clap(long)]
+    pub version: Option<String>,
+    /// Add the dependency to the `[contract-dependencies]` table.
+    #[clap(long)]
+    pub contract: bool,
+    /// The salt with which the contract dependency is deployed, as 32 bytes of hex.
+    #[clap(long, requires = "contract")]
+    pub salt: Option<String>,
+    /// Offline mode, prevents Forc from using the network when managing dependencies.
+    /// Meaning it will only try to use previously downloaded dependencies.
+    #[clap(long = "offline")]
+    pub offline_mode: bool,
+}
+
+pub(crate) fn exec(command: Command) -> Result<()> {
+    forc_add::add(command)
+}
@@ -1,3 +1,4 @@
+pub mod add;
 pub mod addr2line;
 pub mod build;
 pub mod check;
@@ -5,8 +6,12 @@ pub mod clean;
 pub mod completions;
 pub mod init;
 pub mod new;
+pub mod package;
 pub mod parse_bytecode;
 pub mod plugins;
+pub mod publish;
+pub mod remove;
 pub mod template;
 pub mod test;
+pub mod tree;
 pub mod update;
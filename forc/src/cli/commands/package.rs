@@ -0,0 +1,30 @@
+use crate::ops::forc_package;
+use anyhow::Result;
+use clap::Parser;
+
+/// Assemble the package into a versioned archive that may be published to a package registry.
+///
+/// The archive contains the package's manifest, its Sway source files and its license files. By
+/// default, the archive is written to `<project-root>/out/package/<name>-<version>.tar.gz`.
+#[derive(Debug, Default, Parser)]
+pub struct Command {
+    /// Path to the project, if not specified, current working directory will be used.
+    #[clap(short, long)]
+    pub path: Option<String>,
+    /// The directory in which the archive is placed.
+    ///
+    /// By default, this is `<project-root>/out/package`.
+    #[clap(long)]
+    pub output_directory: Option<String>,
+    /// Skip verifying that the package builds from the contents of the archive alone.
+    #[clap(long)]
+    pub no_verify: bool,
+    /// Offline mode, prevents Forc from using the network when managing dependencies.
+    /// Meaning it will only try to use previously downloaded dependencies.
+    #[clap(long = "offline")]
+    pub offline_mode: bool,
+}
+
+pub(crate) fn exec(command: Command) -> Result<()> {
+    forc_package::package(command)
+}
@@ -0,0 +1,31 @@
+use crate::ops::forc_publish;
+use anyhow::Result;
+use clap::Parser;
+
+/// Package the project and publish the resulting archive to a package registry.
+///
+/// The registry is a directory containing an `index.json` file along with the published package
+/// archives. Once published, the package may be depended upon by version.
+#[derive(Debug, Default, Parser)]
+pub struct Command {
+    /// Path to the project, if not specified, current working directory will be used.
+    #[clap(short, long)]
+    pub path: Option<String>,
+    /// Path to the registry to publish to.
+    ///
+    /// By default, this is the registry specified by the `FORC_REGISTRY` environment variable,
+    /// or `~/.forc/registry/local` if unset.
+    #[clap(long)]
+    pub registry: Option<String>,
+    /// Skip verifying that the package builds from the contents of the archive alone.
+    #[clap(long)]
+    pub no_verify: bool,
+    /// Offline mode, prevents Forc from using the network when managing dependencies.
+    /// Meaning it will only try to use previously downloaded dependencies.
+    #[clap(long = "offline")]
+    pub offline_mode: bool,
+}
+
+pub(crate) fn exec(command: Command) -> Result<()> {
+    forc_publish::publish(command)
+}
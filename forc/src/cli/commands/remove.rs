@@ -0,0 +1,26 @@
+use crate::ops::forc_remove;
+use anyhow::Result;
+use clap::Parser;
+
+/// Remove dependencies from the package's `Forc.toml`.
+///
+/// Dependencies are removed from either the `[dependencies]` or `[contract-dependencies]` table,
+/// and the `Forc.lock` file is updated accordingly. The existing formatting and comments of the
+/// manifest are preserved.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The names of the dependencies to remove.
+    #[clap(required = true)]
+    pub names: Vec<String>,
+    /// Path to the project, if not specified, current working directory will be used.
+    #[clap(long, value_name = "PATH")]
+    pub manifest_path: Option<String>,
+    /// Offline mode, prevents Forc from using the network when managing dependencies.
+    /// Meaning it will only try to use previously downloaded dependencies.
+    #[clap(long = "offline")]
+    pub offline_mode: bool,
+}
+
+pub(crate) fn exec(command: Command) -> Result<()> {
+    forc_remove::remove(command)
+}
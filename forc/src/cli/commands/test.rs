@@ -1,9 +1,12 @@
 use crate::cli;
 use ansi_term::Colour;
-use anyhow::{bail, Result};
-use clap::Parser;
+use anyhow::{anyhow, bail, Result};
+use clap::{ArgEnum, Parser};
 use forc_pkg as pkg;
 use forc_test::TestedPackage;
+use forc_util::find_manifest_dir;
+use std::path::{Path, PathBuf};
+use sway_utils::MANIFEST_FILE_NAME;
 use tracing::info;
 
 /// Run the Sway unit tests for the current project.
@@ -17,8 +20,9 @@ use tracing::info;
 /// a unique entry point for a single program and has access to the namespace of the module in
 /// which it is declared.
 ///
-/// Unit tests decorated with the `#[test(script)]` attribute that are declared within `contract`
-/// projects may also call directly into their associated contract's ABI.
+/// Unit tests declared within `contract` projects may call into their associated contract's ABI
+/// via the `CONTRACT_ID` constant. The contract and all of its contract dependencies are deployed
+/// prior to running the tests.
 ///
 /// Upon successful compilation, test scripts are executed to their completion. A test is
 /// considered a failure in the case that a revert (`rvrt`) instruction is encountered during
@@ -29,37 +33,301 @@ pub struct Command {
     pub build: cli::shared::Build,
     /// When specified, only tests containing the given string will be executed.
     pub filter: Option<String>,
+    /// When specified, only the test exactly matching the given string will be executed.
+    #[clap(long, requires = "filter", conflicts_with = "regex")]
+    pub exact: bool,
+    /// When specified, the filter is treated as a regular expression and only tests whose names
+    /// match it will be executed.
+    #[clap(long, requires = "filter")]
+    pub regex: bool,
+    /// Only run the tests declared with `#[test(ignore)]`.
+    #[clap(long, conflicts_with = "include-ignored")]
+    pub ignored: bool,
+    /// Run the tests declared with `#[test(ignore)]` along with all other tests.
+    #[clap(long)]
+    pub include_ignored: bool,
+    /// Number of threads to utilize when running the tests. By default, this is the number of
+    /// threads available in your system.
+    #[clap(long)]
+    pub test_threads: Option<usize>,
+    #[clap(flatten)]
+    pub test_print: TestPrintOpts,
+    #[clap(flatten)]
+    pub gas_snapshot: GasSnapshotOpts,
+    #[clap(flatten)]
+    pub coverage: CoverageOpts,
+    #[clap(flatten)]
+    pub fuzz: FuzzOpts,
 }
 
-pub(crate) fn exec(cmd: Command) -> Result<()> {
-    if let Some(ref _filter) = cmd.filter {
-        bail!("unit test filter not yet supported");
+/// The set of options controlling how test results are printed.
+#[derive(Debug, Clone, Parser)]
+pub struct TestPrintOpts {
+    /// Print the values logged by all tests, decoded using the package's JSON ABI. By default,
+    /// logs are only printed for failing tests.
+    #[clap(long = "logs")]
+    pub print_logs: bool,
+    /// The format in which test results are reported in addition to the human-readable results.
+    /// The `json` and `junit` reports are written to stdout, with all other output written to
+    /// stderr, unless a `--report-path` is given.
+    #[clap(long, arg_enum, default_value = "human")]
+    pub format: TestReportFormat,
+    /// The path of the file to which the `json` or `junit` report is written. A path of `-` writes
+    /// the report to stdout.
+    #[clap(long)]
+    pub report_path: Option<PathBuf>,
+}
+
+impl TestPrintOpts {
+    /// Whether a machine-readable report is written to stdout, in which case all other output
+    /// must be written to stderr such that the report may be parsed.
+    pub fn reports_to_stdout(&self) -> bool {
+        self.format != TestReportFormat::Human && self.report_file().is_none()
     }
 
+    /// The file to which the report is written, if any.
+    fn report_file(&self) -> Option<&Path> {
+        self.report_path
+            .as_deref()
+            .filter(|path| *path != Path::new(REPORT_PATH_STDOUT))
+    }
+}
+
+/// The formats in which test results may be reported.
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TestReportFormat {
+    /// Human-readable results.
+    Human,
+    /// A JSON report.
+    Json,
+    /// A JUnit XML report.
+    Junit,
+}
+
+/// The set of options for recording and checking the gas used by each test.
+#[derive(Debug, Clone, Parser)]
+pub struct GasSnapshotOpts {
+    /// Write the gas used by each test to a `.gas-snapshot` file within the project directory,
+    /// replacing any existing snapshot. If a test filter is provided, only the entries of the
+    /// executed tests are updated and all other entries are retained.
+    #[clap(long, conflicts_with = "check-gas-snapshot")]
+    pub gas_snapshot: bool,
+    /// Compare the gas used by each test against the project's `.gas-snapshot` file and fail if
+    /// the gas used by any test has increased beyond the `--gas-threshold`.
+    #[clap(long)]
+    pub check_gas_snapshot: bool,
+    /// The permitted increase in gas used by each test when checking the gas snapshot, as a
+    /// percentage of the gas used recorded within the snapshot. Defaults to 0.
+    #[clap(long, requires = "check-gas-snapshot")]
+    pub gas_threshold: Option<f64>,
+}
+
+/// The set of options for collecting source line coverage.
+#[derive(Debug, Clone, Parser)]
+pub struct CoverageOpts {
+    /// Collect the source lines executed by the tests and write them as an LCOV tracefile to
+    /// `lcov.info` within the project directory. Only lines within the tested packages are
+    /// reported. Requires forc to be built with the `coverage` feature.
+    #[clap(long)]
+    pub coverage: bool,
+    /// The path to which the LCOV tracefile is written, in place of `lcov.info` within the project
+    /// directory.
+    #[clap(long, requires = "coverage")]
+    pub coverage_output: Option<PathBuf>,
+}
+
+/// The set of options for tests that declare parameters.
+#[derive(Debug, Clone, Parser)]
+pub struct FuzzOpts {
+    /// The number of times each test that declares parameters is executed with generated inputs.
+    #[clap(long, default_value_t = forc_test::DEFAULT_FUZZ_RUNS)]
+    pub fuzz_runs: usize,
+    /// The seed from which the inputs of tests that declare parameters are generated. Defaults to
+    /// 0, such that the same inputs are generated on every run.
+    #[clap(long, default_value_t = 0)]
+    pub fuzz_seed: u64,
+}
+
+/// The name of the file to which coverage is written unless otherwise specified.
+const COVERAGE_FILE_NAME: &str = "lcov.info";
+/// The `--report-path` that writes the report to stdout.
+const REPORT_PATH_STDOUT: &str = "-";
+
+pub(crate) fn exec(cmd: Command) -> Result<()> {
+    let test_runner_count = match cmd.test_threads {
+        Some(0) => bail!("`--test-threads` must be greater than zero"),
+        Some(runner_count) => forc_test::TestRunnerCount::Manual(runner_count),
+        None => forc_test::TestRunnerCount::Auto,
+    };
+    let test_filter = test_filter_from_cmd(&cmd)?;
+    let test_print_opts = cmd.test_print.clone();
+    let gas_snapshot_opts = cmd.gas_snapshot.clone();
+    let coverage_opts = cmd.coverage.clone();
+    if cmd.fuzz.fuzz_runs == 0 {
+        bail!("`--fuzz-runs` must be greater than zero");
+    }
+    let fuzz_config = forc_test::FuzzConfig {
+        runs: cmd.fuzz.fuzz_runs,
+        seed: cmd.fuzz.fuzz_seed,
+    };
+    if coverage_opts.coverage && !cfg!(feature = "coverage") {
+        bail!("`--coverage` requires forc to be built with the `coverage` feature");
+    }
+    if test_print_opts.format == TestReportFormat::Human && test_print_opts.report_path.is_some() {
+        bail!("`--report-path` requires a `--format` of `json` or `junit`");
+    }
+    let gas_threshold = gas_snapshot_opts.gas_threshold.unwrap_or(0.0);
+    if !gas_threshold.is_finite() || gas_threshold < 0.0 {
+        bail!("`--gas-threshold` must be a non-negative percentage");
+    }
+    let project_path = cmd.build.path.clone();
     let opts = opts_from_cmd(cmd);
     let built_tests = forc_test::build(opts)?;
     let start = std::time::Instant::now();
-    info!("   Running {} tests", built_tests.test_count());
-    let tested = built_tests.run()?;
+    info!("   Running {} tests", built_tests.test_count(&test_filter)?);
+    let tested = built_tests.run(
+        test_runner_count,
+        &test_filter,
+        coverage_opts.coverage,
+        fuzz_config,
+    )?;
     let duration = start.elapsed();
 
+    print_tested(&tested, &test_print_opts, duration)?;
+
+    let report = match test_print_opts.format {
+        TestReportFormat::Human => None,
+        TestReportFormat::Json => {
+            let mut json = forc_test::TestReport::from_tested(&tested)?.to_json()?;
+            json.push('\n');
+            Some(json)
+        }
+        TestReportFormat::Junit => {
+            Some(forc_test::TestReport::from_tested(&tested)?.to_junit_xml())
+        }
+    };
+    match (report, test_print_opts.report_file()) {
+        (None, _) => {}
+        (Some(report), None) => print!("{report}"),
+        (Some(report), Some(report_path)) => {
+            std::fs::write(report_path, report).map_err(|e| {
+                anyhow!(
+                    "failed to write test report to {}: {}",
+                    report_path.display(),
+                    e
+                )
+            })?;
+            info!("\n   Wrote test report to {}", report_path.display());
+        }
+    }
+
+    if gas_snapshot_opts.gas_snapshot || gas_snapshot_opts.check_gas_snapshot {
+        let snapshot_path =
+            manifest_dir(project_path.as_deref())?.join(forc_test::GAS_SNAPSHOT_FILE_NAME);
+        let snapshot = forc_test::GasSnapshot::from_tested(&tested);
+        if gas_snapshot_opts.gas_snapshot {
+            // Retain the entries of the tests that were not executed.
+            let snapshot = match test_filter.is_partial() && snapshot_path.exists() {
+                true => {
+                    let mut merged = forc_test::GasSnapshot::from_file(&snapshot_path)?;
+                    merged.merge(snapshot);
+                    merged
+                }
+                false => snapshot,
+            };
+            snapshot.write_to_file(&snapshot_path)?;
+            info!("\n   Wrote gas snapshot to {}", snapshot_path.display());
+        } else {
+            check_gas_snapshot(&snapshot_path, &snapshot, gas_threshold)?;
+        }
+    }
+
+    if coverage_opts.coverage {
+        let coverage_path = match coverage_opts.coverage_output {
+            Some(path) => path,
+            None => manifest_dir(project_path.as_deref())?.join(COVERAGE_FILE_NAME),
+        };
+        let lcov = forc_test::Coverage::from_tested(&tested)?.to_lcov();
+        std::fs::write(&coverage_path, lcov).map_err(|e| {
+            anyhow!(
+                "failed to write coverage to {}: {e}",
+                coverage_path.display()
+            )
+        })?;
+        info!("\n   Wrote coverage to {}", coverage_path.display());
+    }
+
+    Ok(())
+}
+
+/// The directory containing the manifest of the project at the given path.
+fn manifest_dir(project_path: Option<&str>) -> Result<PathBuf> {
+    let this_dir = match project_path {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir()?,
+    };
+    let manifest_dir = find_manifest_dir(&this_dir).ok_or_else(|| {
+        anyhow!(
+            "could not find `{}` in `{}` or any parent directory",
+            MANIFEST_FILE_NAME,
+            this_dir.display(),
+        )
+    })?;
+    Ok(manifest_dir)
+}
+
+/// Compare the gas used by the executed tests against the snapshot at the given path, failing if
+/// the gas used by any test has increased beyond the given threshold percentage.
+fn check_gas_snapshot(
+    snapshot_path: &Path,
+    current: &forc_test::GasSnapshot,
+    gas_threshold: f64,
+) -> Result<()> {
+    let previous = forc_test::GasSnapshot::from_file(snapshot_path)?;
+    let regressions = previous.regressions(current, gas_threshold);
+    if regressions.is_empty() {
+        info!("\n   Gas snapshot check: {}", Colour::Green.paint("OK"));
+        return Ok(());
+    }
+    info!("\n   gas regressions:");
+    for regression in &regressions {
+        info!(
+            "      - test {}: {} -> {} gas (+{:.2}%)",
+            regression.test_path,
+            regression.previous,
+            regression.current,
+            regression.percent_increase()
+        );
+    }
+    bail!(
+        "gas used by {} test(s) increased by more than {}% compared to {}",
+        regressions.len(),
+        gas_threshold,
+        snapshot_path.display()
+    )
+}
+
+fn print_tested(
+    tested: &forc_test::Tested,
+    test_print_opts: &TestPrintOpts,
+    duration: std::time::Duration,
+) -> Result<()> {
     // Eventually we'll print this in a fancy manner, but this will do for testing.
     match tested {
         forc_test::Tested::Workspace(pkgs) => {
             for pkg in pkgs {
                 let built = &pkg.built.pkg_name;
                 info!("\n   tested -- {built}\n");
-                print_tested_pkg(&pkg)?;
+                print_tested_pkg(pkg, test_print_opts)?;
             }
             info!("\n   Finished in {:?}", duration);
         }
-        forc_test::Tested::Package(pkg) => print_tested_pkg(&pkg)?,
+        forc_test::Tested::Package(pkg) => print_tested_pkg(pkg, test_print_opts)?,
     };
-
     Ok(())
 }
 
-fn print_tested_pkg(pkg: &TestedPackage) -> Result<()> {
+fn print_tested_pkg(pkg: &TestedPackage, test_print_opts: &TestPrintOpts) -> Result<()> {
     let succeeded = pkg.tests.iter().filter(|t| t.passed()).count();
     let failed = pkg.tests.len() - succeeded;
     let mut failed_test_details = Vec::new();
@@ -69,32 +337,56 @@ fn print_tested_pkg(pkg: &TestedPackage) -> Result<()> {
             true => ("ok", Colour::Green),
             false => ("FAILED", Colour::Red),
         };
-        info!(
-            "      test {} ... {} ({:?})",
-            test.name,
-            color.paint(state),
-            test.duration
-        );
+        match &test.fuzz {
+            Some(fuzz) => info!(
+                "      test {} ... {} (runs: {}, {:?}, {} gas)",
+                test.name,
+                color.paint(state),
+                fuzz.runs,
+                test.duration,
+                test.gas_used
+            ),
+            None => info!(
+                "      test {} ... {} ({:?}, {} gas)",
+                test.name,
+                color.paint(state),
+                test.duration,
+                test.gas_used
+            ),
+        }
+
+        // Print the logs of failing tests, or of all tests if requested.
+        if !test_passed || test_print_opts.print_logs {
+            print_decoded_logs(pkg, test);
+        }
 
         // If the test is failing, save details.
         if !test_passed {
             let details = test.details()?;
-            failed_test_details.push((test.name.clone(), details));
+            failed_test_details.push((test, details));
         }
     }
+    for ignored_test_name in &pkg.ignored {
+        info!(
+            "      test {} ... {}",
+            ignored_test_name,
+            Colour::Yellow.paint("ignored")
+        );
+    }
     let (state, color) = match succeeded == pkg.tests.len() {
         true => ("OK", Colour::Green),
         false => ("FAILED", Colour::Red),
     };
     if failed != 0 {
         info!("\n   failures:");
-        for (failed_test_name, failed_test_detail) in failed_test_details {
+        for (failed_test, failed_test_detail) in failed_test_details {
             let path = &*failed_test_detail.file_path;
             let line_number = failed_test_detail.line_number;
             info!(
                 "      - test {}, {:?}:{} ",
-                failed_test_name, path, line_number
+                failed_test.name, path, line_number
             );
+            info!("        {}", failed_test.failure_reason());
         }
         info!("\n");
     }
@@ -105,16 +397,56 @@ fn print_tested_pkg(pkg: &TestedPackage) -> Result<()> {
         .map(|test_result| test_result.duration)
         .sum();
     info!(
-        "   Result: {}. {} passed. {} failed. Finished in {:?}.",
+        "   Result: {}. {} passed. {} failed. {} ignored. {} filtered out. Finished in {:?}.",
         color.paint(state),
         succeeded,
         failed,
+        pkg.ignored.len(),
+        pkg.filtered_out,
         pkg_test_durations
     );
 
     Ok(())
 }
 
+fn print_decoded_logs(pkg: &TestedPackage, test: &forc_test::TestResult) {
+    match pkg.decoded_logs(test) {
+        Ok(logs) => {
+            for log in logs {
+                info!("          log {}: {}", log.log_id, log.value);
+            }
+        }
+        Err(err) => {
+            info!("          failed to decode logs: {err}");
+            for receipt in test.log_receipts() {
+                info!("          {receipt:?}");
+            }
+        }
+    }
+}
+
+fn test_filter_from_cmd(cmd: &Command) -> Result<forc_test::TestFilter> {
+    let name_filter = match &cmd.filter {
+        None => None,
+        Some(phrase) if cmd.exact => Some(forc_test::TestNameFilter::Exact(phrase.clone())),
+        Some(phrase) if cmd.regex => {
+            let regex = regex::Regex::new(phrase)
+                .map_err(|e| anyhow!("invalid test filter regex `{}`: {}", phrase, e))?;
+            Some(forc_test::TestNameFilter::Regex(regex))
+        }
+        Some(phrase) => Some(forc_test::TestNameFilter::Substring(phrase.clone())),
+    };
+    let ignored = match (cmd.ignored, cmd.include_ignored) {
+        (true, _) => forc_test::IgnoredTests::Only,
+        (false, true) => forc_test::IgnoredTests::Include,
+        (false, false) => forc_test::IgnoredTests::Skip,
+    };
+    Ok(forc_test::TestFilter {
+        name_filter,
+        ignored,
+    })
+}
+
 fn opts_from_cmd(cmd: Command) -> forc_test::Opts {
     forc_test::Opts {
         pkg: pkg::PkgOpts {
@@ -135,9 +467,15 @@ fn opts_from_cmd(cmd: Command) -> forc_test::Opts {
             json_abi: cmd.build.minify_json_abi,
             json_storage_slots: cmd.build.minify_json_storage_slots,
         },
+        features: pkg::FeatureOpts {
+            features: cmd.build.features,
+            all_features: cmd.build.all_features,
+            no_default_features: cmd.build.no_default_features,
+        },
         build_profile: cmd.build.build_profile,
         release: cmd.build.release,
         time_phases: cmd.build.time_phases,
+        no_cache: cmd.build.no_cache,
         binary_outfile: cmd.build.binary_outfile,
         debug_outfile: cmd.build.debug_outfile,
     }
@@ -0,0 +1,47 @@
+use crate::ops::forc_tree;
+use anyhow::Result;
+use clap::{ArgEnum, Parser};
+
+/// Display the dependency graph of the project as a tree.
+///
+/// Each package is listed along with its version and source. Contract dependencies are listed
+/// along with the salt with which they are deployed. Packages that have already been displayed
+/// are marked with `(*)` and their dependencies are omitted.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Path to the project, if not specified, current working directory will be used.
+    #[clap(short, long)]
+    pub path: Option<String>,
+    /// Display the tree of packages that depend on the given package, rather than the packages
+    /// that the project depends on.
+    #[clap(short, long, value_name = "PACKAGE")]
+    pub invert: Option<String>,
+    /// Display only those packages that are included in the graph from more than one source, along
+    /// with the packages that depend on them. Implies `--invert`.
+    #[clap(short, long, conflicts_with = "invert")]
+    pub duplicates: bool,
+    /// The format in which the dependency graph is displayed.
+    #[clap(long, arg_enum, default_value = "text")]
+    pub format: TreeFormat,
+    /// Offline mode, prevents Forc from using the network when managing dependencies.
+    /// Meaning it will only try to use previously downloaded dependencies.
+    #[clap(long = "offline")]
+    pub offline_mode: bool,
+    /// Requires that the Forc.lock file is up-to-date. If the lock file is missing, or it
+    /// needs to be updated, Forc will exit with an error
+    #[clap(long)]
+    pub locked: bool,
+}
+
+/// The formats in which the dependency graph may be displayed.
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TreeFormat {
+    /// An indented tree.
+    Text,
+    /// A graph in the Graphviz DOT language, e.g. for rendering with `dot -Tsvg`.
+    Dot,
+}
+
+pub(crate) fn exec(command: Command) -> Result<()> {
+    forc_tree::tree(command)
+}
@@ -1,9 +1,10 @@
 use std::str::FromStr;
 
 use self::commands::{
-    addr2line, build, check, clean, completions, init, new, parse_bytecode, plugins, template,
-    test, update,
+    add, addr2line, build, check, clean, completions, init, new, package, parse_bytecode, plugins,
+    publish, remove, template, test, tree, update,
 };
+pub use add::Command as AddCommand;
 use addr2line::Command as Addr2LineCommand;
 use anyhow::{anyhow, Result};
 pub use build::Command as BuildCommand;
@@ -11,14 +12,18 @@ pub use check::Command as CheckCommand;
 use clap::{Parser, Subcommand};
 pub use clean::Command as CleanCommand;
 pub use completions::Command as CompletionsCommand;
-use forc_tracing::{init_tracing_subscriber, TracingSubscriberOptions};
+use forc_tracing::{init_tracing_subscriber, TracingSubscriberOptions, TracingWriterMode};
 pub use init::Command as InitCommand;
 pub use new::Command as NewCommand;
+pub use package::Command as PackageCommand;
 use parse_bytecode::Command as ParseBytecodeCommand;
 pub use plugins::Command as PluginsCommand;
+pub use publish::Command as PublishCommand;
+pub use remove::Command as RemoveCommand;
 pub use template::Command as TemplateCommand;
 pub use test::Command as TestCommand;
 use tracing::metadata::LevelFilter;
+pub use tree::{Command as TreeCommand, TreeFormat};
 pub use update::Command as UpdateCommand;
 
 mod commands;
@@ -47,6 +52,7 @@ struct Opt {
 
 #[derive(Subcommand, Debug)]
 enum Forc {
+    Add(AddCommand),
     #[clap(name = "addr2line")]
     Addr2Line(Addr2LineCommand),
     #[clap(visible_alias = "b")]
@@ -56,9 +62,13 @@ enum Forc {
     Completions(CompletionsCommand),
     New(NewCommand),
     Init(InitCommand),
+    Package(PackageCommand),
     ParseBytecode(ParseBytecodeCommand),
+    Publish(PublishCommand),
+    Remove(RemoveCommand),
     #[clap(visible_alias = "t")]
     Test(TestCommand),
+    Tree(TreeCommand),
     Update(UpdateCommand),
     Plugins(PluginsCommand),
     Template(TemplateCommand),
@@ -76,16 +86,24 @@ enum Forc {
 
 pub async fn run_cli() -> Result<()> {
     let opt = Opt::parse();
+    // A test report written to stdout must not be interleaved with any other output.
+    let writer_mode = match &opt.command {
+        Forc::Test(command) if command.test_print.reports_to_stdout() => {
+            Some(TracingWriterMode::Stderr)
+        }
+        _ => None,
+    };
     let tracing_options = TracingSubscriberOptions {
         verbosity: Some(opt.verbose),
         silent: Some(opt.silent),
         log_level: opt.log_level,
-        ..Default::default()
+        writer_mode,
     };
 
     init_tracing_subscriber(tracing_options);
 
     match opt.command {
+        Forc::Add(command) => add::exec(command),
         Forc::Addr2Line(command) => addr2line::exec(command),
         Forc::Build(command) => build::exec(command),
         Forc::Check(command) => check::exec(command),
@@ -93,9 +111,13 @@ pub async fn run_cli() -> Result<()> {
         Forc::Completions(command) => completions::exec(command),
         Forc::Init(command) => init::exec(command),
         Forc::New(command) => new::exec(command),
+        Forc::Package(command) => package::exec(command),
         Forc::ParseBytecode(command) => parse_bytecode::exec(command),
         Forc::Plugins(command) => plugins::exec(command),
+        Forc::Publish(command) => publish::exec(command),
+        Forc::Remove(command) => remove::exec(command),
         Forc::Test(command) => test::exec(command),
+        Forc::Tree(command) => tree::exec(command),
         Forc::Update(command) => update::exec(command).await,
         Forc::Template(command) => template::exec(command),
         Forc::Plugin(args) => {
@@ -65,6 +65,20 @@ pub struct Build {
     /// Output the time elapsed over each part of the compilation process.
     #[clap(long)]
     pub time_phases: bool,
+    /// Compile every package from source, rather than reusing unchanged packages from the build
+    /// cache.
+    #[clap(long)]
+    pub no_cache: bool,
+    /// Comma separated list of features to enable, in addition to the package's default
+    /// features.
+    #[clap(long, value_delimiter = ',')]
+    pub features: Vec<String>,
+    /// Enable all features declared by the package.
+    #[clap(long)]
+    pub all_features: bool,
+    /// Do not enable the package's `default` features.
+    #[clap(long)]
+    pub no_default_features: bool,
     /// If set, outputs a binary file representing the script bytes.
     #[clap(short = 'o')]
     pub binary_outfile: Option<String>,
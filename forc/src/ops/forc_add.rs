@@ -0,0 +1,146 @@
+use crate::{
+    cli::AddCommand,
+    utils::dependencies::{ManifestEdit, CONTRACT_DEPENDENCIES_TABLE, DEPENDENCIES_TABLE},
+};
+use anyhow::{anyhow, bail, Context, Result};
+use forc_pkg::registry::Registry;
+use std::{
+    path::{Component, Path, PathBuf},
+    str::FromStr,
+};
+use sway_core::fuel_prelude::fuel_tx;
+use tracing::info;
+
+pub fn add(command: AddCommand) -> Result<()> {
+    let AddCommand {
+        name,
+        manifest_path,
+        path,
+        git,
+        branch,
+        tag,
+        rev,
+        package,
+        version,
+        contract,
+        salt,
+        offline_mode: offline,
+    } = command;
+
+    let mut edit = ManifestEdit::load(manifest_path)?;
+    let (table_name, other_table_name) = if contract {
+        (CONTRACT_DEPENDENCIES_TABLE, DEPENDENCIES_TABLE)
+    } else {
+        (DEPENDENCIES_TABLE, CONTRACT_DEPENDENCIES_TABLE)
+    };
+    if edit.contains(other_table_name, &name) {
+        bail!(
+            "`{}` is already listed under `[{}]`, remove it with `forc remove {}` first",
+            name,
+            other_table_name,
+            name
+        );
+    }
+
+    if let Some(version) = &version {
+        semver::VersionReq::parse(version)
+            .with_context(|| format!("invalid version requirement `{}`", version))?;
+    }
+
+    // Dependencies without a path or git source are sourced from the registry, in which case we
+    // default to the latest published version.
+    let version = match (&version, &path, &git) {
+        (None, None, None) => {
+            let pkg_name = package.as_deref().unwrap_or(&name);
+            let entry = Registry::from_env().resolve(pkg_name, &[semver::VersionReq::STAR])?;
+            Some(entry.version.to_string())
+        }
+        _ => version,
+    };
+
+    // Paths are specified relative to the current directory, but the manifest requires that they
+    // are relative to the package.
+    let path = match path {
+        Some(path) => Some(relative_dep_path(edit.manifest.dir(), Path::new(&path))?),
+        None => None,
+    };
+
+    let salt = match salt {
+        Some(salt) => {
+            let salt = fuel_tx::Salt::from_str(&salt)
+                .map_err(|e| anyhow!("invalid salt `{}`: {}", salt, e))?;
+            Some(format!("{:#x}", salt))
+        }
+        None => None,
+    };
+
+    let mut details = toml_edit::InlineTable::new();
+    let fields = [
+        ("version", version.clone()),
+        ("path", path),
+        ("git", git),
+        ("branch", branch),
+        ("tag", tag),
+        ("rev", rev),
+        ("package", package),
+        ("salt", salt),
+    ];
+    for (key, value) in fields {
+        if let Some(value) = value {
+            details.insert(key, value.into());
+        }
+    }
+    details.fmt();
+
+    // A dependency specified by version alone is written in the simple format.
+    let dep = match (&version, details.len()) {
+        (Some(version), 1) => toml_edit::value(version.as_str()),
+        _ => toml_edit::value(details),
+    };
+
+    if edit.table_mut(table_name)?.is_none() {
+        edit.doc[table_name] = toml_edit::table();
+    }
+    let table = edit
+        .table_mut(table_name)?
+        .expect("dependencies table was inserted above");
+    let replaced = table.insert(&name, dep).is_some();
+
+    let manifest_path = edit.manifest.path().to_path_buf();
+    edit.commit(offline)?;
+    let action = if replaced { "Updated" } else { "Added" };
+    info!(
+        "  {} `{}` in `[{}]` of {}",
+        action,
+        name,
+        table_name,
+        manifest_path.display()
+    );
+    Ok(())
+}
+
+/// Produce the path to the given dependency relative to the package directory.
+fn relative_dep_path(pkg_dir: &Path, dep_path: &Path) -> Result<String> {
+    let canonical = |path: &Path| {
+        path.canonicalize()
+            .with_context(|| format!("failed to find {}", path.display()))
+    };
+    let pkg_dir = canonical(pkg_dir)?;
+    let dep_dir = canonical(dep_path)?;
+    let pkg_components: Vec<Component> = pkg_dir.components().collect();
+    let dep_components: Vec<Component> = dep_dir.components().collect();
+    let common = pkg_components
+        .iter()
+        .zip(&dep_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut relative = PathBuf::new();
+    for _ in common..pkg_components.len() {
+        relative.push("..");
+    }
+    relative.extend(&dep_components[common..]);
+    if relative.as_os_str().is_empty() {
+        bail!("a package cannot depend on itself");
+    }
+    Ok(relative.display().to_string())
+}
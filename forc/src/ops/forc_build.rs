@@ -28,9 +28,15 @@ fn opts_from_cmd(cmd: BuildCommand) -> pkg::BuildOpts {
             json_abi: cmd.build.minify_json_abi,
             json_storage_slots: cmd.build.minify_json_storage_slots,
         },
+        features: pkg::FeatureOpts {
+            features: cmd.build.features,
+            all_features: cmd.build.all_features,
+            no_default_features: cmd.build.no_default_features,
+        },
         build_profile: cmd.build.build_profile,
         release: cmd.build.release,
         time_phases: cmd.build.time_phases,
+        no_cache: cmd.build.no_cache,
         binary_outfile: cmd.build.binary_outfile,
         debug_outfile: cmd.build.debug_outfile,
         tests: cmd.tests,
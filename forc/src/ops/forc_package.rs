@@ -0,0 +1,157 @@
+use crate::cli::PackageCommand;
+use anyhow::{anyhow, bail, Context, Result};
+use forc_pkg::{
+    self as pkg,
+    manifest::{Dependency, ManifestFile, PackageManifestFile},
+    registry,
+};
+use forc_util::default_output_directory;
+use std::{fs, path::PathBuf};
+use tracing::info;
+
+/// The name of the directory within the output directory in which archives are placed.
+const PACKAGE_DIR_NAME: &str = "package";
+
+/// A package assembled into an archive.
+pub struct Packaged {
+    pub manifest: PackageManifestFile,
+    pub version: semver::Version,
+    pub archive: Vec<u8>,
+}
+
+pub fn package(command: PackageCommand) -> Result<()> {
+    let PackageCommand {
+        path,
+        output_directory,
+        no_verify,
+        offline_mode,
+    } = command;
+    let packaged = assemble(path, !no_verify, offline_mode)?;
+    let name = &packaged.manifest.project.name;
+    let output_dir = match output_directory {
+        Some(dir) => PathBuf::from(dir),
+        None => default_output_directory(packaged.manifest.dir()).join(PACKAGE_DIR_NAME),
+    };
+    fs::create_dir_all(&output_dir)?;
+    let file_name = format!(
+        "{}.{}",
+        registry::archive_dir_name(name, &packaged.version),
+        registry::ARCHIVE_EXTENSION
+    );
+    let archive_path = output_dir.join(&file_name);
+    fs::write(&archive_path, &packaged.archive)?;
+    let checksum = registry::checksum(&packaged.archive);
+    // Written in the format expected by `sha256sum --check`.
+    fs::write(
+        output_dir.join(format!("{}.sha256", file_name)),
+        format!("{}  {}\n", checksum, file_name),
+    )?;
+    info!(
+        "  Packaged {} v{} to {}",
+        name,
+        packaged.version,
+        archive_path.display()
+    );
+    info!("  Checksum: {}", checksum);
+    Ok(())
+}
+
+/// Assemble the package at the given path into an archive.
+///
+/// If `verify` is `true`, the archive is extracted to a temporary directory and built to ensure
+/// that it does not depend on any files outside of the archive.
+pub(crate) fn assemble(path: Option<String>, verify: bool, offline: bool) -> Result<Packaged> {
+    let this_dir = match path {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir()?,
+    };
+    let manifest = match ManifestFile::from_dir(&this_dir)? {
+        ManifestFile::Package(manifest) => *manifest,
+        ManifestFile::Workspace(_) => bail!(
+            "cannot package workspace {}: packages must be packaged individually",
+            this_dir.display()
+        ),
+    };
+    validate_manifest(&manifest)?;
+    let version = manifest
+        .project
+        .version
+        .clone()
+        .expect("version is checked during validation");
+    let archive = registry::package_archive(&manifest)?;
+    if verify {
+        verify_archive(&manifest, &version, &archive, offline)?;
+    }
+    Ok(Packaged {
+        manifest,
+        version,
+        archive,
+    })
+}
+
+/// Ensure that the package may be built from the contents of its archive alone.
+fn validate_manifest(manifest: &PackageManifestFile) -> Result<()> {
+    let name = &manifest.project.name;
+    if manifest.project.version.is_none() {
+        bail!(
+            "the `version` of package `{}` must be specified under `[project]` in order to \
+            package it",
+            name
+        );
+    }
+    let deps = manifest
+        .deps()
+        .chain(manifest.contract_deps().map(|(n, d)| (n, &d.dependency)))
+        .chain(manifest.patches().flat_map(|(_, patches)| patches.iter()));
+    for (dep_name, dep) in deps {
+        if let Dependency::Detailed(details) = dep {
+            if details.path.is_some() {
+                bail!(
+                    "dependency `{}` of package `{}` is specified by `path`; packaged \
+                    dependencies must be sourced from a registry or a git repository",
+                    dep_name,
+                    name
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Build the package from its archive within a temporary directory.
+fn verify_archive(
+    manifest: &PackageManifestFile,
+    version: &semver::Version,
+    archive: &[u8],
+    offline: bool,
+) -> Result<()> {
+    let name = &manifest.project.name;
+    info!("  Verifying {} v{}", name, version);
+    let fetch_id = pkg::fetch_id(manifest.dir(), std::time::Instant::now());
+    let tmp_dir = std::env::temp_dir().join(format!("forc-package-{:x}", fetch_id));
+    let result = registry::unpack_archive(archive, name, version, &tmp_dir).and_then(|dir| {
+        let path = dir
+            .to_str()
+            .ok_or_else(|| anyhow!("invalid path {}", dir.display()))?
+            .to_string();
+        let build_options = pkg::BuildOpts {
+            pkg: pkg::PkgOpts {
+                path: Some(path),
+                offline,
+                terse: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        pkg::build_with_options(build_options)
+    });
+    let _ = fs::remove_dir_all(&tmp_dir);
+    result.with_context(|| {
+        format!(
+            "failed to verify package `{}`: the package must build from the contents of its \
+            archive alone",
+            name
+        )
+    })?;
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::{cli::PublishCommand, ops::forc_package};
+use anyhow::Result;
+use forc_pkg::registry::Registry;
+use std::path::PathBuf;
+use tracing::info;
+
+pub fn publish(command: PublishCommand) -> Result<()> {
+    let PublishCommand {
+        path,
+        registry,
+        no_verify,
+        offline_mode,
+    } = command;
+    let registry = match registry {
+        Some(path) => Registry::new(PathBuf::from(path)),
+        None => Registry::from_env(),
+    };
+    let packaged = forc_package::assemble(path, !no_verify, offline_mode)?;
+    let name = &packaged.manifest.project.name;
+    let entry = registry.publish(name, &packaged.version, &packaged.archive)?;
+    info!(
+        "  Published {} v{} to {}",
+        name,
+        entry.version,
+        registry.path().display()
+    );
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::{
+    cli::RemoveCommand,
+    utils::dependencies::{ManifestEdit, CONTRACT_DEPENDENCIES_TABLE, DEPENDENCIES_TABLE},
+};
+use anyhow::{bail, Result};
+use tracing::info;
+
+pub fn remove(command: RemoveCommand) -> Result<()> {
+    let RemoveCommand {
+        names,
+        manifest_path,
+        offline_mode: offline,
+    } = command;
+
+    let mut edit = ManifestEdit::load(manifest_path)?;
+    let mut removed = vec![];
+    for name in &names {
+        let mut table_name = None;
+        for name_of_table in [DEPENDENCIES_TABLE, CONTRACT_DEPENDENCIES_TABLE] {
+            if let Some(table) = edit.table_mut(name_of_table)? {
+                if table.remove(name).is_some() {
+                    table_name = Some(name_of_table);
+                    break;
+                }
+            }
+        }
+        match table_name {
+            Some(table_name) => removed.push((name, table_name)),
+            None => bail!(
+                "the dependency `{}` could not be found in `[{}]` or `[{}]`",
+                name,
+                DEPENDENCIES_TABLE,
+                CONTRACT_DEPENDENCIES_TABLE
+            ),
+        }
+    }
+
+    let manifest_path = edit.manifest.path().to_path_buf();
+    edit.commit(offline)?;
+    for (name, table_name) in removed {
+        info!(
+            "  Removed `{}` from `[{}]` of {}",
+            name,
+            table_name,
+            manifest_path.display()
+        );
+    }
+    Ok(())
+}
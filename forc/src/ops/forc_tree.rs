@@ -0,0 +1,269 @@
+use crate::cli::{TreeCommand, TreeFormat};
+use anyhow::{anyhow, bail, Result};
+use forc_pkg::{self as pkg, manifest::ManifestFile, DepKind, Edge, Graph, NodeIx, SourcePinned};
+use petgraph::{
+    visit::{Dfs, EdgeRef, Reversed, Walker},
+    Direction,
+};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Write,
+    path::PathBuf,
+};
+
+pub fn tree(command: TreeCommand) -> Result<()> {
+    let TreeCommand {
+        path,
+        invert,
+        duplicates,
+        format,
+        offline_mode: offline,
+        locked,
+    } = command;
+
+    let this_dir = match path {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir()?,
+    };
+    let manifest_file = ManifestFile::from_dir(&this_dir)?;
+    let member_manifests = manifest_file.member_manifests()?;
+    let lock_path = manifest_file.lock_path()?;
+    let plan =
+        pkg::BuildPlan::from_lock_and_manifests(&lock_path, &member_manifests, locked, offline)?;
+
+    // Determine the roots of the tree, along with the direction in which to traverse the graph.
+    let graph = plan.graph();
+    let (mut roots, direction) = match (invert, duplicates) {
+        (Some(name), _) => {
+            let nodes = nodes_named(graph, &name);
+            if nodes.is_empty() {
+                bail!("package `{}` not found in the dependency graph", name);
+            }
+            (nodes, Direction::Incoming)
+        }
+        (None, true) => (duplicate_nodes(graph), Direction::Incoming),
+        (None, false) => {
+            let roots = match &manifest_file {
+                ManifestFile::Package(manifest) => {
+                    let node = plan
+                        .find_member_index(&manifest.project.name)
+                        .ok_or_else(|| anyhow!("failed to find the project in the graph"))?;
+                    vec![node]
+                }
+                ManifestFile::Workspace(_) => {
+                    let mut members: Vec<_> = plan.member_nodes().collect();
+                    members.sort_by(|&a, &b| graph[a].name.cmp(&graph[b].name));
+                    members
+                }
+            };
+            (roots, Direction::Outgoing)
+        }
+    };
+
+    if direction == Direction::Incoming {
+        roots.sort_by_cached_key(|&node| node_label(&plan, node));
+    }
+    let output = match format {
+        TreeFormat::Text => text_trees(&plan, &roots, direction),
+        TreeFormat::Dot => dot_graph(&plan, &roots, direction),
+    };
+    print!("{}", output);
+    Ok(())
+}
+
+/// All nodes for packages with the given name.
+fn nodes_named(graph: &Graph, name: &str) -> Vec<NodeIx> {
+    graph
+        .node_indices()
+        .filter(|&n| graph[n].name == name)
+        .collect()
+}
+
+/// All nodes for packages that are included in the graph more than once, e.g. from different
+/// sources or versions.
+fn duplicate_nodes(graph: &Graph) -> Vec<NodeIx> {
+    let mut by_name: BTreeMap<&str, Vec<NodeIx>> = BTreeMap::new();
+    for node in graph.node_indices() {
+        by_name.entry(&graph[node].name).or_default().push(node);
+    }
+    by_name
+        .into_values()
+        .filter(|nodes| nodes.len() > 1)
+        .flatten()
+        .collect()
+}
+
+/// Describes the package at the given node, e.g. `std v0.1.0 (git+https://...)`.
+fn node_label(plan: &pkg::BuildPlan, node: NodeIx) -> String {
+    let pinned = &plan.graph()[node];
+    let manifest = &plan.manifest_map()[&pinned.id()];
+    let mut label = pinned.name.clone();
+    let version = match &pinned.source {
+        SourcePinned::Registry(reg) => Some(&reg.version),
+        _ => manifest.project.version.as_ref(),
+    };
+    if let Some(version) = version {
+        write!(label, " v{}", version).unwrap();
+    }
+    match &pinned.source {
+        SourcePinned::Member | SourcePinned::Path(_) => {
+            write!(label, " ({})", manifest.dir().display()).unwrap()
+        }
+        SourcePinned::Git(git) => write!(label, " ({})", git).unwrap(),
+        SourcePinned::Registry(_) => write!(label, " (registry)").unwrap(),
+    }
+    label
+}
+
+/// Describes the dependency edge, in the case that it is not a library dependency of the same name.
+fn edge_label(plan: &pkg::BuildPlan, edge: &Edge, dep_node: NodeIx) -> Option<String> {
+    let mut labels = vec![];
+    if edge.name != plan.graph()[dep_node].name {
+        labels.push(format!("as {}", edge.name));
+    }
+    if let DepKind::Contract { salt } = edge.kind {
+        labels.push(format!("contract, salt {:#x}", salt));
+    }
+    (!labels.is_empty()).then(|| labels.join(", "))
+}
+
+/// Render the tree rooted at each of the given nodes, separated by empty lines.
+fn text_trees(plan: &pkg::BuildPlan, roots: &[NodeIx], direction: Direction) -> String {
+    let mut output = String::new();
+    for (ix, &root) in roots.iter().enumerate() {
+        if ix > 0 {
+            output.push('\n');
+        }
+        // Packages are only expanded once per tree.
+        let mut visited = HashSet::new();
+        write_text_node(
+            plan,
+            root,
+            None,
+            direction,
+            "",
+            "",
+            &mut visited,
+            &mut output,
+        );
+    }
+    output
+}
+
+/// Write the line for the given node, followed by the subtree of its dependencies or dependents.
+#[allow(clippy::too_many_arguments)]
+fn write_text_node(
+    plan: &pkg::BuildPlan,
+    node: NodeIx,
+    edge_label: Option<String>,
+    direction: Direction,
+    indent: &str,
+    connector: &str,
+    visited: &mut HashSet<NodeIx>,
+    output: &mut String,
+) {
+    let graph = plan.graph();
+    let mut children: Vec<_> = graph
+        .edges_directed(node, direction)
+        .map(|edge| {
+            let (child, dep_node) = match direction {
+                Direction::Outgoing => (edge.target(), edge.target()),
+                Direction::Incoming => (edge.source(), edge.target()),
+            };
+            let label = self::edge_label(plan, edge.weight(), dep_node);
+            (node_label(plan, child), child, label)
+        })
+        .collect();
+    children.sort();
+
+    let expand = visited.insert(node);
+    write!(output, "{}{}{}", indent, connector, node_label(plan, node)).unwrap();
+    if let Some(edge_label) = edge_label {
+        write!(output, " [{}]", edge_label).unwrap();
+    }
+    if !expand && !children.is_empty() {
+        write!(output, " (*)").unwrap();
+    }
+    output.push('\n');
+    if !expand {
+        return;
+    }
+
+    let child_indent = match connector {
+        "├── " => format!("{}│   ", indent),
+        "└── " => format!("{}    ", indent),
+        _ => indent.to_string(),
+    };
+    let last = children.len().saturating_sub(1);
+    for (ix, (_, child, edge_label)) in children.into_iter().enumerate() {
+        let connector = if ix == last {
+            "└── "
+        } else {
+            "├── "
+        };
+        write_text_node(
+            plan,
+            child,
+            edge_label,
+            direction,
+            &child_indent,
+            connector,
+            visited,
+            output,
+        );
+    }
+}
+
+/// Render the subgraph reachable from the given roots in the Graphviz DOT language.
+///
+/// Edges are always directed from the dependent to the dependency.
+fn dot_graph(plan: &pkg::BuildPlan, roots: &[NodeIx], direction: Direction) -> String {
+    let graph = plan.graph();
+    let mut nodes = HashSet::new();
+    for &root in roots {
+        match direction {
+            Direction::Outgoing => nodes.extend(Dfs::new(graph, root).iter(graph)),
+            Direction::Incoming => {
+                nodes.extend(Dfs::new(Reversed(graph), root).iter(Reversed(graph)))
+            }
+        }
+    }
+    let mut nodes: Vec<_> = nodes.into_iter().collect();
+    nodes.sort();
+
+    let mut output = String::from("digraph {\n");
+    for &node in &nodes {
+        writeln!(
+            output,
+            "    {} [label=\"{}\"]",
+            node.index(),
+            escape_dot(&node_label(plan, node))
+        )
+        .unwrap();
+    }
+    for &node in &nodes {
+        for edge in graph.edges_directed(node, Direction::Outgoing) {
+            if nodes.binary_search(&edge.target()).is_err() {
+                continue;
+            }
+            write!(
+                output,
+                "    {} -> {}",
+                edge.source().index(),
+                edge.target().index()
+            )
+            .unwrap();
+            if let Some(label) = edge_label(plan, edge.weight(), edge.target()) {
+                write!(output, " [label=\"{}\"]", escape_dot(&label)).unwrap();
+            }
+            output.push('\n');
+        }
+    }
+    output.push_str("}\n");
+    output
+}
+
+/// Escape the given text for use within a quoted DOT string.
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -1,6 +1,11 @@
+pub mod forc_add;
 pub mod forc_build;
 pub mod forc_check;
 pub mod forc_clean;
 pub mod forc_init;
+pub mod forc_package;
+pub mod forc_publish;
+pub mod forc_remove;
 pub mod forc_template;
+pub mod forc_tree;
 pub mod forc_update;
@@ -0,0 +1,95 @@
+//! Utilities shared by the commands that edit the dependencies of a package's manifest.
+
+use anyhow::{anyhow, bail, Context, Result};
+use forc_pkg::{
+    self as pkg,
+    manifest::{ManifestFile, PackageManifestFile},
+};
+use std::{fs, path::PathBuf};
+
+/// The manifest table containing library dependencies.
+pub const DEPENDENCIES_TABLE: &str = "dependencies";
+/// The manifest table containing contract dependencies.
+pub const CONTRACT_DEPENDENCIES_TABLE: &str = "contract-dependencies";
+
+/// A package manifest that is being edited, along with its original contents.
+pub struct ManifestEdit {
+    pub manifest: PackageManifestFile,
+    original: String,
+    pub doc: toml_edit::Document,
+}
+
+impl ManifestEdit {
+    /// Load the manifest of the package at the given path, or the current working directory.
+    pub fn load(path: Option<String>) -> Result<Self> {
+        let this_dir = match path {
+            Some(path) => PathBuf::from(path),
+            None => std::env::current_dir()?,
+        };
+        let manifest = match ManifestFile::from_dir(&this_dir)? {
+            ManifestFile::Package(manifest) => *manifest,
+            ManifestFile::Workspace(manifest) => bail!(
+                "{} is a workspace manifest, the dependencies of a workspace member must be \
+                edited within the member's directory",
+                manifest.path().display()
+            ),
+        };
+        let original = fs::read_to_string(manifest.path())
+            .with_context(|| format!("failed to read {}", manifest.path().display()))?;
+        let doc = original
+            .parse::<toml_edit::Document>()
+            .with_context(|| format!("failed to parse {}", manifest.path().display()))?;
+        Ok(Self {
+            manifest,
+            original,
+            doc,
+        })
+    }
+
+    /// The dependencies table with the given name, if any.
+    pub fn table_mut(&mut self, name: &str) -> Result<Option<&mut dyn toml_edit::TableLike>> {
+        match self.doc.get_mut(name) {
+            None => Ok(None),
+            Some(item) => item
+                .as_table_like_mut()
+                .map(Some)
+                .ok_or_else(|| anyhow!("`{}` in the manifest is not a table", name)),
+        }
+    }
+
+    /// Whether or not the dependencies table with the given name contains the given dependency.
+    pub fn contains(&self, table: &str, dep_name: &str) -> bool {
+        self.doc
+            .get(table)
+            .and_then(|item| item.as_table_like())
+            .map(|table| table.contains_key(dep_name))
+            .unwrap_or(false)
+    }
+
+    /// Write the edited manifest and update the lock file, fetching and pinning any new
+    /// dependencies.
+    ///
+    /// In the case that the dependency graph cannot be updated, the original manifest is restored.
+    pub fn commit(self, offline: bool) -> Result<()> {
+        let path = self.manifest.path();
+        fs::write(path, self.doc.to_string())
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        if let Err(err) = update_lock(&self.manifest, offline) {
+            fs::write(path, &self.original)
+                .with_context(|| format!("failed to restore {}", path.display()))?;
+            return Err(err);
+        }
+        Ok(())
+    }
+}
+
+/// Reload the edited manifest and produce a build plan, updating the lock file in the case that
+/// it no longer matches the manifests.
+fn update_lock(manifest: &PackageManifestFile, offline: bool) -> Result<()> {
+    let manifest_file = ManifestFile::from_dir(manifest.dir())?;
+    let member_manifests = manifest_file.member_manifests()?;
+    let lock_path = manifest_file.lock_path()?;
+    let locked = false;
+    pkg::BuildPlan::from_lock_and_manifests(&lock_path, &member_manifests, locked, offline)?;
+    Ok(())
+}
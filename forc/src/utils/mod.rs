@@ -1,2 +1,3 @@
 pub mod defaults;
+pub mod dependencies;
 pub mod program_type;
@@ -12,6 +12,8 @@ pub struct Annotated<T> {
 //    #[attribute()]
 //    #[attribute(value)]
 //    #[attribute(value0, value1, value2)]
+//    #[attribute(name = "value")]
+//    #[attribute(name(value0, value1))]
 
 #[derive(Clone, Debug)]
 pub struct AttributeDecl {
@@ -28,7 +30,7 @@ impl Spanned for AttributeDecl {
 #[derive(Clone, Debug)]
 pub struct Attribute {
     pub name: Ident,
-    pub args: Option<Parens<Punctuated<Ident, CommaToken>>>,
+    pub args: Option<Parens<Punctuated<AttributeArg, CommaToken>>>,
 }
 
 impl Spanned for Attribute {
@@ -39,3 +41,22 @@ impl Spanned for Attribute {
             .unwrap_or_else(|| self.name.span())
     }
 }
+
+/// An argument to an attribute, optionally assigned a literal value, e.g. `should_revert = "42"`,
+/// or applied to a nested list of arguments, e.g. `not(feature = "logging")`.
+#[derive(Clone, Debug)]
+pub struct AttributeArg {
+    pub name: Ident,
+    pub value_opt: Option<(EqToken, Literal)>,
+    pub args: Option<Parens<Punctuated<AttributeArg, CommaToken>>>,
+}
+
+impl Spanned for AttributeArg {
+    fn span(&self) -> Span {
+        match (&self.value_opt, &self.args) {
+            (Some((_eq_token, value)), _) => Span::join(self.name.span(), value.span()),
+            (None, Some(args)) => Span::join(self.name.span(), args.span()),
+            (None, None) => self.name.span(),
+        }
+    }
+}
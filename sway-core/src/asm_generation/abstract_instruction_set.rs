@@ -1,8 +1,11 @@
 use crate::{
-    asm_generation::{register_allocator, AllocatedAbstractInstructionSet, RegisterSequencer},
+    asm_generation::{
+        compiler_constants, register_allocator, AllocatedAbstractInstructionSet, RegisterSequencer,
+    },
     asm_lang::{
         allocated_ops::{AllocatedOp, AllocatedOpcode},
-        AllocatedAbstractOp, Op, OrganizationalOp, RealizedOp, VirtualOp, VirtualRegister,
+        AllocatedAbstractOp, ControlFlowOp, Op, OrganizationalOp, RealizedOp, VirtualImmediate24,
+        VirtualOp, VirtualRegister,
     },
 };
 
@@ -165,31 +168,91 @@ impl AbstractInstructionSet {
     /// algorithm (https://en.wikipedia.org/wiki/Chaitin%27s_algorithm). The individual steps of
     /// the algorithm are thoroughly explained in register_allocator.rs.
     ///
+    /// In the case that there are not enough registers, some virtual registers are spilled to the
+    /// stack and the allocation is repeated until it succeeds. Fails if the registers cannot be
+    /// assigned even after spilling, or if the spilled registers do not fit within the stack.
+    ///
     pub(crate) fn allocate_registers(
         self,
         register_sequencer: &mut RegisterSequencer,
-    ) -> AllocatedAbstractInstructionSet {
-        // Step 1: Liveness Analysis.
-        let live_out = register_allocator::liveness_analysis(&self.ops);
+    ) -> Result<AllocatedAbstractInstructionSet, CompileError> {
+        let mut ops = self.ops;
+        let mut num_registers = usize::MAX;
+        let mut spill_slots = register_allocator::SpillSlots::default();
+        let (reduced_ops, pool) = loop {
+            // Step 1: Liveness Analysis.
+            let live_out = register_allocator::liveness_analysis(&ops);
 
-        // Step 2: Construct the interference graph.
-        let (mut interference_graph, mut reg_to_node_ix) =
-            register_allocator::create_interference_graph(&self.ops, &live_out);
+            // Step 2: Construct the interference graph.
+            let (mut interference_graph, mut reg_to_node_ix) =
+                register_allocator::create_interference_graph(&ops, &live_out);
 
-        // Step 3: Remove redundant MOVE instructions using the interference graph.
-        let reduced_ops = register_allocator::coalesce_registers(
-            &self.ops,
-            &mut interference_graph,
-            &mut reg_to_node_ix,
-            register_sequencer,
-        );
+            // Step 3: Remove redundant MOVE instructions using the interference graph.
+            let reduced_ops = register_allocator::coalesce_registers(
+                &ops,
+                &mut interference_graph,
+                &mut reg_to_node_ix,
+                register_sequencer,
+            );
 
-        // Step 4: Simplify - i.e. color the interference graph and return a stack that contains
-        // each colorable node and its neighbors.
-        let mut stack = register_allocator::color_interference_graph(&mut interference_graph);
+            // Step 4: Simplify - i.e. color the interference graph and return a stack that
+            // contains each colorable node and its neighbors. If some nodes can't be colored,
+            // spill them to the stack and start over.
+            let spill_costs = register_allocator::spill_costs(&reduced_ops, &spill_slots);
+            let uncolored_graph = interference_graph.clone();
+            let mut stack = match register_allocator::color_interference_graph(
+                &mut interference_graph,
+                num_registers,
+                &spill_costs,
+            ) {
+                Ok(stack) => stack,
+                Err(spills) => {
+                    ops = register_allocator::spill_registers(
+                        &reduced_ops,
+                        &spills,
+                        &mut spill_slots,
+                        register_sequencer,
+                    );
+                    continue;
+                }
+            };
 
-        // Step 5: Use the stack to assign a register for each virtual register.
-        let pool = register_allocator::assign_registers(&mut stack);
+            // Step 5: Use the stack to assign a register for each virtual register. If that
+            // fails, color the graph again with the actual number of registers, such that
+            // spilling takes place. If it still fails, spill the cheapest of the unassigned
+            // register and its neighbors, and start over.
+            match register_allocator::assign_registers(&mut stack) {
+                Ok(pool) => break (reduced_ops, pool),
+                Err(_) if num_registers == usize::MAX => {
+                    num_registers = compiler_constants::NUM_ALLOCATABLE_REGISTERS as usize;
+                    ops = reduced_ops;
+                }
+                Err(reg) => {
+                    let spill = register_allocator::spill_candidate(
+                        &reg,
+                        &uncolored_graph,
+                        &reg_to_node_ix,
+                        &spill_costs,
+                    )
+                    .ok_or_else(|| {
+                        CompileError::InternalOwned(
+                            format!(
+                                "The allocator cannot resolve a register mapping for {} in this \
+                                program.",
+                                reg
+                            ),
+                            Span::dummy(),
+                        )
+                    })?;
+                    ops = register_allocator::spill_registers(
+                        &reduced_ops,
+                        &BTreeSet::from([spill]),
+                        &mut spill_slots,
+                        register_sequencer,
+                    );
+                }
+            }
+        };
 
         // Step 6: Update all instructions to use the resulting register pool.
         let mut buf = vec![];
@@ -201,7 +264,42 @@ impl AbstractInstructionSet {
             })
         }
 
-        AllocatedAbstractInstructionSet { ops: buf }
+        // Step 7: Reserve the stack space for the spilled registers, if any, right after their
+        // base address is saved, and free it again before the saved registers are restored.
+        let spill_size = spill_slots.size_in_bytes();
+        if spill_size != 0 {
+            if spill_size > compiler_constants::TWENTY_FOUR_BITS {
+                return Err(CompileError::Internal(
+                    "The registers spilled to the stack exceed the maximum stack frame size.",
+                    Span::dummy(),
+                ));
+            }
+            let spill_size = VirtualImmediate24 {
+                value: spill_size as u32,
+            };
+            let spill_base_index = register_allocator::spill_base_index(&reduced_ops);
+            buf.insert(
+                spill_base_index + 1,
+                AllocatedAbstractOp {
+                    opcode: Either::Left(AllocatedOpcode::CFEI(spill_size.clone())),
+                    comment: format!("allocate {} bytes for spilled registers", spill_size.value),
+                    owning_span: None,
+                },
+            );
+            buf = buf.into_iter().fold(Vec::new(), |mut ops, op| {
+                if let Either::Right(ControlFlowOp::PopAll(_)) = op.opcode {
+                    ops.push(AllocatedAbstractOp {
+                        opcode: Either::Left(AllocatedOpcode::CFSI(spill_size.clone())),
+                        comment: format!("free {} bytes for spilled registers", spill_size.value),
+                        owning_span: None,
+                    });
+                }
+                ops.push(op);
+                ops
+            });
+        }
+
+        Ok(AllocatedAbstractInstructionSet { ops: buf })
     }
 }
 
@@ -55,24 +55,31 @@ pub(super) struct AsmBuilder<'ir> {
     // Metadata manager for converting metadata to Spans, etc.
     md_mgr: MetadataManager,
 
-    // Final resulting VM bytecode ops; entry functions with their function and label, and regular
-    // non-entry functions.
-    entries: Vec<(Function, Label, Vec<Op>, Option<DeclarationId>)>,
+    // Final resulting VM bytecode ops; entry functions and regular non-entry functions.
+    entries: Vec<AsmBuilderEntry>,
     non_entries: Vec<Vec<Op>>,
 
     // In progress VM bytecode ops.
     cur_bytecode: Vec<Op>,
 }
 
+/// A compiled entry function.
+pub(super) struct AsmBuilderEntry {
+    pub(super) function: Function,
+    pub(super) label: Label,
+    pub(super) ops: AbstractInstructionSet,
+    /// The test declaration, if this is a unit test.
+    pub(super) test_decl_id: Option<DeclarationId>,
+    /// Whether this is a contract method to be checked first by the selector switch.
+    pub(super) is_hot: bool,
+    /// Whether this is the contract function to be called when no selector matches.
+    pub(super) is_fallback: bool,
+}
+
 type AsmBuilderResult = (
     DataSection,
     RegisterSequencer,
-    Vec<(
-        Function,
-        Label,
-        AbstractInstructionSet,
-        Option<DeclarationId>,
-    )>,
+    Vec<AsmBuilderEntry>,
     Vec<AbstractInstructionSet>,
 );
 
@@ -122,12 +129,7 @@ impl<'ir> AsmBuilder<'ir> {
         (
             self.data_section,
             self.reg_seqr,
-            self.entries
-                .into_iter()
-                .map(|(f, l, ops, test_decl_id)| {
-                    (f, l, AbstractInstructionSet { ops }, test_decl_id)
-                })
-                .collect(),
+            self.entries,
             self.non_entries
                 .into_iter()
                 .map(|ops| AbstractInstructionSet { ops })
@@ -154,7 +156,12 @@ impl<'ir> AsmBuilder<'ir> {
                     self.compile_binary_op(instr_val, op, arg1, arg2)
                 }
                 Instruction::Branch(to_block) => self.compile_branch(to_block),
-                Instruction::Call(func, args) => self.compile_call(instr_val, func, args),
+                Instruction::Call(func, args) => check!(
+                    self.compile_call(instr_val, func, args),
+                    return err(warnings, errors),
+                    warnings,
+                    errors
+                ),
                 Instruction::Cmp(pred, lhs_value, rhs_value) => {
                     self.compile_cmp(instr_val, pred, lhs_value, rhs_value)
                 }
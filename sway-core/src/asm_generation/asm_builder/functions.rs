@@ -1,10 +1,10 @@
 use super::{
     compiler_constants, ir_type_size_in_bytes, size_bytes_in_words,
-    size_bytes_round_up_to_word_alignment, AsmBuilder, ProgramKind,
+    size_bytes_round_up_to_word_alignment, AsmBuilder, AsmBuilderEntry, ProgramKind,
 };
 
 use crate::{
-    asm_generation::{from_ir::*, Entry},
+    asm_generation::{from_ir::*, AbstractInstructionSet, Entry},
     asm_lang::{
         virtual_register::*, Op, OrganizationalOp, VirtualImmediate12, VirtualImmediate18,
         VirtualImmediate24, VirtualOp,
@@ -14,6 +14,7 @@ use crate::{
     fuel_prelude::fuel_asm::GTFArgs,
 };
 
+use sway_error::error::CompileError;
 use sway_ir::*;
 
 use either::Either;
@@ -21,7 +22,7 @@ use either::Either;
 /// A summary of the adopted calling convention:
 ///
 /// - Function arguments are passed left to right in the reserved registers.  Extra args are passed
-///   on the stack.
+///   on the stack, a word each, in which case the last reserved register holds their address.
 /// - The return value is returned in $retv.
 /// - The return address is passed in $reta.
 /// - All other general purpose registers must be preserved.
@@ -52,23 +53,71 @@ use either::Either;
 ///   - Jump to the return address.
 
 impl<'ir> AsmBuilder<'ir> {
-    pub(super) fn compile_call(&mut self, instr_val: &Value, function: &Function, args: &[Value]) {
-        // Put the args into the args registers.
-        for (idx, arg_val) in args.iter().enumerate() {
-            if idx < compiler_constants::NUM_ARG_REGISTERS as usize {
-                let arg_reg = self.value_to_register(arg_val);
-                self.cur_bytecode.push(Op::register_move(
-                    VirtualRegister::Constant(ConstantRegister::ARG_REGS[idx]),
-                    arg_reg,
-                    format!("pass arg {idx}"),
-                    self.md_mgr.val_to_span(self.context, *arg_val),
-                ));
-            } else {
-                todo!(
-                    "can't do more than {} args yet",
-                    compiler_constants::NUM_ARG_REGISTERS
+    pub(super) fn compile_call(
+        &mut self,
+        instr_val: &Value,
+        function: &Function,
+        args: &[Value],
+    ) -> CompileResult<()> {
+        // If there are too many args for the args registers then the surplus are written to the
+        // stack, and the last args register instead holds their address.
+        let num_reg_args = num_reg_args(args.len());
+        let stack_args_size = (args.len() - num_reg_args) as u64 * 8;
+        if stack_args_size != 0 {
+            if stack_args_size > compiler_constants::TWENTY_FOUR_BITS {
+                return err(
+                    Vec::new(),
+                    vec![CompileError::Internal(
+                        "Too many arguments are passed on the stack to this function.",
+                        self.md_mgr
+                            .val_to_span(self.context, *instr_val)
+                            .unwrap_or_else(Self::empty_span),
+                    )],
                 );
             }
+            let stack_args_base_reg = self.reg_seqr.next();
+            self.cur_bytecode.push(Op::register_move(
+                stack_args_base_reg.clone(),
+                VirtualRegister::Constant(ConstantRegister::StackPointer),
+                "save stack args base register",
+                None,
+            ));
+            self.cur_bytecode.push(Op {
+                opcode: Either::Left(VirtualOp::CFEI(VirtualImmediate24 {
+                    value: stack_args_size as u32,
+                })),
+                comment: format!("allocate {} bytes for stack args", stack_args_size),
+                owning_span: None,
+            });
+            for (word_offset, (idx, arg_val)) in
+                args.iter().enumerate().skip(num_reg_args).enumerate()
+            {
+                let arg_reg = self.value_to_register(arg_val);
+                let (base_reg, offset) =
+                    self.stack_arg_address(&stack_args_base_reg, word_offset as u64);
+                self.cur_bytecode.push(Op {
+                    opcode: Either::Left(VirtualOp::SW(base_reg, arg_reg, offset)),
+                    comment: format!("pass arg {idx} on the stack"),
+                    owning_span: self.md_mgr.val_to_span(self.context, *arg_val),
+                });
+            }
+            self.cur_bytecode.push(Op::register_move(
+                VirtualRegister::Constant(ConstantRegister::ARG_REGS[num_reg_args]),
+                stack_args_base_reg,
+                "pass stack args base",
+                None,
+            ));
+        }
+
+        // Put the rest of the args into the args registers.
+        for (idx, arg_val) in args.iter().take(num_reg_args).enumerate() {
+            let arg_reg = self.value_to_register(arg_val);
+            self.cur_bytecode.push(Op::register_move(
+                VirtualRegister::Constant(ConstantRegister::ARG_REGS[idx]),
+                arg_reg,
+                format!("pass arg {idx}"),
+                self.md_mgr.val_to_span(self.context, *arg_val),
+            ));
         }
 
         // Set a new return address.
@@ -89,6 +138,17 @@ impl<'ir> AsmBuilder<'ir> {
         });
         self.cur_bytecode.push(Op::unowned_jump_label(ret_label));
 
+        // Free the stack args.
+        if stack_args_size != 0 {
+            self.cur_bytecode.push(Op {
+                opcode: Either::Left(VirtualOp::CFSI(VirtualImmediate24 {
+                    value: stack_args_size as u32,
+                })),
+                comment: format!("free {} bytes for stack args", stack_args_size),
+                owning_span: None,
+            });
+        }
+
         // Save the return value.
         let ret_reg = self.reg_seqr.next();
         self.cur_bytecode.push(Op {
@@ -100,6 +160,7 @@ impl<'ir> AsmBuilder<'ir> {
             owning_span: None,
         });
         self.reg_map.insert(*instr_val, ret_reg);
+        ok((), Vec::new(), Vec::new())
     }
 
     pub(super) fn compile_ret_from_call(&mut self, instr_val: &Value, ret_val: &Value) {
@@ -153,6 +214,8 @@ impl<'ir> AsmBuilder<'ir> {
             (Some(span), Some(decl_index)) => Some(DeclarationId::new(*decl_index, span.clone())),
             _ => None,
         };
+        let is_hot = self.md_mgr.md_is_hot(self.context, md);
+        let is_fallback = self.md_mgr.md_is_fallback(self.context, md);
         let comment = format!(
             "--- start of function: {} ---",
             function.get_name(self.context)
@@ -173,7 +236,7 @@ impl<'ir> AsmBuilder<'ir> {
         }
 
         if func_is_entry {
-            self.compile_external_args(function)
+            self.compile_external_args(function, test_decl_id.is_some())
         } else {
             // Make copies of the arg registers.
             self.compile_fn_call_args(function)
@@ -255,8 +318,14 @@ impl<'ir> AsmBuilder<'ir> {
         let mut ops = Vec::new();
         ops.append(&mut self.cur_bytecode);
         if func_is_entry {
-            self.entries
-                .push((function, start_label, ops, test_decl_id));
+            self.entries.push(AsmBuilderEntry {
+                function,
+                label: start_label,
+                ops: AbstractInstructionSet { ops },
+                test_decl_id,
+                is_hot,
+                is_fallback,
+            });
         } else {
             self.non_entries.push(ops);
         }
@@ -266,30 +335,81 @@ impl<'ir> AsmBuilder<'ir> {
 
     fn compile_fn_call_args(&mut self, function: Function) {
         // The first n args are passed in registers, but the rest arrive on the stack.
+        let num_reg_args = num_reg_args(function.args_iter(self.context).count());
+        let stack_args_base_reg = self.reg_seqr.next();
         for (idx, (_, arg_val)) in function.args_iter(self.context).enumerate() {
-            if idx < compiler_constants::NUM_ARG_REGISTERS as usize {
-                // Make a copy of the args in case we make calls and need to use the arg registers.
-                let arg_copy_reg = self.reg_seqr.next();
+            // Make a copy of the args in case we make calls and need to use the arg registers.
+            let arg_copy_reg = self.reg_seqr.next();
+            if idx < num_reg_args {
                 self.cur_bytecode.push(Op::register_move(
                     arg_copy_reg.clone(),
                     VirtualRegister::Constant(ConstantRegister::ARG_REGS[idx]),
                     format!("save arg {idx}"),
                     self.md_mgr.val_to_span(self.context, *arg_val),
                 ));
-
-                // Remember our arg copy.
-                self.reg_map.insert(*arg_val, arg_copy_reg);
             } else {
-                todo!(
-                    "can't do more than {} args yet",
-                    compiler_constants::NUM_ARG_REGISTERS
-                );
+                if idx == num_reg_args {
+                    self.cur_bytecode.push(Op::register_move(
+                        stack_args_base_reg.clone(),
+                        VirtualRegister::Constant(ConstantRegister::ARG_REGS[idx]),
+                        "save stack args base",
+                        None,
+                    ));
+                }
+                let (base_reg, offset) =
+                    self.stack_arg_address(&stack_args_base_reg, (idx - num_reg_args) as u64);
+                self.cur_bytecode.push(Op {
+                    opcode: Either::Left(VirtualOp::LW(arg_copy_reg.clone(), base_reg, offset)),
+                    comment: format!("load arg {idx} from the stack"),
+                    owning_span: self.md_mgr.val_to_span(self.context, *arg_val),
+                });
             }
+
+            // Remember our arg copy.
+            self.reg_map.insert(*arg_val, arg_copy_reg);
+        }
+    }
+
+    // Get the base register and immediate with which to address the stack arg at `word_offset`
+    // from `stack_args_base_reg`.  If the offset doesn't fit in a 12-bit immediate then the
+    // address of the arg is computed in a new register instead.
+    fn stack_arg_address(
+        &mut self,
+        stack_args_base_reg: &VirtualRegister,
+        word_offset: u64,
+    ) -> (VirtualRegister, VirtualImmediate12) {
+        if word_offset > compiler_constants::TWELVE_BITS {
+            let offs_reg = self.reg_seqr.next();
+            self.number_to_reg(word_offset * 8, &offs_reg, None);
+            self.cur_bytecode.push(Op {
+                opcode: Either::Left(VirtualOp::ADD(
+                    offs_reg.clone(),
+                    stack_args_base_reg.clone(),
+                    offs_reg.clone(),
+                )),
+                comment: "get address of stack arg".into(),
+                owning_span: None,
+            });
+            (offs_reg, VirtualImmediate12 { value: 0 })
+        } else {
+            (
+                stack_args_base_reg.clone(),
+                VirtualImmediate12 {
+                    value: word_offset as u16,
+                },
+            )
         }
     }
 
-    // Handle loading the arguments of a contract call
-    fn compile_external_args(&mut self, function: Function) {
+    // Handle loading the arguments of a contract call, or of a script or predicate's main function.
+    //
+    // Unit tests are always executed as scripts, so the arguments of test entries are read from
+    // the script data regardless of the kind of program.
+    fn compile_external_args(&mut self, function: Function, is_test: bool) {
+        let program_kind = match is_test {
+            true => ProgramKind::Script,
+            false => self.program_kind,
+        };
         match function.args_iter(self.context).count() {
             // Nothing to do if there are no arguments
             0 => (),
@@ -299,11 +419,11 @@ impl<'ir> AsmBuilder<'ir> {
             1 => {
                 let (_, val) = function.args_iter(self.context).next().unwrap();
                 let single_arg_reg = self.value_to_register(val);
-                match self.program_kind {
+                match program_kind {
                     ProgramKind::Contract => self.read_args_base_from_frame(&single_arg_reg),
                     ProgramKind::Library => (), // Nothing to do here
                     ProgramKind::Script | ProgramKind::Predicate => {
-                        if let ProgramKind::Predicate = self.program_kind {
+                        if let ProgramKind::Predicate = program_kind {
                             self.read_args_base_from_predicate_data(&single_arg_reg);
                         } else {
                             self.read_args_base_from_script_data(&single_arg_reg);
@@ -328,7 +448,7 @@ impl<'ir> AsmBuilder<'ir> {
             // Otherwise, the args are bundled together and pointed to by the base register.
             _ => {
                 let args_base_reg = self.reg_seqr.next();
-                match self.program_kind {
+                match program_kind {
                     ProgramKind::Contract => self.read_args_base_from_frame(&args_base_reg),
                     ProgramKind::Library => return, // Nothing to do here
                     ProgramKind::Predicate => {
@@ -676,3 +796,14 @@ impl<'ir> AsmBuilder<'ir> {
         &self.locals_ctxs.last().expect("No locals").1
     }
 }
+
+/// The number of args which are passed in registers, out of `num_args`.  If there are more args
+/// than args registers then the last args register is reserved for the address of the surplus.
+fn num_reg_args(num_args: usize) -> usize {
+    let num_arg_registers = compiler_constants::NUM_ARG_REGISTERS as usize;
+    if num_args > num_arg_registers {
+        num_arg_registers - 1
+    } else {
+        num_args
+    }
+}
@@ -119,15 +119,17 @@ fn compile_module_to_asm(
     let (data_section, reg_seqr, entries, non_entries) = builder.finalize();
     let entries = entries
         .into_iter()
-        .map(|(func, label, ops, test_decl_id)| {
-            let selector = func.get_selector(context);
-            let name = func.get_name(context).to_string();
+        .map(|entry| {
+            let selector = entry.function.get_selector(context);
+            let name = entry.function.get_name(context).to_string();
             AbstractEntry {
-                test_decl_id,
+                test_decl_id: entry.test_decl_id,
                 selector,
-                label,
-                ops,
+                label: entry.label,
+                ops: entry.ops,
                 name,
+                is_hot: entry.is_hot,
+                is_fallback: entry.is_fallback,
             }
         })
         .collect();
@@ -41,6 +41,10 @@ pub(super) struct AbstractEntry {
     pub(super) ops: AbstractInstructionSet,
     pub(super) name: FnName,
     pub(super) test_decl_id: Option<DeclarationId>,
+    /// Whether this is a contract method to be checked first by the selector switch.
+    pub(super) is_hot: bool,
+    /// Whether this is the contract function to be called when no selector matches.
+    pub(super) is_fallback: bool,
 }
 
 /// An AllocatedProgram represents code which has allocated registers but still has abstract
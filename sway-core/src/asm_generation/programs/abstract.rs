@@ -7,7 +7,7 @@ use crate::{
     },
     asm_lang::{
         allocated_ops::{AllocatedOpcode, AllocatedRegister},
-        AllocatedAbstractOp, ConstantRegister, ControlFlowOp, VirtualImmediate12,
+        AllocatedAbstractOp, ConstantRegister, ControlFlowOp, Label, VirtualImmediate12,
         VirtualImmediate18,
     },
 };
@@ -16,6 +16,11 @@ use sway_error::error::CompileError;
 
 use either::Either;
 
+// Registers used by the contract ABI selector switch, which runs before any others are allocated.
+const INPUT_SELECTOR_REG: AllocatedRegister = AllocatedRegister::Allocated(0);
+const PROG_SELECTOR_REG: AllocatedRegister = AllocatedRegister::Allocated(1);
+const CMP_RESULT_REG: AllocatedRegister = AllocatedRegister::Allocated(2);
+
 impl AbstractProgram {
     pub(crate) fn new(
         kind: ProgramKind,
@@ -69,9 +74,12 @@ impl AbstractProgram {
         // Allocate the registers for each function.
         let functions = abstract_functions
             .into_iter()
-            .map(|fn_ops| fn_ops.allocate_registers(&mut self.reg_seqr))
-            .map(AllocatedAbstractInstructionSet::emit_pusha_popa)
-            .collect::<Vec<_>>();
+            .map(|fn_ops| {
+                fn_ops
+                    .allocate_registers(&mut self.reg_seqr)
+                    .map(AllocatedAbstractInstructionSet::emit_pusha_popa)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
         // XXX need to verify that the stack use for each function is balanced.
 
@@ -147,11 +155,15 @@ impl AbstractProgram {
     /// 'selector'.
     /// See https://fuellabs.github.io/fuel-specs/master/vm#call-frames which
     /// describes the first argument to be at word offset 73.
+    ///
+    /// The selectors of methods marked `#[hot]` are compared first, in declaration order.  The
+    /// remaining selectors are sorted and binary searched, so that dispatching to any method
+    /// costs a number of instructions logarithmic in the size of the ABI rather than linear.
+    ///
+    /// If no selector matches then the contract's `#[fallback]` function is jumped to, or if it
+    /// has none the call reverts.
     fn build_contract_abi_switch(&mut self, asm_buf: &mut AllocatedAbstractInstructionSet) {
         const SELECTOR_WORD_OFFSET: u64 = 73;
-        const INPUT_SELECTOR_REG: AllocatedRegister = AllocatedRegister::Allocated(0);
-        const PROG_SELECTOR_REG: AllocatedRegister = AllocatedRegister::Allocated(1);
-        const CMP_RESULT_REG: AllocatedRegister = AllocatedRegister::Allocated(2);
 
         // Build the switch statement for selectors.
         asm_buf.ops.push(AllocatedAbstractOp {
@@ -174,48 +186,52 @@ impl AbstractProgram {
             owning_span: None,
         });
 
-        // Add a 'case' for each entry with a selector.
-        for entry in &self.entries {
-            let selector = match entry.selector {
-                Some(sel) => sel,
-                // Skip entries that don't have a selector - they're probably tests.
-                None => continue,
-            };
-
-            // Put the selector in the data section.
-            let data_label = self
-                .data_section
-                .insert_data_value(Entry::new_word(u32::from_be_bytes(selector) as u64, None));
-
-            // Load the data into a register for comparison.
-            asm_buf.ops.push(AllocatedAbstractOp {
-                opcode: Either::Left(AllocatedOpcode::LWDataId(PROG_SELECTOR_REG, data_label)),
-                comment: "load fn selector for comparison".into(),
-                owning_span: None,
-            });
+        // Gather the entries with a selector, skipping those without - they're probably tests.
+        let (hot_cases, mut cases): (Vec<_>, Vec<_>) = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                entry.selector.map(|selector| {
+                    (
+                        u32::from_be_bytes(selector) as u64,
+                        entry.label,
+                        entry.is_hot,
+                    )
+                })
+            })
+            .partition(|(_, _, is_hot)| *is_hot);
 
-            // Compare with the input selector.
-            asm_buf.ops.push(AllocatedAbstractOp {
-                opcode: Either::Left(AllocatedOpcode::EQ(
-                    CMP_RESULT_REG,
-                    INPUT_SELECTOR_REG,
-                    PROG_SELECTOR_REG,
-                )),
-                comment: "function selector comparison".into(),
-                owning_span: None,
-            });
+        // Add a 'case' for each hot entry first.
+        for (selector, label, _) in hot_cases {
+            self.build_selector_case(asm_buf, selector, label);
+        }
+
+        // Then search the rest.
+        cases.sort_unstable_by_key(|(selector, _, _)| *selector);
+        let cases = cases
+            .into_iter()
+            .map(|(selector, label, _)| (selector, label))
+            .collect::<Vec<_>>();
+        let mismatch_label = self.reg_seqr.get_label();
+        self.build_selector_search(asm_buf, &cases, mismatch_label, true);
+
+        asm_buf.ops.push(AllocatedAbstractOp {
+            opcode: Either::Right(ControlFlowOp::Label(mismatch_label)),
+            comment: "no selectors matched".into(),
+            owning_span: None,
+        });
 
-            // Jump to the function label if the selector was equal.
+        // If none of the selectors matched, then call the fallback function if there is one.
+        if let Some(fallback_entry) = self.entries.iter().find(|entry| entry.is_fallback) {
             asm_buf.ops.push(AllocatedAbstractOp {
-                // If the comparison result is _not_ equal to 0, then it was indeed equal.
-                opcode: Either::Right(ControlFlowOp::JumpIfNotZero(CMP_RESULT_REG, entry.label)),
-                comment: "jump to selected function".into(),
+                opcode: Either::Right(ControlFlowOp::Jump(fallback_entry.label)),
+                comment: "jump to fallback function".into(),
                 owning_span: None,
             });
+            return;
         }
 
-        // If none of the selectors matched, then revert.  This may change in the future, see
-        // https://github.com/FuelLabs/sway/issues/444
+        // Otherwise revert.
         asm_buf.ops.push(AllocatedAbstractOp {
             opcode: Either::Left(AllocatedOpcode::MOVI(
                 AllocatedRegister::Constant(ConstantRegister::Scratch),
@@ -234,6 +250,112 @@ impl AbstractProgram {
             owning_span: None,
         });
     }
+
+    /// Builds a binary search for the input selector over `cases`, which are sorted by selector,
+    /// jumping to the label of the matching case or to `mismatch_label` if there is none.  If the
+    /// search `falls_through` then `mismatch_label` directly follows it and needn't be jumped to.
+    ///
+    /// Once few enough cases remain they are compared one by one, as splitting them further
+    /// wouldn't reduce the number of instructions executed.
+    fn build_selector_search(
+        &mut self,
+        asm_buf: &mut AllocatedAbstractInstructionSet,
+        cases: &[(u64, Label)],
+        mismatch_label: Label,
+        falls_through: bool,
+    ) {
+        const LINEAR_SEARCH_MAX_CASES: usize = 3;
+
+        if cases.len() <= LINEAR_SEARCH_MAX_CASES {
+            for (selector, label) in cases {
+                self.build_selector_case(asm_buf, *selector, *label);
+            }
+            if !falls_through {
+                asm_buf.ops.push(AllocatedAbstractOp {
+                    opcode: Either::Right(ControlFlowOp::Jump(mismatch_label)),
+                    comment: "no selectors matched".into(),
+                    owning_span: None,
+                });
+            }
+            return;
+        }
+
+        // Split the cases at the median selector; the lower half is searched if the input selector
+        // is less than it, otherwise the upper half is.
+        let (lower_cases, upper_cases) = cases.split_at(cases.len() / 2);
+        let lower_label = self.reg_seqr.get_label();
+
+        let data_label = self
+            .data_section
+            .insert_data_value(Entry::new_word(upper_cases[0].0, None));
+        asm_buf.ops.push(AllocatedAbstractOp {
+            opcode: Either::Left(AllocatedOpcode::LWDataId(PROG_SELECTOR_REG, data_label)),
+            comment: "load median fn selector for comparison".into(),
+            owning_span: None,
+        });
+        asm_buf.ops.push(AllocatedAbstractOp {
+            opcode: Either::Left(AllocatedOpcode::LT(
+                CMP_RESULT_REG,
+                INPUT_SELECTOR_REG,
+                PROG_SELECTOR_REG,
+            )),
+            comment: "function selector comparison".into(),
+            owning_span: None,
+        });
+        asm_buf.ops.push(AllocatedAbstractOp {
+            opcode: Either::Right(ControlFlowOp::JumpIfNotZero(CMP_RESULT_REG, lower_label)),
+            comment: "search lower fn selectors".into(),
+            owning_span: None,
+        });
+
+        self.build_selector_search(asm_buf, upper_cases, mismatch_label, false);
+
+        asm_buf.ops.push(AllocatedAbstractOp {
+            opcode: Either::Right(ControlFlowOp::Label(lower_label)),
+            comment: "lower fn selectors".into(),
+            owning_span: None,
+        });
+        self.build_selector_search(asm_buf, lower_cases, mismatch_label, falls_through);
+    }
+
+    /// Builds a comparison of the input selector with `selector`, jumping to `label` if equal.
+    fn build_selector_case(
+        &mut self,
+        asm_buf: &mut AllocatedAbstractInstructionSet,
+        selector: u64,
+        label: Label,
+    ) {
+        // Put the selector in the data section.
+        let data_label = self
+            .data_section
+            .insert_data_value(Entry::new_word(selector, None));
+
+        // Load the data into a register for comparison.
+        asm_buf.ops.push(AllocatedAbstractOp {
+            opcode: Either::Left(AllocatedOpcode::LWDataId(PROG_SELECTOR_REG, data_label)),
+            comment: "load fn selector for comparison".into(),
+            owning_span: None,
+        });
+
+        // Compare with the input selector.
+        asm_buf.ops.push(AllocatedAbstractOp {
+            opcode: Either::Left(AllocatedOpcode::EQ(
+                CMP_RESULT_REG,
+                INPUT_SELECTOR_REG,
+                PROG_SELECTOR_REG,
+            )),
+            comment: "function selector comparison".into(),
+            owning_span: None,
+        });
+
+        // Jump to the function label if the selector was equal.
+        asm_buf.ops.push(AllocatedAbstractOp {
+            // If the comparison result is _not_ equal to 0, then it was indeed equal.
+            opcode: Either::Right(ControlFlowOp::JumpIfNotZero(CMP_RESULT_REG, label)),
+            comment: "jump to selected function".into(),
+            owning_span: None,
+        });
+    }
 }
 
 impl std::fmt::Display for AbstractProgram {
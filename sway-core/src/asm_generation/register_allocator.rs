@@ -1,11 +1,12 @@
 use crate::{
     asm_generation::{
-        register_sequencer::RegisterSequencer, RegisterAllocationStatus, RegisterPool,
+        compiler_constants, register_sequencer::RegisterSequencer, RegisterAllocationStatus,
+        RegisterPool,
     },
-    asm_lang::{virtual_register::*, Op, VirtualOp},
+    asm_lang::{virtual_register::*, Op, OrganizationalOp, VirtualImmediate12, VirtualOp},
 };
 
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use either::Either;
 use petgraph::graph::NodeIndex;
@@ -334,23 +335,61 @@ pub(crate) fn coalesce_registers(
 /// 1. Pick any node n such that degree(n) < k and put it on the stack along with its neighbors.
 /// 2. Remove node n and all its edges from the graph
 ///    - This may make some new nodes have fewer than k neighbours which is nice.
-/// 3. If some vertex n still has k or more neighbors, then the graph is not k colorable, and we
-///    have to spill. For now, we will just error out, but we may be able to do better in the
-///    future.
+/// 3. If some vertex n still has k or more neighbors, then the graph may not be k colorable, and
+///    we have to spill. The spill candidate with the lowest spill cost per neighbor is removed
+///    from the graph, such that it is stored on the stack rather than assigned a register.
 /// ===============================================================================================
 ///
-/// As we don't implement spilling just yet, I've modified the algorithm above to assume k=infinity
-/// and moving the colorability checking until the register assignment phase. The reason for this
-/// is that the algorithm above can be too conservative and may bail our early even though a valid
-/// assignment is actually available. We can revisit this decision when decide to implement
-/// spilling.
+/// The allocator initially calls this with k=infinity, moving the colorability checking until the
+/// register assignment phase. The reason for this is that the algorithm above can be too
+/// conservative and may spill even though a valid assignment is actually available. Only in the
+/// case that the assignment fails is k set to the number of allocatable registers.
+///
+/// Registers without a spill cost are never spilled. In the case that no node can be spilled,
+/// the node is optimistically pushed onto the stack regardless.
+///
+/// Returns the stack in the case that no registers must be spilled, otherwise the registers to
+/// spill.
 ///
 pub(crate) fn color_interference_graph(
     interference_graph: &mut InterferenceGraph,
-) -> Vec<(VirtualRegister, BTreeSet<VirtualRegister>)> {
+    num_registers: usize,
+    spill_costs: &HashMap<VirtualRegister, usize>,
+) -> Result<Vec<(VirtualRegister, BTreeSet<VirtualRegister>)>, BTreeSet<VirtualRegister>> {
     let mut stack: Vec<(VirtualRegister, BTreeSet<VirtualRegister>)> = vec![];
-
-    while let Some(node) = interference_graph.node_indices().next() {
+    let mut spills = BTreeSet::new();
+
+    while let Some(first_node) = interference_graph.node_indices().next() {
+        let degree = |node| interference_graph.neighbors(node).count();
+        let node = match interference_graph
+            .node_indices()
+            .find(|node| degree(*node) < num_registers)
+        {
+            Some(node) => node,
+            None => {
+                // Compare the cost per neighbor of each candidate, i.e. `cost / degree`.
+                let spill_candidate = interference_graph
+                    .node_indices()
+                    .filter_map(|node| {
+                        let cost = spill_costs.get(&interference_graph[node])?;
+                        Some((node, *cost, degree(node)))
+                    })
+                    .min_by(|(_, cost_a, degree_a), (_, cost_b, degree_b)| {
+                        (cost_a * degree_b).cmp(&(cost_b * degree_a))
+                    });
+                match spill_candidate {
+                    Some((node, _, _)) => {
+                        spills.insert(
+                            interference_graph
+                                .remove_node(node)
+                                .expect("Node must exist"),
+                        );
+                        continue;
+                    }
+                    None => first_node,
+                }
+            }
+        };
         let neighbors = interference_graph
             .neighbors(node)
             .map(|n| interference_graph[n].clone())
@@ -363,7 +402,11 @@ pub(crate) fn color_interference_graph(
         ));
     }
 
-    stack
+    if spills.is_empty() {
+        Ok(stack)
+    } else {
+        Err(spills)
+    }
 }
 
 /// Use the stack generated by the coloring algorithm to figure out a register assignment for each
@@ -372,9 +415,11 @@ pub(crate) fn color_interference_graph(
 /// the neighbors of v (available from the stack) and the list of virtual registers already used by
 /// r (available in the used_by field) is empty.
 ///
+/// Returns the first virtual register for which no register is available, if any.
+///
 pub(crate) fn assign_registers(
     stack: &mut Vec<(VirtualRegister, BTreeSet<VirtualRegister>)>,
-) -> RegisterPool {
+) -> Result<RegisterPool, VirtualRegister> {
     let mut pool = RegisterPool::init();
     while let Some((reg, neighbors)) = stack.pop() {
         if matches!(reg, VirtualRegister::Virtual(_)) {
@@ -388,15 +433,506 @@ pub(crate) fn assign_registers(
             if let Some(RegisterAllocationStatus { reg: _, used_by }) = available {
                 used_by.insert(reg.clone());
             } else {
-                // Error out for now if no available register is found
-                unimplemented!(
-                    "The allocator cannot resolve a register mapping for this program. \
-                     This is a temporary artifact of the extremely early stage version \
-                     of this language. Try to lower the number of variables you use."
+                return Err(reg);
+            }
+        }
+    }
+
+    Ok(pool)
+}
+
+/// Given a virtual register `reg` for which `assign_registers()` found no register, pick the
+/// register to spill before the interference graph is colored again. This is the cheapest to
+/// spill of `reg` and the registers that interfere with it.
+///
+/// Returns `None` if none of these registers may be spilled.
+///
+pub(crate) fn spill_candidate(
+    reg: &VirtualRegister,
+    interference_graph: &InterferenceGraph,
+    reg_to_node_map: &HashMap<VirtualRegister, NodeIndex>,
+    spill_costs: &HashMap<VirtualRegister, usize>,
+) -> Option<VirtualRegister> {
+    let node = *reg_to_node_map.get(reg)?;
+    std::iter::once(node)
+        .chain(interference_graph.neighbors(node))
+        .filter_map(|node| {
+            let reg = &interference_graph[node];
+            spill_costs.get(reg).map(|cost| (reg, *cost))
+        })
+        .min_by_key(|(_, cost)| *cost)
+        .map(|(reg, _)| reg.clone())
+}
+
+/// The virtual registers of a function which have been spilled to the stack.
+///
+/// Spilled registers are stored in slots within a region of the stack which is reserved upon
+/// entry to the function, addressed relative to the `base` register.
+#[derive(Default)]
+pub(crate) struct SpillSlots {
+    /// The register holding the address of the first slot, once any register has been spilled.
+    pub(crate) base: Option<VirtualRegister>,
+    /// The slot of each spilled register, in words.
+    slots: HashMap<VirtualRegister, u64>,
+    /// The short lived registers which load and store the spilled values around each of their
+    /// uses and definitions. These are never spilled themselves.
+    temps: HashSet<VirtualRegister>,
+}
+
+impl SpillSlots {
+    /// The size of the stack region reserved for the slots.
+    pub(crate) fn size_in_bytes(&self) -> u64 {
+        self.slots.len() as u64 * 8
+    }
+}
+
+/// Compute the cost of spilling each virtual register that may be spilled, as the number of times
+/// that it is used or defined.
+///
+pub(crate) fn spill_costs(ops: &[Op], spill_slots: &SpillSlots) -> HashMap<VirtualRegister, usize> {
+    let mut costs: HashMap<VirtualRegister, usize> = HashMap::new();
+    for op in ops {
+        for reg in op.use_registers().into_iter().chain(op.def_registers()) {
+            if matches!(reg, VirtualRegister::Virtual(_))
+                && spill_slots.base.as_ref() != Some(reg)
+                && !spill_slots.temps.contains(reg)
+            {
+                *costs.entry(reg.clone()).or_default() += 1;
+            }
+        }
+    }
+    costs
+}
+
+/// Given a list of instructions `ops` and a set of virtual registers `spills`, generate a new list
+/// of instructions where each of the spilled registers is stored in a stack slot rather than a
+/// register.
+///
+/// Each use of a spilled register is preceded by a load of its slot into a new virtual register,
+/// and each definition is followed by a store of a new virtual register into its slot. Both new
+/// registers are only live for the single instruction, making the interference graph easier to
+/// color.
+///
+/// The first time that a register is spilled, the base address of the slots is saved right after
+/// the function's label and the saving of its registers, i.e. prior to any other instruction.
+///
+pub(crate) fn spill_registers(
+    ops: &[Op],
+    spills: &BTreeSet<VirtualRegister>,
+    spill_slots: &mut SpillSlots,
+    register_sequencer: &mut RegisterSequencer,
+) -> Vec<Op> {
+    let mut spilled_ops = Vec::with_capacity(ops.len());
+    let base = match &spill_slots.base {
+        Some(base) => base.clone(),
+        None => {
+            let base = register_sequencer.next();
+            spill_slots.base = Some(base.clone());
+            base
+        }
+    };
+    for reg in spills {
+        let slot = spill_slots.slots.len() as u64;
+        spill_slots.slots.insert(reg.clone(), slot);
+    }
+    for op in ops {
+        let op_spills: BTreeSet<VirtualRegister> = op
+            .registers()
+            .into_iter()
+            .filter(|reg| spills.contains(reg))
+            .cloned()
+            .collect();
+        if op_spills.is_empty() {
+            spilled_ops.push(op.clone());
+            continue;
+        }
+
+        let temps: HashMap<VirtualRegister, VirtualRegister> = op_spills
+            .iter()
+            .map(|reg| (reg.clone(), register_sequencer.next()))
+            .collect();
+        spill_slots.temps.extend(temps.values().cloned());
+        for reg in op.use_registers() {
+            if let Some(temp) = temps.get(reg) {
+                let (slot_base, slot_imm) = spill_slot_address(
+                    &base,
+                    spill_slots.slots[reg],
+                    &mut spilled_ops,
+                    spill_slots,
+                    register_sequencer,
+                );
+                spilled_ops.push(Op {
+                    opcode: Either::Left(VirtualOp::LW(temp.clone(), slot_base, slot_imm)),
+                    comment: format!("load spilled {}", reg),
+                    owning_span: op.owning_span.clone(),
+                });
+            }
+        }
+        spilled_ops.push(op.update_register(&temps));
+        for reg in op.def_registers() {
+            if let Some(temp) = temps.get(reg) {
+                let (slot_base, slot_imm) = spill_slot_address(
+                    &base,
+                    spill_slots.slots[reg],
+                    &mut spilled_ops,
+                    spill_slots,
+                    register_sequencer,
                 );
+                spilled_ops.push(Op {
+                    opcode: Either::Left(VirtualOp::SW(slot_base, temp.clone(), slot_imm)),
+                    comment: format!("spill {}", reg),
+                    owning_span: op.owning_span.clone(),
+                });
             }
         }
     }
 
-    pool
+    if !ops.iter().any(|op| op.def_registers().contains(&base)) {
+        spilled_ops.insert(
+            spill_base_index(&spilled_ops),
+            Op::register_move(
+                base,
+                VirtualRegister::Constant(ConstantRegister::StackPointer),
+                "save spill slots base register",
+                None,
+            ),
+        );
+    }
+
+    spilled_ops
+}
+
+/// Get the base register and immediate with which to address the spill slot at `slot` from
+/// `base`. If the offset doesn't fit in a 12-bit immediate then the address of the slot is
+/// computed in a new register instead, by appending the instructions to `ops`. That register is
+/// only live until the slot is accessed and is never spilled itself.
+///
+fn spill_slot_address(
+    base: &VirtualRegister,
+    slot: u64,
+    ops: &mut Vec<Op>,
+    spill_slots: &mut SpillSlots,
+    register_sequencer: &mut RegisterSequencer,
+) -> (VirtualRegister, VirtualImmediate12) {
+    if slot <= compiler_constants::TWELVE_BITS {
+        return (base.clone(), VirtualImmediate12 { value: slot as u16 });
+    }
+
+    // Use bitwise ORs and SHIFTs to create the 24 bit offset of the slot in a register. The
+    // spilled registers never exceed 24 bits worth of stack.
+    let offset = slot * 8;
+    let addr_reg = register_sequencer.next();
+    spill_slots.temps.insert(addr_reg.clone());
+    ops.push(Op {
+        opcode: Either::Left(VirtualOp::ORI(
+            addr_reg.clone(),
+            VirtualRegister::Constant(ConstantRegister::Zero),
+            VirtualImmediate12 {
+                value: (offset >> 12) as u16,
+            },
+        )),
+        comment: "get spill slot offset high bits".into(),
+        owning_span: None,
+    });
+    ops.push(Op {
+        opcode: Either::Left(VirtualOp::SLLI(
+            addr_reg.clone(),
+            addr_reg.clone(),
+            VirtualImmediate12 { value: 12 },
+        )),
+        comment: "shift spill slot offset high bits".into(),
+        owning_span: None,
+    });
+    ops.push(Op {
+        opcode: Either::Left(VirtualOp::ORI(
+            addr_reg.clone(),
+            addr_reg.clone(),
+            VirtualImmediate12 {
+                value: (offset & 0xfff) as u16,
+            },
+        )),
+        comment: "get spill slot offset low bits".into(),
+        owning_span: None,
+    });
+    ops.push(Op {
+        opcode: Either::Left(VirtualOp::ADD(
+            addr_reg.clone(),
+            base.clone(),
+            addr_reg.clone(),
+        )),
+        comment: "get address of spill slot".into(),
+        owning_span: None,
+    });
+    (addr_reg, VirtualImmediate12 { value: 0 })
+}
+
+/// The index of the first instruction of a function after its label and the saving of its
+/// registers, at which the base address of its spill slots is saved.
+///
+pub(crate) fn spill_base_index(ops: &[Op]) -> usize {
+    let position = |f: fn(&OrganizationalOp) -> bool| {
+        ops.iter()
+            .position(|op| matches!(&op.opcode, Either::Right(org_op) if f(org_op)))
+    };
+    position(|op| matches!(op, OrganizationalOp::PushAll(_)))
+        .or_else(|| position(|op| matches!(op, OrganizationalOp::Label(_))))
+        .map(|index| index + 1)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm_lang::VirtualImmediate18;
+
+    fn op(opcode: VirtualOp) -> Op {
+        Op {
+            opcode: Either::Left(opcode),
+            comment: String::new(),
+            owning_span: None,
+        }
+    }
+
+    /// Four values which are all live at once, followed by their sum.
+    fn four_live_values(register_sequencer: &mut RegisterSequencer) -> Vec<Op> {
+        let regs: Vec<_> = (0..7).map(|_| register_sequencer.next()).collect();
+        let (a, b, c, d, e, f, g) = (
+            &regs[0], &regs[1], &regs[2], &regs[3], &regs[4], &regs[5], &regs[6],
+        );
+        let movi = |reg: &VirtualRegister, value| {
+            op(VirtualOp::MOVI(reg.clone(), VirtualImmediate18 { value }))
+        };
+        vec![
+            movi(a, 1),
+            movi(b, 2),
+            movi(c, 3),
+            movi(d, 4),
+            op(VirtualOp::ADD(e.clone(), a.clone(), b.clone())),
+            op(VirtualOp::ADD(f.clone(), c.clone(), d.clone())),
+            op(VirtualOp::ADD(g.clone(), e.clone(), f.clone())),
+            op(VirtualOp::ADD(g.clone(), g.clone(), a.clone())),
+        ]
+    }
+
+    /// Run the allocator's coloring and spilling loop with only `num_registers` registers.
+    fn color_with_spills(
+        mut ops: Vec<Op>,
+        num_registers: usize,
+        spill_slots: &mut SpillSlots,
+        register_sequencer: &mut RegisterSequencer,
+    ) -> (Vec<Op>, RegisterPool) {
+        for _ in 0..16 {
+            let live_out = liveness_analysis(&ops);
+            let (mut interference_graph, mut reg_to_node_ix) =
+                create_interference_graph(&ops, &live_out);
+            let reduced_ops = coalesce_registers(
+                &ops,
+                &mut interference_graph,
+                &mut reg_to_node_ix,
+                register_sequencer,
+            );
+            let spill_costs = spill_costs(&reduced_ops, spill_slots);
+            match color_interference_graph(&mut interference_graph, num_registers, &spill_costs) {
+                Ok(mut stack) => {
+                    let pool = assign_registers(&mut stack).expect("registers must be assigned");
+                    return (reduced_ops, pool);
+                }
+                Err(spills) => {
+                    ops = spill_registers(&reduced_ops, &spills, spill_slots, register_sequencer);
+                }
+            }
+        }
+        panic!("spilling must make the interference graph colorable");
+    }
+
+    #[test]
+    fn spill_costs_count_uses_and_defs() {
+        let mut register_sequencer = RegisterSequencer::new();
+        let ops = four_live_values(&mut register_sequencer);
+        let regs: Vec<_> = (0..7)
+            .map(|i| VirtualRegister::Virtual(i.to_string()))
+            .collect();
+
+        let costs = spill_costs(&ops, &SpillSlots::default());
+        let expected = [3, 2, 2, 2, 2, 2, 3];
+        for (reg, cost) in regs.iter().zip(expected) {
+            assert_eq!(costs.get(reg), Some(&cost), "cost of {reg}");
+        }
+    }
+
+    #[test]
+    fn spill_costs_exclude_spill_registers() {
+        let mut register_sequencer = RegisterSequencer::new();
+        let ops = four_live_values(&mut register_sequencer);
+        let a = VirtualRegister::Virtual("0".into());
+
+        let mut spill_slots = SpillSlots::default();
+        let ops = spill_registers(
+            &ops,
+            &BTreeSet::from([a.clone()]),
+            &mut spill_slots,
+            &mut register_sequencer,
+        );
+        let costs = spill_costs(&ops, &spill_slots);
+
+        assert!(!costs.contains_key(&a));
+        assert!(!costs.contains_key(spill_slots.base.as_ref().unwrap()));
+        assert!(spill_slots
+            .temps
+            .iter()
+            .all(|temp| !costs.contains_key(temp)));
+        assert_eq!(costs.len(), 6);
+    }
+
+    #[test]
+    fn spill_registers_loads_and_stores_slots() {
+        let mut register_sequencer = RegisterSequencer::new();
+        let ops = four_live_values(&mut register_sequencer);
+        let a = VirtualRegister::Virtual("0".into());
+
+        let mut spill_slots = SpillSlots::default();
+        let ops = spill_registers(
+            &ops,
+            &BTreeSet::from([a.clone()]),
+            &mut spill_slots,
+            &mut register_sequencer,
+        );
+        let base = spill_slots.base.clone().unwrap();
+
+        // The base is saved first, `a` is stored after its definition and loaded before each of
+        // its two uses.
+        let stack_pointer = VirtualRegister::Constant(ConstantRegister::StackPointer);
+        assert!(matches!(
+            &ops[0].opcode,
+            Either::Left(VirtualOp::MOVE(reg, sp)) if *reg == base && *sp == stack_pointer
+        ));
+        let (mut stores, mut loads) = (0, 0);
+        for op in &ops {
+            match &op.opcode {
+                Either::Left(VirtualOp::SW(reg, _, imm)) if *reg == base && imm.value == 0 => {
+                    stores += 1
+                }
+                Either::Left(VirtualOp::LW(_, reg, imm)) if *reg == base && imm.value == 0 => {
+                    loads += 1
+                }
+                _ => (),
+            }
+        }
+        assert_eq!((stores, loads), (1, 2));
+        assert!(ops.iter().all(|op| !op.registers().contains(&&a)));
+        assert_eq!(spill_slots.size_in_bytes(), 8);
+    }
+
+    #[test]
+    fn spill_registers_address_slots_beyond_twelve_bits() {
+        let mut register_sequencer = RegisterSequencer::new();
+        let ops = four_live_values(&mut register_sequencer);
+        let a = VirtualRegister::Virtual("0".into());
+
+        // Fill the slots which can be addressed with a 12-bit immediate.
+        let mut spill_slots = SpillSlots::default();
+        for slot in 0..=compiler_constants::TWELVE_BITS {
+            spill_slots
+                .slots
+                .insert(VirtualRegister::Virtual(format!("filler{slot}")), slot);
+        }
+        let ops = spill_registers(
+            &ops,
+            &BTreeSet::from([a]),
+            &mut spill_slots,
+            &mut register_sequencer,
+        );
+        let base = spill_slots.base.clone().unwrap();
+
+        // Each access computes the byte offset 4096 * 8 = 0x8000 and adds it to the base.
+        let store_ix = ops
+            .iter()
+            .position(|op| matches!(&op.opcode, Either::Left(VirtualOp::SW(..))))
+            .unwrap();
+        let addr = match &ops[store_ix].opcode {
+            Either::Left(VirtualOp::SW(addr, _, imm)) => {
+                assert_eq!(imm.value, 0);
+                addr.clone()
+            }
+            _ => unreachable!(),
+        };
+        assert_ne!(addr, base);
+        assert!(spill_slots.temps.contains(&addr));
+        let opcodes: Vec<_> = ops[store_ix - 4..store_ix]
+            .iter()
+            .map(|op| &op.opcode)
+            .collect();
+        let zero = VirtualRegister::Constant(ConstantRegister::Zero);
+        assert!(matches!(
+            opcodes[..],
+            [
+                Either::Left(VirtualOp::ORI(_, r0, high)),
+                Either::Left(VirtualOp::SLLI(_, _, shift)),
+                Either::Left(VirtualOp::ORI(_, _, low)),
+                Either::Left(VirtualOp::ADD(_, b, _)),
+            ] if *r0 == zero && high.value == 8 && shift.value == 12 && low.value == 0 && *b == base
+        ));
+    }
+
+    #[test]
+    fn spilling_colors_graph_with_few_registers() {
+        let mut register_sequencer = RegisterSequencer::new();
+        let ops = four_live_values(&mut register_sequencer);
+
+        // Four values are live at once, so three registers aren't enough without spilling.
+        let live_out = liveness_analysis(&ops);
+        let (mut interference_graph, _) = create_interference_graph(&ops, &live_out);
+        let costs = spill_costs(&ops, &SpillSlots::default());
+        assert!(color_interference_graph(&mut interference_graph, 3, &costs).is_err());
+
+        let mut spill_slots = SpillSlots::default();
+        let (ops, pool) = color_with_spills(ops, 3, &mut spill_slots, &mut register_sequencer);
+        assert!(spill_slots.size_in_bytes() > 0);
+
+        let used = pool
+            .registers
+            .iter()
+            .filter(|status| !status.used_by.is_empty())
+            .count();
+        assert!(used <= 3, "{used} registers used");
+
+        // Every virtual register has been assigned one of them.
+        for reg in ops.iter().flat_map(|op| op.registers()) {
+            if matches!(reg, VirtualRegister::Virtual(_)) {
+                assert!(pool
+                    .registers
+                    .iter()
+                    .any(|status| status.used_by.contains(reg)));
+            }
+        }
+    }
+
+    #[test]
+    fn spill_candidate_is_cheapest_of_register_and_neighbors() {
+        let mut register_sequencer = RegisterSequencer::new();
+        let ops = four_live_values(&mut register_sequencer);
+        let live_out = liveness_analysis(&ops);
+        let (interference_graph, reg_to_node_ix) = create_interference_graph(&ops, &live_out);
+        let reg = |i: u32| VirtualRegister::Virtual(i.to_string());
+
+        // `a` interferes with `b`, `c` and `d`, which are all cheaper to spill than `a` itself.
+        let mut costs = spill_costs(&ops, &SpillSlots::default());
+        costs.insert(reg(2), 1);
+        assert_eq!(
+            spill_candidate(&reg(0), &interference_graph, &reg_to_node_ix, &costs),
+            Some(reg(2))
+        );
+
+        // Registers without a spill cost are never picked.
+        let costs = HashMap::from([(reg(0), 3)]);
+        assert_eq!(
+            spill_candidate(&reg(1), &interference_graph, &reg_to_node_ix, &costs),
+            Some(reg(0))
+        );
+        let costs = HashMap::new();
+        assert_eq!(
+            spill_candidate(&reg(1), &interference_graph, &reg_to_node_ix, &costs),
+            None
+        );
+    }
 }
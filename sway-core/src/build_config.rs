@@ -1,4 +1,4 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{collections::BTreeSet, path::PathBuf, sync::Arc};
 
 /// Configuration for the overall build and compilation process.
 #[derive(Clone)]
@@ -11,6 +11,7 @@ pub struct BuildConfig {
     pub(crate) print_finalized_asm: bool,
     pub(crate) print_ir: bool,
     pub(crate) include_tests: bool,
+    pub(crate) features: BTreeSet<String>,
 }
 
 impl BuildConfig {
@@ -34,7 +35,7 @@ impl BuildConfig {
             true => root_module,
             false => {
                 assert!(
-                    root_module.starts_with(canonical_manifest_dir.file_stem().unwrap()),
+                    root_module.starts_with(canonical_manifest_dir.file_name().unwrap()),
                     "file_name must be either absolute or relative to manifest directory",
                 );
                 canonical_manifest_dir
@@ -50,6 +51,7 @@ impl BuildConfig {
             print_finalized_asm: false,
             print_ir: false,
             include_tests: false,
+            features: BTreeSet::new(),
         }
     }
 
@@ -93,6 +95,13 @@ impl BuildConfig {
         }
     }
 
+    /// The enabled features of the package, satisfying `#[cfg(feature = "<name>")]` predicates.
+    ///
+    /// Default: none
+    pub fn features(self, features: BTreeSet<String>) -> Self {
+        Self { features, ..self }
+    }
+
     pub fn canonical_root_module(&self) -> Arc<PathBuf> {
         self.canonical_root_module.clone()
     }
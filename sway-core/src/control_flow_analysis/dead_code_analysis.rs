@@ -172,7 +172,9 @@ fn entry_points(
                     }) => {
                         let decl =
                             declaration_engine.get_function(decl_id.clone(), &decl_id.span())?;
-                        decl.visibility == Visibility::Public || decl.is_test()
+                        decl.visibility == Visibility::Public
+                            || decl.is_test()
+                            || decl.is_fallback()
                     }
                     ControlFlowGraphNode::ProgramNode(ty::TyAstNode {
                         content:
@@ -1432,17 +1434,33 @@ fn connect_expression<'eng: 'cfg, 'cfg>(
             typed_reassignment.rhs.clone().span,
             options,
         ),
-        StorageReassignment(typed_storage_reassignment) => connect_expression(
-            engines,
-            &typed_storage_reassignment.rhs.expression,
-            graph,
-            leaves,
-            exit_node,
-            "variable reassignment",
-            tree_type,
-            typed_storage_reassignment.rhs.clone().span,
-            options,
-        ),
+        StorageReassignment(typed_storage_reassignment) => {
+            let mut leaves = leaves.to_vec();
+            if let Some(index) = &typed_storage_reassignment.index {
+                leaves = connect_expression(
+                    engines,
+                    &index.expression,
+                    graph,
+                    &leaves,
+                    exit_node,
+                    "",
+                    tree_type,
+                    index.span.clone(),
+                    options,
+                )?;
+            }
+            connect_expression(
+                engines,
+                &typed_storage_reassignment.rhs.expression,
+                graph,
+                &leaves,
+                exit_node,
+                "variable reassignment",
+                tree_type,
+                typed_storage_reassignment.rhs.clone().span,
+                options,
+            )
+        }
         Return(exp) => {
             let this_index = graph.add_node(engines, "return entry".into());
             for leaf in leaves {
@@ -70,9 +70,13 @@ pub fn compile_program(
             &messages_types,
             &test_fns,
         ),
-        ty::TyProgramKind::Contract { abi_entries } => compile::compile_contract(
+        ty::TyProgramKind::Contract {
+            abi_entries,
+            fallback_fn,
+        } => compile::compile_contract(
             &mut ctx,
             abi_entries,
+            fallback_fn.as_ref(),
             &root.namespace,
             declarations,
             &logged_types,
@@ -8,7 +8,7 @@ use crate::{
 };
 
 use super::{
-    const_eval::{compile_const_decl, LookupEnv},
+    const_eval::{compile_const_decl, compile_constant_expression, LookupEnv},
     convert::convert_resolved_typeid,
     function::FnCompiler,
 };
@@ -115,6 +115,7 @@ pub(super) fn compile_predicate(
 pub(super) fn compile_contract(
     context: &mut Context,
     abi_entries: &[ty::TyFunctionDeclaration],
+    fallback_fn: Option<&ty::TyFunctionDeclaration>,
     namespace: &namespace::Module,
     declarations: &[ty::TyDeclaration],
     logged_types_map: &HashMap<TypeId, LogId>,
@@ -145,6 +146,18 @@ pub(super) fn compile_contract(
             engines,
         )?;
     }
+    if let Some(fallback_fn) = fallback_fn {
+        compile_entry_function(
+            engines,
+            context,
+            &mut md_mgr,
+            module,
+            fallback_fn,
+            logged_types_map,
+            messages_types_map,
+            None,
+        )?;
+    }
     compile_tests(
         engines,
         context,
@@ -216,6 +229,33 @@ pub(crate) fn compile_constants(
         )?;
     }
 
+    // Test constants are declared under their own names, so they're only found within the
+    // module's test constants, see `namespace::Module::insert_test_constants`.
+    for (decl_name, decl) in module_ns.test_constants() {
+        let decl = match decl {
+            ty::TyDeclaration::ConstantDeclaration(decl_id) => {
+                declaration_engine.get_constant(decl_id.clone(), &decl_name.span())?
+            }
+            _otherwise => continue,
+        };
+        if module
+            .get_global_constant(context, decl.name.as_str())
+            .is_some()
+        {
+            continue;
+        }
+        let const_val = compile_constant_expression(
+            engines,
+            context,
+            md_mgr,
+            module,
+            Some(module_ns),
+            None,
+            &decl.value,
+        )?;
+        module.add_global_constant(context, decl.name.as_str().to_owned(), const_val);
+    }
+
     for submodule_ns in module_ns.submodules().values() {
         compile_constants(engines, context, md_mgr, module, submodule_ns)?;
     }
@@ -460,6 +500,14 @@ fn compile_fn_with_args(
         let inline_md_idx = md_mgr.inline_to_md(context, inline);
         metadata = md_combine(context, &metadata, &inline_md_idx);
     }
+    if selector.is_some() && ast_fn_decl.is_hot() {
+        let hot_md_idx = md_mgr.hot_to_md(context);
+        metadata = md_combine(context, &metadata, &hot_md_idx);
+    }
+    if is_entry && decl_index.is_none() && ast_fn_decl.is_fallback() {
+        let fallback_md_idx = md_mgr.fallback_to_md(context);
+        metadata = md_combine(context, &metadata, &fallback_md_idx);
+    }
 
     let func = Function::new(
         context,
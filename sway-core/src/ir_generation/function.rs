@@ -2,7 +2,9 @@ use super::{
     compile::compile_function,
     convert::*,
     lexical_map::LexicalMap,
-    storage::{add_to_b256, get_storage_key},
+    storage::{
+        add_to_b256, get_storage_key, storage_array_element_layout, StorageArrayElementLayout,
+    },
     types::*,
 };
 use crate::{
@@ -417,6 +419,7 @@ impl<'eng> FnCompiler<'eng> {
                     md_mgr,
                     &storage_reassignment.fields,
                     &storage_reassignment.ix,
+                    storage_reassignment.index.as_ref(),
                     &storage_reassignment.rhs,
                     span_md_idx,
                 ),
@@ -1777,8 +1780,48 @@ impl<'eng> FnCompiler<'eng> {
             .iter()
             .any(|f| matches!(f, ProjectionKind::ArrayIndex { .. }))
         {
+            // Drill down through the projections, extracting the element or field for each but
+            // the last, into which the new value is inserted.  Nested aggregates are extracted by
+            // reference, so the variable is updated in place.
+            let mut type_id = ast_reassignment.lhs_type;
             let it = &mut ast_reassignment.lhs_indices.iter().peekable();
-            while let Some(ProjectionKind::ArrayIndex { index, .. }) = it.next() {
+            while let Some(projection) = it.next() {
+                let is_last_index = it.peek().is_none();
+                let index = match projection {
+                    ProjectionKind::ArrayIndex { index, .. } => index,
+                    ProjectionKind::StructField { .. } | ProjectionKind::TupleField { .. } => {
+                        let (field_idcs, field_type_id) = get_indices_and_type_for_struct_access(
+                            self.type_engine,
+                            type_id,
+                            std::slice::from_ref(projection),
+                        )?;
+                        let ty = match val.get_stripped_ptr_type(context).unwrap() {
+                            Type::Struct(aggregate) => aggregate,
+                            _otherwise => {
+                                return Err(CompileError::Internal(
+                                    "Reassignment with multiple accessors to non-aggregate.",
+                                    projection.span(),
+                                ));
+                            }
+                        };
+                        val = if is_last_index {
+                            self.current_block.ins(context).insert_value(
+                                val,
+                                ty,
+                                reassign_val,
+                                field_idcs,
+                            )
+                        } else {
+                            self.current_block
+                                .ins(context)
+                                .extract_value(val, ty, field_idcs)
+                        }
+                        .add_metadatum(context, span_md_idx);
+                        type_id = field_type_id;
+                        continue;
+                    }
+                };
+
                 let index_val = self.compile_expression(context, md_mgr, index)?;
                 if index_val.is_diverging(context) {
                     return Ok(index_val);
@@ -1802,7 +1845,6 @@ impl<'eng> FnCompiler<'eng> {
 
                 // When handling nested array indexing, we should keep extracting the first
                 // elements up until the last, and insert into the last element.
-                let is_last_index = it.peek().is_none();
                 if is_last_index {
                     val = self
                         .current_block
@@ -1816,6 +1858,9 @@ impl<'eng> FnCompiler<'eng> {
                         .extract_element(val, ty, index_val)
                         .add_metadatum(context, span_md_idx);
                 }
+                if let TypeInfo::Array(elem_type, _) = self.type_engine.look_up_type_id(type_id) {
+                    type_id = elem_type.type_id;
+                }
             }
         } else {
             // An aggregate.  Iterate over the field names from the left hand side and collect
@@ -1852,12 +1897,14 @@ impl<'eng> FnCompiler<'eng> {
         Ok(Constant::get_unit(context).add_metadatum(context, span_md_idx))
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn compile_storage_reassignment(
         &mut self,
         context: &mut Context,
         md_mgr: &mut MetadataManager,
         fields: &[ty::TyStorageReassignDescriptor],
         ix: &StateIndex,
+        index: Option<&ty::TyExpression>,
         rhs: &ty::TyExpression,
         span_md_idx: Option<MetadataIndex>,
     ) -> Result<Value, CompileError> {
@@ -1879,6 +1926,19 @@ impl<'eng> FnCompiler<'eng> {
         let base_type = fields[0].type_id;
         let field_idcs = get_indices_for_struct_access(self.type_engine, base_type, &fields[1..])?;
 
+        if let Some(index_expr) = index {
+            return self.compile_storage_array_element_reassignment(
+                context,
+                md_mgr,
+                ix,
+                &field_idcs,
+                &access_type,
+                index_expr,
+                rhs,
+                span_md_idx,
+            );
+        }
+
         // Do the actual work. This is a recursive function because we want to drill down
         // to store each primitive type in the storage field in its own storage slot.
         self.compile_storage_write(
@@ -1893,6 +1953,238 @@ impl<'eng> FnCompiler<'eng> {
         Ok(Constant::get_unit(context).add_metadatum(context, span_md_idx))
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn compile_storage_array_element_reassignment(
+        &mut self,
+        context: &mut Context,
+        md_mgr: &mut MetadataManager,
+        ix: &StateIndex,
+        indices: &[u64],
+        array_type: &Type,
+        index_expr: &ty::TyExpression,
+        rhs: Value,
+        span_md_idx: Option<MetadataIndex>,
+    ) -> Result<Value, CompileError> {
+        let aggregate = match array_type {
+            Type::Array(aggregate) => *aggregate,
+            _ => {
+                return Err(CompileError::Internal(
+                    "Indexed storage reassignment to a non-array.",
+                    index_expr.span.clone(),
+                ))
+            }
+        };
+        self.check_array_index_bounds(context, md_mgr, &aggregate, index_expr)?;
+
+        let index_val = self.compile_expression(context, md_mgr, index_expr)?;
+        if index_val.is_diverging(context) {
+            return Ok(index_val);
+        }
+
+        let storage_key = get_storage_key(ix, indices);
+        match storage_array_element_layout(context, &storage_key, array_type) {
+            Some(layout) => {
+                let (key_val, buffer_ptr, buffer_aggregate, index_in_buffer) = self
+                    .compile_storage_array_element_slots(
+                        context,
+                        ix,
+                        indices,
+                        &storage_key,
+                        &aggregate,
+                        &layout,
+                        index_val,
+                        span_md_idx,
+                    )?;
+                let number_of_slots = Constant::get_uint(context, 64, layout.slots_per_access);
+
+                // Other elements may share the slot, so it has to be loaded before the element
+                // is inserted into it.
+                if layout.elems_per_access > 1 {
+                    let buffer_b256 = self
+                        .current_block
+                        .ins(context)
+                        .get_ptr(buffer_ptr, Type::B256, 0)
+                        .add_metadatum(context, span_md_idx);
+                    self.current_block
+                        .ins(context)
+                        .state_load_quad_word(buffer_b256, key_val, number_of_slots)
+                        .add_metadatum(context, span_md_idx);
+                }
+
+                let buffer_val = self
+                    .current_block
+                    .ins(context)
+                    .get_ptr(buffer_ptr, Type::Array(buffer_aggregate), 0)
+                    .add_metadatum(context, span_md_idx);
+                self.current_block
+                    .ins(context)
+                    .insert_element(buffer_val, buffer_aggregate, rhs, index_in_buffer)
+                    .add_metadatum(context, span_md_idx);
+
+                let buffer_b256 = self
+                    .current_block
+                    .ins(context)
+                    .get_ptr(buffer_ptr, Type::B256, 0)
+                    .add_metadatum(context, span_md_idx);
+                self.current_block
+                    .ins(context)
+                    .state_store_quad_word(buffer_b256, key_val, number_of_slots)
+                    .add_metadatum(context, span_md_idx);
+            }
+            None => {
+                // The element doesn't line up with slot boundaries, so read the whole array,
+                // update the element and write the whole array back.
+                let array_val = self.compile_storage_read(
+                    context,
+                    md_mgr,
+                    ix,
+                    indices,
+                    array_type,
+                    span_md_idx,
+                )?;
+                let array_val = self
+                    .current_block
+                    .ins(context)
+                    .insert_element(array_val, aggregate, rhs, index_val)
+                    .add_metadatum(context, span_md_idx);
+                self.compile_storage_write(
+                    context,
+                    md_mgr,
+                    ix,
+                    indices,
+                    array_type,
+                    array_val,
+                    span_md_idx,
+                )?;
+            }
+        }
+        Ok(Constant::get_unit(context).add_metadatum(context, span_md_idx))
+    }
+
+    /// Sets up the access to the storage slots holding the element at `index_val` of an array in
+    /// storage. The key of the first slot is computed from the array's `storage_key` and a local
+    /// buffer is created to load the slots into or store them from.
+    ///
+    /// Returns the key, the buffer, the buffer's array type and the index of the element within
+    /// the buffer.
+    #[allow(clippy::too_many_arguments)]
+    fn compile_storage_array_element_slots(
+        &mut self,
+        context: &mut Context,
+        ix: &StateIndex,
+        indices: &[u64],
+        storage_key: &fuel_types::Bytes32,
+        aggregate: &Aggregate,
+        layout: &StorageArrayElementLayout,
+        index_val: Value,
+        span_md_idx: Option<MetadataIndex>,
+    ) -> Result<(Value, Pointer, Aggregate, Value), CompileError> {
+        let name_suffix = format!(
+            "{}{}",
+            ix.to_usize(),
+            indices
+                .iter()
+                .map(|idx| format!("_{idx}"))
+                .collect::<Vec<_>>()
+                .join("")
+        );
+
+        // Find the slot holding the element and the element's position within it.
+        let (slot_offset, index_in_buffer) = if layout.elems_per_access > 1 {
+            let elems_per_access = Constant::get_uint(context, 64, layout.elems_per_access);
+            let slot_offset = self
+                .current_block
+                .ins(context)
+                .binary_op(BinaryOpKind::Div, index_val, elems_per_access)
+                .add_metadatum(context, span_md_idx);
+            let first_index_in_slot = self
+                .current_block
+                .ins(context)
+                .binary_op(BinaryOpKind::Mul, slot_offset, elems_per_access)
+                .add_metadatum(context, span_md_idx);
+            let index_in_buffer = self
+                .current_block
+                .ins(context)
+                .binary_op(BinaryOpKind::Sub, index_val, first_index_in_slot)
+                .add_metadatum(context, span_md_idx);
+            (slot_offset, index_in_buffer)
+        } else if layout.slots_per_access > 1 {
+            let slots_per_access = Constant::get_uint(context, 64, layout.slots_per_access);
+            let slot_offset = self
+                .current_block
+                .ins(context)
+                .binary_op(BinaryOpKind::Mul, index_val, slots_per_access)
+                .add_metadatum(context, span_md_idx);
+            (slot_offset, Constant::get_uint(context, 64, 0))
+        } else {
+            (index_val, Constant::get_uint(context, 64, 0))
+        };
+
+        // The key is held as four words so that the slot offset can be added to the lowest one.
+        let key_aggregate = Aggregate::new_struct(context, vec![Type::Uint(64); 4]);
+        let alias_key_name = self.lexical_map.insert(format!("key_for_{name_suffix}"));
+        let key_ptr = self
+            .function
+            .new_local_ptr(
+                context,
+                alias_key_name,
+                Type::Struct(key_aggregate),
+                true,
+                None,
+            )
+            .map_err(|ir_error| CompileError::InternalOwned(ir_error.to_string(), Span::dummy()))?;
+
+        let const_key = convert_literal_to_value(context, &Literal::B256(**storage_key))
+            .add_metadatum(context, span_md_idx);
+        let key_val = self
+            .current_block
+            .ins(context)
+            .get_ptr(key_ptr, Type::B256, 0)
+            .add_metadatum(context, span_md_idx);
+        self.current_block
+            .ins(context)
+            .store(key_val, const_key)
+            .add_metadatum(context, span_md_idx);
+
+        let key_words_val = self
+            .current_block
+            .ins(context)
+            .get_ptr(key_ptr, Type::Struct(key_aggregate), 0)
+            .add_metadatum(context, span_md_idx);
+        let low_word = self
+            .current_block
+            .ins(context)
+            .extract_value(key_words_val, key_aggregate, vec![3])
+            .add_metadatum(context, span_md_idx);
+        let low_word = self
+            .current_block
+            .ins(context)
+            .binary_op(BinaryOpKind::Add, low_word, slot_offset)
+            .add_metadatum(context, span_md_idx);
+        self.current_block
+            .ins(context)
+            .insert_value(key_words_val, key_aggregate, low_word, vec![3])
+            .add_metadatum(context, span_md_idx);
+
+        // The buffer holds exactly the slots accessed.
+        let (elem_type, _) = aggregate.get_content(context).array_type();
+        let elem_type = *elem_type;
+        let buffer_aggregate = Aggregate::new_array(context, elem_type, layout.elems_per_access);
+        let alias_value_name = self.lexical_map.insert(format!("val_for_{name_suffix}"));
+        let buffer_ptr = self
+            .function
+            .new_local_ptr(
+                context,
+                alias_value_name,
+                Type::Array(buffer_aggregate),
+                true,
+                None,
+            )
+            .map_err(|ir_error| CompileError::InternalOwned(ir_error.to_string(), Span::dummy()))?;
+
+        Ok((key_val, buffer_ptr, buffer_aggregate, index_in_buffer))
+    }
+
     fn compile_array_expr(
         &mut self,
         context: &mut Context,
@@ -1947,6 +2239,19 @@ impl<'eng> FnCompiler<'eng> {
         index_expr: &ty::TyExpression,
         span_md_idx: Option<MetadataIndex>,
     ) -> Result<Value, CompileError> {
+        if let ty::TyExpressionVariant::StorageAccess(access) = &array_expr.expression {
+            if let Some(element_val) = self.compile_storage_array_element_access(
+                context,
+                md_mgr,
+                &access.fields,
+                &access.ix,
+                index_expr,
+                span_md_idx,
+            )? {
+                return Ok(element_val);
+            }
+        }
+
         let array_expr_span = array_expr.span.clone();
 
         let array_val = self.compile_expression(context, md_mgr, array_expr)?;
@@ -1979,6 +2284,102 @@ impl<'eng> FnCompiler<'eng> {
             ))
         }?;
 
+        self.check_array_index_bounds(context, md_mgr, &aggregate, index_expr)?;
+
+        let index_val = self.compile_expression(context, md_mgr, index_expr)?;
+        if index_val.is_diverging(context) {
+            return Ok(index_val);
+        }
+
+        Ok(self
+            .current_block
+            .ins(context)
+            .extract_element(array_val, aggregate, index_val)
+            .add_metadatum(context, span_md_idx))
+    }
+
+    /// Reads a single element of an array in storage, loading only the slots holding it.
+    /// Returns `None` if the element doesn't line up with slot boundaries, in which case the
+    /// whole array has to be read instead.
+    fn compile_storage_array_element_access(
+        &mut self,
+        context: &mut Context,
+        md_mgr: &mut MetadataManager,
+        fields: &[ty::TyStorageAccessDescriptor],
+        ix: &StateIndex,
+        index_expr: &ty::TyExpression,
+        span_md_idx: Option<MetadataIndex>,
+    ) -> Result<Option<Value>, CompileError> {
+        let array_type = convert_resolved_typeid_no_span(
+            self.type_engine,
+            context,
+            &fields.last().expect("guaranteed by grammar").type_id,
+        )?;
+        let aggregate = match array_type {
+            Type::Array(aggregate) => aggregate,
+            _ => return Ok(None),
+        };
+
+        let base_type = fields[0].type_id;
+        let field_idcs = get_indices_for_struct_access(self.type_engine, base_type, &fields[1..])?;
+        let storage_key = get_storage_key(ix, &field_idcs);
+        let layout = match storage_array_element_layout(context, &storage_key, &array_type) {
+            Some(layout) => layout,
+            None => return Ok(None),
+        };
+
+        self.check_array_index_bounds(context, md_mgr, &aggregate, index_expr)?;
+
+        let index_val = self.compile_expression(context, md_mgr, index_expr)?;
+        if index_val.is_diverging(context) {
+            return Ok(Some(index_val));
+        }
+
+        let (key_val, buffer_ptr, buffer_aggregate, index_in_buffer) = self
+            .compile_storage_array_element_slots(
+                context,
+                ix,
+                &field_idcs,
+                &storage_key,
+                &aggregate,
+                &layout,
+                index_val,
+                span_md_idx,
+            )?;
+
+        let buffer_b256 = self
+            .current_block
+            .ins(context)
+            .get_ptr(buffer_ptr, Type::B256, 0)
+            .add_metadatum(context, span_md_idx);
+        let number_of_slots = Constant::get_uint(context, 64, layout.slots_per_access);
+        self.current_block
+            .ins(context)
+            .state_load_quad_word(buffer_b256, key_val, number_of_slots)
+            .add_metadatum(context, span_md_idx);
+
+        let buffer_val = self
+            .current_block
+            .ins(context)
+            .get_ptr(buffer_ptr, Type::Array(buffer_aggregate), 0)
+            .add_metadatum(context, span_md_idx);
+        Ok(Some(
+            self.current_block
+                .ins(context)
+                .extract_element(buffer_val, buffer_aggregate, index_in_buffer)
+                .add_metadatum(context, span_md_idx),
+        ))
+    }
+
+    /// Reports an error if `index_expr` is a constant which is out of bounds for the array type
+    /// `aggregate`. Non-constant indices aren't checked.
+    fn check_array_index_bounds(
+        &mut self,
+        context: &mut Context,
+        md_mgr: &mut MetadataManager,
+        aggregate: &Aggregate,
+        index_expr: &ty::TyExpression,
+    ) -> Result<(), CompileError> {
         let index_expr_span = index_expr.span.clone();
 
         if let Ok(Constant {
@@ -2002,17 +2403,7 @@ impl<'eng> FnCompiler<'eng> {
                 });
             }
         }
-
-        let index_val = self.compile_expression(context, md_mgr, index_expr)?;
-        if index_val.is_diverging(context) {
-            return Ok(index_val);
-        }
-
-        Ok(self
-            .current_block
-            .ins(context)
-            .extract_element(array_val, aggregate, index_val)
-            .add_metadatum(context, span_md_idx))
+        Ok(())
     }
 
     fn compile_struct_expr(
@@ -2446,10 +2837,6 @@ impl<'eng> FnCompiler<'eng> {
                     .add_metadatum(context, span_md_idx);
 
                 match ty {
-                    Type::Array(_) => Err(CompileError::Internal(
-                        "Arrays in storage have not been implemented yet.",
-                        Span::dummy(),
-                    )),
                     Type::Pointer(_) => Err(CompileError::Internal(
                         "Pointers in storage have not been implemented yet.",
                         Span::dummy(),
@@ -2471,16 +2858,17 @@ impl<'eng> FnCompiler<'eng> {
                         ty,
                         span_md_idx,
                     ),
-                    Type::String(_) | Type::Union(_) => self.compile_union_or_string_storage_read(
-                        context,
-                        ix,
-                        indices,
-                        &mut key_ptr_val,
-                        &key_ptr,
-                        &storage_key,
-                        ty,
-                        span_md_idx,
-                    ),
+                    Type::Array(_) | Type::String(_) | Type::Union(_) => self
+                        .compile_union_or_string_storage_read(
+                            context,
+                            ix,
+                            indices,
+                            &mut key_ptr_val,
+                            &key_ptr,
+                            &storage_key,
+                            ty,
+                            span_md_idx,
+                        ),
                     Type::Struct(_) => unreachable!("structs are already handled!"),
                     Type::Unit => {
                         Ok(Constant::get_unit(context).add_metadatum(context, span_md_idx))
@@ -2568,10 +2956,6 @@ impl<'eng> FnCompiler<'eng> {
                     .add_metadatum(context, span_md_idx);
 
                 match ty {
-                    Type::Array(_) => Err(CompileError::Internal(
-                        "Arrays in storage have not been implemented yet.",
-                        Span::dummy(),
-                    )),
                     Type::Pointer(_) => Err(CompileError::Internal(
                         "Pointers in storage have not been implemented yet.",
                         Span::dummy(),
@@ -2594,17 +2978,18 @@ impl<'eng> FnCompiler<'eng> {
                         rhs,
                         span_md_idx,
                     ),
-                    Type::String(_) | Type::Union(_) => self.compile_union_or_string_storage_write(
-                        context,
-                        ix,
-                        indices,
-                        &mut key_ptr_val,
-                        &key_ptr,
-                        &storage_key,
-                        ty,
-                        rhs,
-                        span_md_idx,
-                    ),
+                    Type::Array(_) | Type::String(_) | Type::Union(_) => self
+                        .compile_union_or_string_storage_write(
+                            context,
+                            ix,
+                            indices,
+                            &mut key_ptr_val,
+                            &key_ptr,
+                            &storage_key,
+                            ty,
+                            rhs,
+                            span_md_idx,
+                        ),
                     Type::Struct(_) => unreachable!("structs are already handled!"),
                     Type::Unit => Ok(()),
                 }
@@ -47,11 +47,63 @@ pub(super) fn add_to_b256(x: Bytes32, y: u64) -> Bytes32 {
     Bytes32::from(res)
 }
 
+/// Describes the storage slots accessed when a single element of an array in storage is read or
+/// written. Either a slot holds `elems_per_access` whole elements, or an element spans
+/// `slots_per_access` whole slots.
+///
+pub(super) struct StorageArrayElementLayout {
+    pub(super) elems_per_access: u64,
+    pub(super) slots_per_access: u64,
+}
+
+/// Returns the layout used to access a single element of the array of type `array_ty` stored at
+/// `storage_key`, or `None` if elements don't line up with slot boundaries, in which case the
+/// whole array has to be accessed instead.
+///
+/// The key of an element's slot is computed at runtime by adding the slot offset to the lowest
+/// word of `storage_key`, so the array must not cross a boundary where that word would wrap.
+///
+pub(super) fn storage_array_element_layout(
+    context: &Context,
+    storage_key: &Bytes32,
+    array_ty: &Type,
+) -> Option<StorageArrayElementLayout> {
+    let (elem_ty, count) = match array_ty {
+        Type::Array(aggregate) => match aggregate.get_content(context) {
+            AggregateContent::ArrayType(elem_ty, count) => (*elem_ty, *count),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let layout = match ir_type_size_in_bytes(context, &elem_ty) / 8 {
+        elem_size_in_words @ (1 | 2 | 4) => StorageArrayElementLayout {
+            elems_per_access: 4 / elem_size_in_words,
+            slots_per_access: 1,
+        },
+        elem_size_in_words if elem_size_in_words > 4 && elem_size_in_words % 4 == 0 => {
+            StorageArrayElementLayout {
+                elems_per_access: 1,
+                slots_per_access: elem_size_in_words / 4,
+            }
+        }
+        _ => return None,
+    };
+
+    // An upper bound of the number of slots holding the array.
+    let number_of_slots = count.checked_mul(layout.slots_per_access)?;
+    let low_word = u64::from_be_bytes(storage_key[24..].try_into().unwrap());
+    low_word.checked_add(number_of_slots)?;
+
+    Some(layout)
+}
+
 /// Given a constant value `constant`, a type `ty`, a state index, and a vector of subfield
 /// indices, serialize the constant into a vector of storage slots. The keys (slots) are
 /// generated using the state index and the subfield indices which are recursively built. The
-/// values are generated such that each subfield gets its own storage slot except for enums and
-/// strings which are spread over successive storage slots (use `serialize_to_words` in this case).
+/// values are generated such that each subfield gets its own storage slot except for arrays, enums
+/// and strings which are spread over successive storage slots (use `serialize_to_words` in this
+/// case).
 ///
 /// This behavior matches the behavior of how storage slots are assigned for storage reads and
 /// writes (i.e. how `state_read_*` and `state_write_*` instructions are generated).
@@ -104,9 +156,6 @@ pub fn serialize_to_storage_slots(
                 Bytes32::new(*b),
             )]
         }
-        (Type::Array(_), ConstantValue::Array(_a)) => {
-            unimplemented!("Arrays in storage have not been implemented yet.")
-        }
         (Type::Struct(aggregate), ConstantValue::Struct(vec)) => {
             match aggregate.get_content(context) {
                 AggregateContent::FieldTypes(field_tys) => vec
@@ -130,7 +179,7 @@ pub fn serialize_to_storage_slots(
                 _ => unreachable!("Wrong content for struct."),
             }
         }
-        (Type::Union(_), _) | (Type::String(_), _) => {
+        (Type::Array(_), _) | (Type::Union(_), _) | (Type::String(_), _) => {
             // Serialize the constant data in words and add zero words until the number of words
             // is a multiple of 4. This is useful because each storage slot is 4 words.
             let mut packed = serialize_to_words(constant, context, ty);
@@ -202,8 +251,14 @@ pub fn serialize_to_words(constant: &Constant, context: &Context, ty: &Type) ->
                 )
             }))
         }
-        (Type::Array(_), ConstantValue::Array(_)) => {
-            unimplemented!("Arrays in storage have not been implemented yet.")
+        (Type::Array(aggregate), ConstantValue::Array(elems)) => {
+            match aggregate.get_content(context) {
+                AggregateContent::ArrayType(elem_ty, _) => elems
+                    .iter()
+                    .flat_map(|elem| serialize_to_words(elem, context, elem_ty))
+                    .collect(),
+                _ => unreachable!("Wrong content for array."),
+            }
         }
         (Type::Struct(aggregate), ConstantValue::Struct(vec)) => {
             match aggregate.get_content(context) {
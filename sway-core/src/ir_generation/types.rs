@@ -129,72 +129,79 @@ pub(super) fn get_indices_for_struct_access(
     base_type: TypeId,
     fields: &[impl TypedNamedField],
 ) -> Result<Vec<u64>, CompileError> {
-    fields
-        .iter()
-        .try_fold(
-            (Vec::new(), base_type),
-            |(mut fld_idcs, prev_type_id), field| {
-                let field_kind = field.get_field_kind();
-                let ty_info = match type_engine.to_typeinfo(prev_type_id, &field_kind.span()) {
-                    Ok(ty_info) => ty_info,
-                    Err(error) => {
-                        return Err(CompileError::InternalOwned(
-                            format!("type error resolving type for reassignment: {}", error),
-                            field_kind.span(),
-                        ));
-                    }
-                };
-                // Make sure we have an aggregate to index into.
-                // Get the field index and also its type for the next iteration.
-                match (ty_info, &field_kind) {
-                    (
-                        TypeInfo::Struct { name, fields, .. },
-                        ty::ProjectionKind::StructField { name: field_name },
-                    ) => {
-                        let field_idx_and_type_opt = fields
-                            .iter()
-                            .enumerate()
-                            .find(|(_, field)| field.name == *field_name);
-                        let (field_idx, field_type) = match field_idx_and_type_opt {
-                            Some((idx, field)) => (idx as u64, field.type_id),
-                            None => {
-                                return Err(CompileError::InternalOwned(
-                                    format!(
-                                        "Unknown field '{}' for struct {} in reassignment.",
-                                        field_kind.pretty_print(),
-                                        name,
-                                    ),
-                                    field_kind.span(),
-                                ));
-                            }
-                        };
-                        // Save the field index.
-                        fld_idcs.push(field_idx);
-                        Ok((fld_idcs, field_type))
-                    }
-                    (TypeInfo::Tuple(fields), ty::ProjectionKind::TupleField { index, .. }) => {
-                        let field_type = match fields.get(*index) {
-                            Some(field_type_argument) => field_type_argument.type_id,
-                            None => {
-                                return Err(CompileError::InternalOwned(
-                                    format!(
-                                        "index {} is out of bounds for tuple of length {}",
-                                        index,
-                                        fields.len(),
-                                    ),
-                                    field_kind.span(),
-                                ));
-                            }
-                        };
-                        fld_idcs.push(*index as u64);
-                        Ok((fld_idcs, field_type))
-                    }
-                    _ => Err(CompileError::Internal(
-                        "Unknown aggregate in reassignment.",
+    get_indices_and_type_for_struct_access(type_engine, base_type, fields)
+        .map(|(fld_idcs, _)| fld_idcs)
+}
+
+/// Like [get_indices_for_struct_access], but also returns the type of the accessed field.
+pub(super) fn get_indices_and_type_for_struct_access(
+    type_engine: &TypeEngine,
+    base_type: TypeId,
+    fields: &[impl TypedNamedField],
+) -> Result<(Vec<u64>, TypeId), CompileError> {
+    fields.iter().try_fold(
+        (Vec::new(), base_type),
+        |(mut fld_idcs, prev_type_id), field| {
+            let field_kind = field.get_field_kind();
+            let ty_info = match type_engine.to_typeinfo(prev_type_id, &field_kind.span()) {
+                Ok(ty_info) => ty_info,
+                Err(error) => {
+                    return Err(CompileError::InternalOwned(
+                        format!("type error resolving type for reassignment: {}", error),
                         field_kind.span(),
-                    )),
+                    ));
                 }
-            },
-        )
-        .map(|(fld_idcs, _)| fld_idcs)
+            };
+            // Make sure we have an aggregate to index into.
+            // Get the field index and also its type for the next iteration.
+            match (ty_info, &field_kind) {
+                (
+                    TypeInfo::Struct { name, fields, .. },
+                    ty::ProjectionKind::StructField { name: field_name },
+                ) => {
+                    let field_idx_and_type_opt = fields
+                        .iter()
+                        .enumerate()
+                        .find(|(_, field)| field.name == *field_name);
+                    let (field_idx, field_type) = match field_idx_and_type_opt {
+                        Some((idx, field)) => (idx as u64, field.type_id),
+                        None => {
+                            return Err(CompileError::InternalOwned(
+                                format!(
+                                    "Unknown field '{}' for struct {} in reassignment.",
+                                    field_kind.pretty_print(),
+                                    name,
+                                ),
+                                field_kind.span(),
+                            ));
+                        }
+                    };
+                    // Save the field index.
+                    fld_idcs.push(field_idx);
+                    Ok((fld_idcs, field_type))
+                }
+                (TypeInfo::Tuple(fields), ty::ProjectionKind::TupleField { index, .. }) => {
+                    let field_type = match fields.get(*index) {
+                        Some(field_type_argument) => field_type_argument.type_id,
+                        None => {
+                            return Err(CompileError::InternalOwned(
+                                format!(
+                                    "index {} is out of bounds for tuple of length {}",
+                                    index,
+                                    fields.len(),
+                                ),
+                                field_kind.span(),
+                            ));
+                        }
+                    };
+                    fld_idcs.push(*index as u64);
+                    Ok((fld_idcs, field_type))
+                }
+                _ => Err(CompileError::Internal(
+                    "Unknown aggregate in reassignment.",
+                    field_kind.span(),
+                )),
+            }
+        },
+    )
 }
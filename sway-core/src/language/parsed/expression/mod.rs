@@ -208,12 +208,17 @@ pub enum ExpressionKind {
 }
 
 /// Represents the left hand side of a reassignment, which could either be a regular variable
-/// expression, denoted by [ReassignmentTarget::VariableExpression], or, a storage field, denoted
-/// by [ReassignmentTarget::StorageField].
+/// expression, denoted by [ReassignmentTarget::VariableExpression], a storage field, denoted
+/// by [ReassignmentTarget::StorageField], or an element of an array within a storage field, denoted
+/// by [ReassignmentTarget::StorageArrayElement].
 #[derive(Debug, Clone)]
 pub enum ReassignmentTarget {
     VariableExpression(Box<Expression>),
     StorageField(Vec<Ident>),
+    StorageArrayElement {
+        fields: Vec<Ident>,
+        index: Box<Expression>,
+    },
 }
 
 #[derive(Debug, Clone)]
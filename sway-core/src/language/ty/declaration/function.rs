@@ -380,6 +380,19 @@ impl TyFunctionDeclaration {
             .contains_key(&transform::AttributeKind::Test)
     }
 
+    /// Whether or not this function is a contract method which is checked first when dispatching on
+    /// the function selector, i.e. decorated with `#[hot]`.
+    pub fn is_hot(&self) -> bool {
+        self.attributes.contains_key(&transform::AttributeKind::Hot)
+    }
+
+    /// Whether or not this function is called by a contract when no ABI method matches the
+    /// function selector, i.e. decorated with `#[fallback]`.
+    pub fn is_fallback(&self) -> bool {
+        self.attributes
+            .contains_key(&transform::AttributeKind::Fallback)
+    }
+
     pub fn inline(&self) -> Option<Inline> {
         match self
             .attributes
@@ -387,6 +400,7 @@ impl TyFunctionDeclaration {
             .last()?
             .args
             .first()?
+            .name
             .as_str()
         {
             INLINE_NEVER_NAME => Some(Inline::Never),
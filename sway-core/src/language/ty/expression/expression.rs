@@ -385,6 +385,14 @@ impl CollectTypesMetadata for TyExpression {
                         errors
                     ));
                 }
+                if let Some(index) = &storage_reassignment.index {
+                    res.append(&mut check!(
+                        index.collect_types_metadata(ctx),
+                        return err(warnings, errors),
+                        warnings,
+                        errors
+                    ));
+                }
                 res.append(&mut check!(
                     storage_reassignment.rhs.collect_types_metadata(ctx),
                     return err(warnings, errors),
@@ -496,9 +504,13 @@ impl DeterministicallyAborts for TyExpression {
             Reassignment(reassignment) => reassignment
                 .rhs
                 .deterministically_aborts(declaration_engine, check_call_body),
-            StorageReassignment(storage_reassignment) => storage_reassignment
-                .rhs
-                .deterministically_aborts(declaration_engine, check_call_body),
+            StorageReassignment(storage_reassignment) => {
+                storage_reassignment.index.iter().any(|index| {
+                    index.deterministically_aborts(declaration_engine, check_call_body)
+                }) || storage_reassignment
+                    .rhs
+                    .deterministically_aborts(declaration_engine, check_call_body)
+            }
             // TODO: Is this correct?
             // I'm not sure what this function is supposed to do exactly. It's called
             // "deterministically_aborts" which I thought meant it checks for an abort/panic, but
@@ -870,9 +870,12 @@ impl TyExpressionVariant {
             TyExpressionVariant::Reassignment(reassignment) => {
                 reassignment.rhs.gather_return_statements()
             }
-            TyExpressionVariant::StorageReassignment(storage_reassignment) => {
-                storage_reassignment.rhs.gather_return_statements()
-            }
+            TyExpressionVariant::StorageReassignment(storage_reassignment) => storage_reassignment
+                .index
+                .iter()
+                .chain(std::iter::once(&storage_reassignment.rhs))
+                .flat_map(|expr| expr.gather_return_statements())
+                .collect(),
             TyExpressionVariant::LazyOperator { lhs, rhs, .. } => [lhs, rhs]
                 .into_iter()
                 .flat_map(|expr| expr.gather_return_statements())
@@ -122,6 +122,8 @@ impl ProjectionKind {
 pub struct TyStorageReassignment {
     pub fields: Vec<TyStorageReassignDescriptor>,
     pub(crate) ix: StateIndex,
+    /// The index of the element being reassigned in the case that the last field is an array.
+    pub index: Option<TyExpression>,
     pub rhs: TyExpression,
 }
 
@@ -130,6 +132,7 @@ impl PartialEqWithEngines for TyStorageReassignment {
     fn eq(&self, other: &Self, engines: Engines<'_>) -> bool {
         self.fields.eq(&other.fields, engines)
             && self.ix == other.ix
+            && self.index.eq(&other.index, engines)
             && self.rhs.eq(&other.rhs, engines)
     }
 }
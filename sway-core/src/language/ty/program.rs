@@ -1,4 +1,7 @@
-use sway_error::error::CompileError;
+use sway_error::{
+    error::CompileError,
+    warning::{CompileWarning, Warning},
+};
 use sway_types::*;
 
 use crate::{
@@ -7,6 +10,7 @@ use crate::{
     fuel_prelude::fuel_tx::StorageSlot,
     language::{parsed, ty::*, Purity},
     semantic_analysis::storage_only_types,
+    transform::AttributeKind,
     type_system::*,
     Engines,
 };
@@ -56,6 +60,8 @@ impl TyProgram {
         let mut mains = Vec::new();
         let mut declarations = Vec::<TyDeclaration>::new();
         let mut abi_entries = Vec::new();
+        let mut fallback_fns = Vec::new();
+        let mut hot_fns = Vec::new();
         let mut fn_declarations = std::collections::HashSet::new();
         for node in &root.all_nodes {
             match &node.content {
@@ -73,6 +79,14 @@ impl TyProgram {
                         mains.push(func.clone());
                     }
 
+                    if func.is_fallback() {
+                        fallback_fns.push(func.clone());
+                    }
+
+                    if func.is_hot() {
+                        hot_fns.push(func.clone());
+                    }
+
                     if !fn_declarations.insert(func.name.clone()) {
                         errors
                             .push(CompileError::MultipleDefinitionsOfFunction { name: func.name });
@@ -96,15 +110,16 @@ impl TyProgram {
                         warnings,
                         errors
                     );
-                    if matches!(
+                    let is_contract = matches!(
                         ty_engine.look_up_type_id(implementing_for_type_id),
                         TypeInfo::Contract
-                    ) {
-                        for method_id in methods {
-                            match declaration_engine.get_function(method_id, &span) {
-                                Ok(method) => abi_entries.push(method),
-                                Err(err) => errors.push(err),
-                            }
+                    );
+                    for method_id in methods {
+                        match declaration_engine.get_function(method_id, &span) {
+                            Ok(method) if is_contract => abi_entries.push(method),
+                            Ok(method) if method.is_hot() => hot_fns.push(method),
+                            Ok(_) => {}
+                            Err(err) => errors.push(err),
                         }
                     }
                 }
@@ -160,9 +175,43 @@ impl TyProgram {
             }
         }
 
+        // A fallback function may only be declared once, in a contract, and receives the call data
+        // via the call frame rather than as arguments.
+        if kind != parsed::TreeType::Contract {
+            errors.extend(
+                fallback_fns
+                    .iter()
+                    .map(|func| CompileError::FallbackFnInNonContract {
+                        name: func.name.clone(),
+                    }),
+            );
+        } else if let Some(func) = fallback_fns.get(1) {
+            errors.push(CompileError::MultipleFallbackFns {
+                name: func.name.clone(),
+            });
+        }
+        for func in &fallback_fns {
+            if !func.parameters.is_empty() || !func.type_parameters.is_empty() {
+                errors.push(CompileError::FallbackFnWithParameters {
+                    name: func.name.clone(),
+                });
+            }
+        }
+
+        // `#[hot]` only affects the dispatch of a contract's ABI methods.
+        warnings.extend(hot_fns.iter().map(|func| CompileWarning {
+            span: func.attributes[&AttributeKind::Hot][0].span.clone(),
+            warning_content: Warning::HotAttributeOutsideContractAbi {
+                fn_name: func.name.clone(),
+            },
+        }));
+
         // Perform other validation based on the tree type.
         let typed_program_kind = match kind {
-            parsed::TreeType::Contract => TyProgramKind::Contract { abi_entries },
+            parsed::TreeType::Contract => TyProgramKind::Contract {
+                abi_entries,
+                fallback_fn: fallback_fns.into_iter().next(),
+            },
             parsed::TreeType::Library { name } => TyProgramKind::Library { name },
             parsed::TreeType::Predicate => {
                 // A predicate must have a main function and that function must return a boolean.
@@ -258,7 +307,8 @@ impl TyProgram {
         let mut errors = vec![];
         let declaration_engine = ctx.declaration_engine;
         // Get all of the entry points for this tree type. For libraries, that's everything
-        // public. For contracts, ABI entries. For scripts and predicates, any function named `main`.
+        // public. For contracts, ABI entries and the fallback function. For scripts and predicates,
+        // any function named `main`.
         let metadata = match &self.kind {
             TyProgramKind::Library { .. } => {
                 let mut ret = vec![];
@@ -338,7 +388,10 @@ impl TyProgram {
                 }
                 data
             }
-            TyProgramKind::Contract { abi_entries, .. } => {
+            TyProgramKind::Contract {
+                abi_entries,
+                fallback_fn,
+            } => {
                 let mut data = vec![];
                 for node in self.root.all_nodes.iter() {
                     let is_test = check!(
@@ -356,7 +409,7 @@ impl TyProgram {
                         ));
                     }
                 }
-                for entry in abi_entries.iter() {
+                for entry in abi_entries.iter().chain(fallback_fn) {
                     data.append(&mut check!(
                         entry.collect_types_metadata(ctx),
                         return err(warnings, errors),
@@ -406,15 +459,34 @@ impl TyProgram {
                     messages_types: Some(messages_types),
                 }
             }
-            _ => fuels_types::ProgramABI {
-                types: vec![],
-                functions: vec![],
-                logged_types: None,
-                messages_types: None,
-            },
+            TyProgramKind::Library { .. } => {
+                // Libraries have no entry points of their own, however the types logged within
+                // their unit tests are still required in order to decode test logs.
+                let logged_types = self.generate_json_logged_types(type_engine, types);
+                let messages_types = self.generate_json_messages_types(type_engine, types);
+                fuels_types::ProgramABI {
+                    types: types.to_vec(),
+                    functions: vec![],
+                    logged_types: Some(logged_types),
+                    messages_types: Some(messages_types),
+                }
+            }
         }
     }
 
+    /// Generate the JSON ABI of each of the program's test functions, allowing the inputs of tests
+    /// that declare parameters to be ABI-encoded.
+    pub fn generate_json_abi_test_functions(
+        &self,
+        type_engine: &TypeEngine,
+        declaration_engine: &DeclarationEngine,
+        types: &mut Vec<fuels_types::TypeDeclaration>,
+    ) -> Vec<fuels_types::ABIFunction> {
+        self.test_fns(declaration_engine)
+            .map(|(test_fn, _)| test_fn.generate_json_abi_function(type_engine, types))
+            .collect()
+    }
+
     fn generate_json_logged_types(
         &self,
         type_engine: &TypeEngine,
@@ -499,6 +571,8 @@ impl TyProgram {
 pub enum TyProgramKind {
     Contract {
         abi_entries: Vec<TyFunctionDeclaration>,
+        /// The function called when no ABI method matches the function selector, if any.
+        fallback_fn: Option<TyFunctionDeclaration>,
     },
     Library {
         name: Ident,
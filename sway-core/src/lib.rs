@@ -71,8 +71,14 @@ pub fn parse(
         None => parse_in_memory(h, engines, input),
         // When a `BuildConfig` is given,
         // the module source may declare `dep`s that must be parsed from other files.
-        Some(config) => parse_module_tree(h, engines, input, config.canonical_root_module())
-            .map(|(kind, root)| parsed::ParseProgram { kind, root }),
+        Some(config) => {
+            let cfg = to_parsed_lang::CfgContext {
+                include_tests: config.include_tests,
+                features: &config.features,
+            };
+            parse_module_tree(h, engines, input, config.canonical_root_module(), &cfg)
+                .map(|(kind, root)| parsed::ParseProgram { kind, root })
+        }
     })
 }
 
@@ -91,7 +97,13 @@ fn parse_in_memory(
     engines: Engines<'_>,
     src: Arc<str>,
 ) -> Result<parsed::ParseProgram, ErrorEmitted> {
-    let module = sway_parse::parse_file(handler, src, None)?;
+    let mut module = sway_parse::parse_file(handler, src, None)?;
+    // Without a build config, tests are always included and no features are enabled.
+    let cfg = to_parsed_lang::CfgContext {
+        include_tests: true,
+        features: &Default::default(),
+    };
+    to_parsed_lang::strip_disabled_items(handler, &cfg, &mut module);
     let (kind, tree) = to_parsed_lang::convert_parse_tree(handler, engines, module)?;
     let submodules = Default::default();
     let root = parsed::ParseModule { tree, submodules };
@@ -104,6 +116,7 @@ fn parse_submodules(
     engines: Engines<'_>,
     module: &sway_ast::Module,
     module_dir: &Path,
+    cfg: &to_parsed_lang::CfgContext,
 ) -> Vec<(Ident, parsed::ParseSubmodule)> {
     // Assume the happy path, so there'll be as many submodules as dependencies, but no more.
     let mut submods = Vec::with_capacity(module.dependencies().count());
@@ -125,7 +138,7 @@ fn parse_submodules(
         };
 
         if let Ok((kind, module)) =
-            parse_module_tree(handler, engines, dep_str.clone(), dep_path.clone())
+            parse_module_tree(handler, engines, dep_str.clone(), dep_path.clone(), cfg)
         {
             let library_name = match kind {
                 parsed::TreeType::Library { name } => name,
@@ -153,19 +166,23 @@ fn parse_submodules(
 
 /// Given the source of the module along with its path,
 /// parse this module including all of its submodules.
+///
+/// Items whose `#[cfg(...)]` predicates are not satisfied are omitted.
 fn parse_module_tree(
     handler: &Handler,
     engines: Engines<'_>,
     src: Arc<str>,
     path: Arc<PathBuf>,
+    cfg: &to_parsed_lang::CfgContext,
 ) -> Result<(parsed::TreeType, parsed::ParseModule), ErrorEmitted> {
-    // Parse this module first.
+    // Parse this module first, omitting disabled items so that disabled submodules are skipped.
     let module_dir = path.parent().expect("module file has no parent directory");
-    let module = sway_parse::parse_file(handler, src, Some(path.clone()))?;
+    let mut module = sway_parse::parse_file(handler, src, Some(path.clone()))?;
+    to_parsed_lang::strip_disabled_items(handler, cfg, &mut module);
 
     // Parse all submodules before converting to the `ParseTree`.
     // This always recovers on parse errors for the file itself by skipping that file.
-    let submodules = parse_submodules(handler, engines, &module, module_dir);
+    let submodules = parse_submodules(handler, engines, &module, module_dir, cfg);
 
     // Convert from the raw parsed module to the `ParseTree` ready for type-check.
     let (kind, tree) = to_parsed_lang::convert_parse_tree(handler, engines, module)?;
@@ -523,14 +540,6 @@ pub fn inline_function_calls(
             None => {}
         }
 
-        // For now, pending improvements to ASMgen for calls, we must inline any function which has
-        // too many args.
-        if func.args_iter(ctx).count() as u8
-            > crate::asm_generation::compiler_constants::NUM_ARG_REGISTERS
-        {
-            return true;
-        }
-
         // If the function is called only once then definitely inline it.
         if call_counts.get(func).copied().unwrap_or(0) == 1 {
             return true;
@@ -28,6 +28,8 @@ pub(crate) struct MetadataManager {
     storage_key_md_cache: HashMap<u64, MetadataIndex>,
     inline_md_cache: HashMap<Inline, MetadataIndex>,
     test_decl_index_md_cache: HashMap<usize, MetadataIndex>,
+    hot_md: Option<MetadataIndex>,
+    fallback_md: Option<MetadataIndex>,
 }
 
 #[derive(Clone, Copy)]
@@ -137,6 +139,28 @@ impl MetadataManager {
         })
     }
 
+    /// Whether the metadata index marks a contract method as hot.
+    pub(crate) fn md_is_hot(&self, context: &Context, md_idx: Option<MetadataIndex>) -> bool {
+        Self::for_each_md_idx(context, md_idx, |md_idx| {
+            md_idx
+                .get_content(context)
+                .unwrap_struct("hot", 0)
+                .map(|_| ())
+        })
+        .is_some()
+    }
+
+    /// Whether the metadata index marks a contract entry as the fallback function.
+    pub(crate) fn md_is_fallback(&self, context: &Context, md_idx: Option<MetadataIndex>) -> bool {
+        Self::for_each_md_idx(context, md_idx, |md_idx| {
+            md_idx
+                .get_content(context)
+                .unwrap_struct("fallback", 0)
+                .map(|_| ())
+        })
+        .is_some()
+    }
+
     /// Gets Inline information from metadata index.
     pub(crate) fn md_to_inline(
         &mut self,
@@ -324,6 +348,22 @@ impl MetadataManager {
         )
     }
 
+    /// Marks a contract method as hot, to be checked first when dispatching on its selector.
+    pub(crate) fn hot_to_md(&mut self, context: &mut Context) -> Option<MetadataIndex> {
+        Some(*self.hot_md.get_or_insert_with(|| {
+            // Create new metadatum.
+            MetadataIndex::new_struct(context, "hot", Vec::new())
+        }))
+    }
+
+    /// Marks a contract entry as the fallback function, called when no selector matches.
+    pub(crate) fn fallback_to_md(&mut self, context: &mut Context) -> Option<MetadataIndex> {
+        Some(*self.fallback_md.get_or_insert_with(|| {
+            // Create new metadatum.
+            MetadataIndex::new_struct(context, "fallback", Vec::new())
+        }))
+    }
+
     fn file_location_to_md(
         &mut self,
         context: &mut Context,
@@ -7,6 +7,7 @@ use crate::{
     error::*,
     language::{parsed::*, ty, Visibility},
     semantic_analysis::*,
+    transform,
     type_system::*,
 };
 use sway_types::{style::is_snake_case, Spanned};
@@ -48,6 +49,12 @@ impl ty::TyFunctionDeclaration {
 
         // create a namespace for the function
         let mut fn_namespace = ctx.namespace.clone();
+
+        // Constants provided only to tests, e.g. a contract's own `CONTRACT_ID`, are only in scope
+        // within test functions.
+        if attributes.contains_key(&transform::AttributeKind::Test) {
+            fn_namespace.import_test_constants();
+        }
         let mut fn_ctx = ctx.by_ref().scoped(&mut fn_namespace).with_purity(purity);
 
         // type check the type parameters, which will also insert them into the namespace
@@ -360,11 +360,18 @@ impl ty::TyImplTrait {
                     )?
                 }
                 ty::TyExpressionVariant::StorageReassignment(storage_reassignment) => {
-                    expr_contains_get_storage_index(
-                        declaration_engine,
-                        &storage_reassignment.rhs,
-                        access_span,
-                    )?
+                    let index_contains_get_storage_index = match &storage_reassignment.index {
+                        Some(index) => {
+                            expr_contains_get_storage_index(declaration_engine, index, access_span)?
+                        }
+                        None => false,
+                    };
+                    index_contains_get_storage_index
+                        || expr_contains_get_storage_index(
+                            declaration_engine,
+                            &storage_reassignment.rhs,
+                            access_span,
+                        )?
                 }
                 ty::TyExpressionVariant::Return(exp) => {
                     expr_contains_get_storage_index(declaration_engine, exp, access_span)?
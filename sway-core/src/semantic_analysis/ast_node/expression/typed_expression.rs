@@ -450,6 +450,10 @@ impl ty::TyExpression {
                 });
                 ty::TyExpression::error(name.span(), engines)
             }
+            None if ctx.namespace.test_constants().contains_key(&name) => {
+                errors.push(CompileError::TestConstantOutsideOfTest { name: name.clone() });
+                ty::TyExpression::error(name.span(), engines)
+            }
             None => {
                 errors.push(CompileError::UnknownVariable {
                     var_name: name.clone(),
@@ -1863,33 +1867,48 @@ impl ty::TyExpression {
                 )
             }
             ReassignmentTarget::StorageField(fields) => {
-                let ctx = ctx
-                    .with_type_annotation(
-                        type_engine.insert_type(declaration_engine, TypeInfo::Unknown),
-                    )
-                    .with_help_text("");
-                let reassignment = check!(
-                    reassign_storage_subfield(ctx, fields, rhs, span.clone()),
-                    return err(warnings, errors),
-                    warnings,
-                    errors,
-                );
-                ok(
-                    ty::TyExpression {
-                        expression: ty::TyExpressionVariant::StorageReassignment(Box::new(
-                            reassignment,
-                        )),
-                        return_type: type_engine
-                            .insert_type(declaration_engine, TypeInfo::Tuple(Vec::new())),
-                        span,
-                    },
-                    warnings,
-                    errors,
-                )
+                Self::type_check_storage_reassignment(ctx, fields, None, rhs, span)
+            }
+            ReassignmentTarget::StorageArrayElement { fields, index } => {
+                Self::type_check_storage_reassignment(ctx, fields, Some(*index), rhs, span)
             }
         }
     }
 
+    fn type_check_storage_reassignment(
+        ctx: TypeCheckContext,
+        fields: Vec<Ident>,
+        index: Option<Expression>,
+        rhs: Expression,
+        span: Span,
+    ) -> CompileResult<Self> {
+        let mut errors = vec![];
+        let mut warnings = vec![];
+
+        let type_engine = ctx.type_engine;
+        let declaration_engine = ctx.declaration_engine;
+
+        let ctx = ctx
+            .with_type_annotation(type_engine.insert_type(declaration_engine, TypeInfo::Unknown))
+            .with_help_text("");
+        let reassignment = check!(
+            reassign_storage_subfield(ctx, fields, index, rhs, span.clone()),
+            return err(warnings, errors),
+            warnings,
+            errors,
+        );
+        ok(
+            ty::TyExpression {
+                expression: ty::TyExpressionVariant::StorageReassignment(Box::new(reassignment)),
+                return_type: type_engine
+                    .insert_type(declaration_engine, TypeInfo::Tuple(Vec::new())),
+                span,
+            },
+            warnings,
+            errors,
+        )
+    }
+
     fn resolve_numeric_literal(
         ctx: TypeCheckContext,
         lit: Literal,
@@ -16,7 +16,7 @@ use crate::{
 };
 
 use sway_error::{error::CompileError, warning::Warning};
-use sway_types::{span::Span, state::StateIndex, Spanned};
+use sway_types::{integer_bits::IntegerBits, span::Span, state::StateIndex, Spanned};
 
 impl ty::TyAstNode {
     pub(crate) fn type_check(ctx: TypeCheckContext, node: AstNode) -> CompileResult<Self> {
@@ -104,9 +104,12 @@ impl ty::TyAstNode {
     }
 }
 
+/// Type check the reassignment of the storage field named by `fields`, or of the element at `index`
+/// in the case that the storage field is an array.
 pub(crate) fn reassign_storage_subfield(
-    ctx: TypeCheckContext,
+    mut ctx: TypeCheckContext,
     fields: Vec<Ident>,
+    index: Option<Expression>,
     rhs: Expression,
     span: Span,
 ) -> CompileResult<ty::TyStorageReassignment> {
@@ -199,6 +202,33 @@ pub(crate) fn reassign_storage_subfield(
             }
         }
     }
+    let index = match index {
+        Some(index) => match type_engine.look_up_type_id(curr_type) {
+            TypeInfo::Array(elem_type, _) => {
+                curr_type = elem_type.type_id;
+                let type_info_u64 = TypeInfo::UnsignedInteger(IntegerBits::SixtyFour);
+                let ctx = ctx.by_ref().with_help_text("").with_type_annotation(
+                    type_engine.insert_type(declaration_engine, type_info_u64),
+                );
+                Some(check!(
+                    ty::TyExpression::type_check(ctx, index),
+                    return err(warnings, errors),
+                    warnings,
+                    errors
+                ))
+            }
+            actually => {
+                let names: Vec<_> = type_checked_buf.iter().map(|f| f.name.as_str()).collect();
+                errors.push(CompileError::NotIndexable {
+                    name: format!("storage.{}", names.join(".")),
+                    span,
+                    actually: engines.help_out(actually).to_string(),
+                });
+                return err(warnings, errors);
+            }
+        },
+        None => None,
+    };
     let ctx = ctx.with_type_annotation(curr_type).with_help_text("");
     let rhs = check!(
         ty::TyExpression::type_check(ctx, rhs),
@@ -211,6 +241,7 @@ pub(crate) fn reassign_storage_subfield(
         ty::TyStorageReassignment {
             fields: type_checked_buf,
             ix,
+            index,
             rhs,
         },
         warnings,
@@ -188,7 +188,12 @@ fn analyze_expression(
         Reassignment(reassgn) => analyze_expression(engines, &reassgn.rhs, block_name, warnings),
         StorageReassignment(reassgn) => {
             let storage_effs = HashSet::from([Effect::StorageWrite]);
-            let rhs_effs = analyze_expression(engines, &reassgn.rhs, block_name, warnings);
+            let rhs_effs = match &reassgn.index {
+                Some(index) => {
+                    analyze_two_expressions(engines, index, &reassgn.rhs, block_name, warnings)
+                }
+                None => analyze_expression(engines, &reassgn.rhs, block_name, warnings),
+            };
             if rhs_effs.contains(&Effect::Interaction) {
                 warn_after_interaction(
                     &storage_effs,
@@ -507,6 +512,9 @@ fn effects_of_expression(engines: Engines<'_>, expr: &ty::TyExpression) -> HashS
         },
         StorageReassignment(storage_reassign) => {
             let mut effs = HashSet::from([Effect::StorageWrite]);
+            if let Some(index) = &storage_reassign.index {
+                effs.extend(effects_of_expression(engines, index));
+            }
             effs.extend(effects_of_expression(engines, &storage_reassign.rhs));
             effs
         }
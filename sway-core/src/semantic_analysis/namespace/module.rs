@@ -19,7 +19,7 @@ use sway_ast::ItemConst;
 use sway_error::handler::Handler;
 use sway_error::{error::CompileError, handler::ErrorEmitted};
 use sway_parse::{lex, Parser};
-use sway_types::{span::Span, ConfigTimeConstant, Spanned};
+use sway_types::{constants::TEST_CONSTANT_NAME_PREFIX, span::Span, ConfigTimeConstant, Spanned};
 
 /// A single `Module` within a Sway project.
 ///
@@ -41,6 +41,11 @@ pub struct Module {
     pub(crate) submodules: im::OrdMap<ModuleName, Module>,
     /// The set of symbols, implementations, synonyms and aliases present within this module.
     items: Items,
+    /// Constants that are only in scope within the tests of this module, e.g. a contract's own
+    /// `CONTRACT_ID`.
+    ///
+    /// See [Module::insert_test_constants].
+    test_constants: SymbolMap,
 }
 
 impl Module {
@@ -58,6 +63,61 @@ impl Module {
         })
     }
 
+    /// Declare the given config-time constants within this module for use by its tests, replacing
+    /// any test constants of the same names.
+    ///
+    /// Test constants are kept apart from the module's symbols. They are only brought into scope
+    /// within test functions, where they are shadowed by any symbol of the same name declared or
+    /// imported by the module. Elsewhere, referring to a test constant is an error.
+    ///
+    /// Constants are resolved by name alone during IR generation, so each constant is declared
+    /// under its own name prefixed with [TEST_CONSTANT_NAME_PREFIX] while remaining accessible
+    /// via its given name. This way, a test constant never collides with a constant of the same
+    /// name declared within another module, e.g. the `CONTRACT_ID` of a contract dependency.
+    pub fn insert_test_constants(
+        &mut self,
+        engines: Engines<'_>,
+        constants: BTreeMap<String, ConfigTimeConstant>,
+    ) -> Result<(), vec1::Vec1<CompileError>> {
+        let declaration_engine = engines.de();
+        let test_constants = Module::default_with_constants(engines, constants)?;
+        for (name, decl) in test_constants.items.symbols {
+            let decl = match decl {
+                ty::TyDeclaration::ConstantDeclaration(decl_id) => {
+                    let mut const_decl = declaration_engine
+                        .get_constant(decl_id, &name.span())
+                        .map_err(|err| vec1::vec1![err])?;
+                    let prefixed_name = format!("{TEST_CONSTANT_NAME_PREFIX}{name}");
+                    const_decl.name = Ident::new_with_override(
+                        Box::leak(prefixed_name.into_boxed_str()),
+                        name.span(),
+                    );
+                    ty::TyDeclaration::ConstantDeclaration(
+                        declaration_engine.insert_constant(const_decl),
+                    )
+                }
+                decl => decl,
+            };
+            self.test_constants.insert(name, decl);
+        }
+        Ok(())
+    }
+
+    /// Immutable access to the constants that are only in scope within the tests of this module.
+    pub fn test_constants(&self) -> &SymbolMap {
+        &self.test_constants
+    }
+
+    /// Bring the test constants of this module into scope, unless shadowed by a symbol of the same
+    /// name.
+    pub(crate) fn import_test_constants(&mut self) {
+        for (name, decl) in self.test_constants.clone() {
+            if !self.items.symbols.contains_key(&name) {
+                self.items.symbols.insert(name, decl);
+            }
+        }
+    }
+
     fn default_with_constants_inner(
         handler: &Handler,
         engines: Engines<'_>,
@@ -120,6 +120,9 @@ fn expr_validate(engines: Engines<'_>, expr: &ty::TyExpression) -> CompileResult
                 warnings,
                 errors,
             );
+            if let Some(index) = &storage_reassignment.index {
+                check!(expr_validate(engines, index), (), warnings, errors);
+            }
             check!(expr_validate(engines, rhs), (), warnings, errors)
         }
         ty::TyExpressionVariant::Return(exp) => {
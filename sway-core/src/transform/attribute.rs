@@ -19,9 +19,30 @@
 //! is
 //!
 //!   #[foo(bar, bar)]
+//!
+//! Args may optionally be assigned a literal value, e.g.,
+//!
+//!   #[test(should_revert = "42")]
 
 use std::{collections::HashMap, sync::Arc};
-use sway_types::{Ident, Span};
+use sway_ast::Literal;
+use sway_types::{Ident, Span, Spanned};
+
+/// An attribute argument with a name (i.e "read", "should_revert"),
+/// an optional literal value and
+/// a span from its declaration.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttributeArg {
+    pub name: Ident,
+    pub value: Option<Literal>,
+    pub span: Span,
+}
+
+impl Spanned for AttributeArg {
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
 
 /// An attribute has a name (i.e "doc", "storage"),
 /// a vector of possible arguments and
@@ -29,7 +50,7 @@ use sway_types::{Ident, Span};
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Attribute {
     pub name: Ident,
-    pub args: Vec<Ident>,
+    pub args: Vec<AttributeArg>,
     pub span: Span,
 }
 
@@ -42,27 +63,33 @@ pub enum AttributeKind {
     Inline,
     Test,
     Payable,
+    Hot,
+    Fallback,
+    Cfg,
 }
 
 /// Stores the attributes associated with the type.
 pub type AttributesMap = Arc<HashMap<AttributeKind, Vec<Attribute>>>;
 
+/// Produce the attributes included within the JSON ABI.
+///
+/// `cfg` and `hot` attributes only affect compilation, so are omitted.
 pub(crate) fn generate_json_abi_attributes_map(
     attr_map: &AttributesMap,
 ) -> Option<Vec<fuels_types::Attribute>> {
-    if attr_map.is_empty() {
+    let attributes: Vec<_> = attr_map
+        .iter()
+        .filter(|(attr_kind, _attrs)| !matches!(attr_kind, AttributeKind::Cfg | AttributeKind::Hot))
+        .flat_map(|(_attr_kind, attrs)| {
+            attrs.iter().map(|attr| fuels_types::Attribute {
+                name: attr.name.to_string(),
+                arguments: attr.args.iter().map(|arg| arg.name.to_string()).collect(),
+            })
+        })
+        .collect();
+    if attributes.is_empty() {
         None
     } else {
-        Some(
-            attr_map
-                .iter()
-                .flat_map(|(_attr_kind, attrs)| {
-                    attrs.iter().map(|attr| fuels_types::Attribute {
-                        name: attr.name.to_string(),
-                        arguments: attr.args.iter().map(|arg| arg.to_string()).collect(),
-                    })
-                })
-                .collect(),
-        )
+        Some(attributes)
     }
 }
@@ -0,0 +1,167 @@
+//! Conditional compilation via the `#[cfg(...)]` attribute.
+//!
+//! Items whose `cfg` predicates are not satisfied are removed from the module before it is
+//! converted to the parsed AST, such that they are neither type-checked nor compiled. This includes
+//! `dep` declarations, in which case the submodule is never parsed.
+//!
+//! The supported predicates are:
+//!
+//!   #[cfg(test)]                    - satisfied when tests are included, e.g. by `forc test`.
+//!   #[cfg(feature = "logging")]      - satisfied when the package's `logging` feature is enabled.
+//!   #[cfg(not(feature = "logging"))] - satisfied when the nested predicate is not.
+//!   #[cfg(all(test, feature = "a"))] - satisfied when all nested predicates are.
+//!   #[cfg(any(test, feature = "a"))] - satisfied when at least one nested predicate is.
+//!
+//! An item with more than one predicate, either within a single `cfg` attribute or across multiple
+//! `cfg` attributes, is only included in the case that all of its predicates are satisfied.
+
+use std::collections::BTreeSet;
+use sway_ast::{
+    attribute::{Annotated, AttributeArg},
+    AttributeDecl, ItemKind, Literal, Module, Punctuated,
+};
+use sway_error::convert_parse_tree_error::ConvertParseTreeError;
+use sway_error::handler::Handler;
+use sway_types::{
+    constants::{
+        CFG_ALL_NAME, CFG_ANY_NAME, CFG_ATTRIBUTE_NAME, CFG_FEATURE_NAME, CFG_NOT_NAME,
+        CFG_TEST_NAME,
+    },
+    Spanned,
+};
+
+/// The configuration against which `cfg` predicates are evaluated.
+pub(crate) struct CfgContext<'a> {
+    /// Whether or not tests are included, satisfying `#[cfg(test)]`.
+    pub(crate) include_tests: bool,
+    /// The enabled features of the package.
+    pub(crate) features: &'a BTreeSet<String>,
+}
+
+/// Remove all items from the module whose `cfg` predicates are not satisfied, including the
+/// items nested within structs, enums, storage, traits, ABIs and impls.
+///
+/// Invalid predicates are reported as errors, in which case the item is retained.
+pub(crate) fn strip_disabled_items(handler: &Handler, cfg: &CfgContext, module: &mut Module) {
+    let enabled = |attribute_list: &[AttributeDecl]| is_enabled(handler, cfg, attribute_list);
+    module.items.retain(|item| enabled(&item.attribute_list));
+    for item in &mut module.items {
+        match &mut item.value {
+            ItemKind::Struct(item_struct) => {
+                retain_annotated(&mut item_struct.fields.inner, enabled)
+            }
+            ItemKind::Enum(item_enum) => retain_annotated(&mut item_enum.fields.inner, enabled),
+            ItemKind::Storage(item_storage) => {
+                retain_annotated(&mut item_storage.fields.inner, enabled)
+            }
+            ItemKind::Impl(item_impl) => item_impl
+                .contents
+                .inner
+                .retain(|item_fn| enabled(&item_fn.attribute_list)),
+            ItemKind::Trait(item_trait) => {
+                item_trait
+                    .trait_items
+                    .inner
+                    .retain(|(fn_signature, _)| enabled(&fn_signature.attribute_list));
+                if let Some(trait_defs) = &mut item_trait.trait_defs_opt {
+                    trait_defs
+                        .inner
+                        .retain(|item_fn| enabled(&item_fn.attribute_list));
+                }
+            }
+            ItemKind::Abi(item_abi) => {
+                item_abi
+                    .abi_items
+                    .inner
+                    .retain(|(fn_signature, _)| enabled(&fn_signature.attribute_list));
+                if let Some(abi_defs) = &mut item_abi.abi_defs_opt {
+                    abi_defs
+                        .inner
+                        .retain(|item_fn| enabled(&item_fn.attribute_list));
+                }
+            }
+            ItemKind::Dependency(_) | ItemKind::Use(_) | ItemKind::Fn(_) | ItemKind::Const(_) => (),
+        }
+    }
+}
+
+/// Whether or not all `cfg` predicates within the given attributes are satisfied.
+fn is_enabled(handler: &Handler, cfg: &CfgContext, attribute_list: &[AttributeDecl]) -> bool {
+    let mut enabled = true;
+    let cfg_attrs = attribute_list
+        .iter()
+        .flat_map(|attr_decl| attr_decl.attribute.get())
+        .filter(|attr| attr.name.as_str() == CFG_ATTRIBUTE_NAME);
+    for attr in cfg_attrs {
+        let args: Vec<_> = match &attr.args {
+            Some(args) => args.get().into_iter().collect(),
+            None => vec![],
+        };
+        if args.is_empty() {
+            let error = ConvertParseTreeError::InvalidCfgPredicate { span: attr.span() };
+            handler.emit_err(error.into());
+        }
+        for arg in args {
+            // Invalid predicates are treated as satisfied so that the item is retained.
+            enabled &= eval_predicate(handler, cfg, arg).unwrap_or(true);
+        }
+    }
+    enabled
+}
+
+/// Evaluate a single `cfg` predicate, returning `None` in the case that it, or any of its nested
+/// predicates, is invalid.
+fn eval_predicate(handler: &Handler, cfg: &CfgContext, arg: &AttributeArg) -> Option<bool> {
+    let nested: Option<Vec<_>> = arg
+        .args
+        .as_ref()
+        .map(|args| args.get().into_iter().collect());
+    match (arg.name.as_str(), &arg.value_opt, nested) {
+        (CFG_TEST_NAME, None, None) => return Some(cfg.include_tests),
+        (CFG_FEATURE_NAME, Some((_eq_token, Literal::String(feature))), None) => {
+            return Some(cfg.features.contains(&feature.parsed))
+        }
+        (CFG_NOT_NAME, None, Some(nested)) if nested.len() == 1 => {
+            return eval_predicate(handler, cfg, nested[0]).map(|satisfied| !satisfied)
+        }
+        (CFG_ALL_NAME, None, Some(nested)) => {
+            return eval_nested(handler, cfg, nested).map(|results| results.iter().all(|b| *b))
+        }
+        (CFG_ANY_NAME, None, Some(nested)) => {
+            return eval_nested(handler, cfg, nested).map(|results| results.iter().any(|b| *b))
+        }
+        _ => (),
+    }
+    let error = ConvertParseTreeError::InvalidCfgPredicate { span: arg.span() };
+    handler.emit_err(error.into());
+    None
+}
+
+/// Evaluate each of the nested predicates, reporting all invalid predicates rather than only the
+/// first.
+fn eval_nested(
+    handler: &Handler,
+    cfg: &CfgContext,
+    nested: Vec<&AttributeArg>,
+) -> Option<Vec<bool>> {
+    let results: Vec<_> = nested
+        .into_iter()
+        .map(|arg| eval_predicate(handler, cfg, arg))
+        .collect();
+    results.into_iter().collect()
+}
+
+/// Retain only those values for which `f` returns `true`.
+fn retain_annotated<T, P>(
+    punctuated: &mut Punctuated<Annotated<T>, P>,
+    mut f: impl FnMut(&[AttributeDecl]) -> bool,
+) {
+    punctuated
+        .value_separator_pairs
+        .retain(|(value, _)| f(&value.attribute_list));
+    if let Some(value) = &punctuated.final_value_opt {
+        if !f(&value.attribute_list) {
+            punctuated.final_value_opt = None;
+        }
+    }
+}
@@ -23,10 +23,11 @@ use sway_error::handler::{ErrorEmitted, Handler};
 use sway_error::warning::{CompileWarning, Warning};
 use sway_types::{
     constants::{
-        DESTRUCTURE_PREFIX, DOC_ATTRIBUTE_NAME, DOC_COMMENT_ATTRIBUTE_NAME, INLINE_ATTRIBUTE_NAME,
-        MATCH_RETURN_VAR_NAME_PREFIX, PAYABLE_ATTRIBUTE_NAME, STORAGE_PURITY_ATTRIBUTE_NAME,
-        STORAGE_PURITY_READ_NAME, STORAGE_PURITY_WRITE_NAME, TEST_ATTRIBUTE_NAME,
-        TUPLE_NAME_PREFIX, VALID_ATTRIBUTE_NAMES,
+        CFG_ATTRIBUTE_NAME, DESTRUCTURE_PREFIX, DOC_ATTRIBUTE_NAME, DOC_COMMENT_ATTRIBUTE_NAME,
+        FALLBACK_ATTRIBUTE_NAME, HOT_ATTRIBUTE_NAME, INLINE_ATTRIBUTE_NAME,
+        MATCH_RETURN_VAR_NAME_PREFIX, PAYABLE_ATTRIBUTE_NAME, STORAGE_FIELD_VAR_NAME_PREFIX,
+        STORAGE_PURITY_ATTRIBUTE_NAME, STORAGE_PURITY_READ_NAME, STORAGE_PURITY_WRITE_NAME,
+        TEST_ATTRIBUTE_NAME, TUPLE_NAME_PREFIX, VALID_ATTRIBUTE_NAMES,
     },
     integer_bits::IntegerBits,
 };
@@ -119,8 +120,13 @@ fn item_to_ast_nodes(
                 };
                 handler.emit_err(error.into());
             }
+            // Dependencies may only be conditionally compiled, in which case their `cfg`
+            // predicates have already been evaluated.
             for (attribute_kind, attributes) in attributes.iter() {
-                if attribute_kind != &AttributeKind::DocComment {
+                if !matches!(
+                    attribute_kind,
+                    AttributeKind::DocComment | AttributeKind::Cfg
+                ) {
                     for attribute in attributes {
                         let error = ConvertParseTreeError::CannotAnnotateDependency {
                             span: attribute.span.clone(),
@@ -443,7 +449,7 @@ fn get_attributed_purity(
     match attributes.get(&AttributeKind::Storage) {
         Some(attrs) if !attrs.is_empty() => {
             for arg in attrs.iter().flat_map(|attr| &attr.args) {
-                match arg.as_str() {
+                match arg.name.as_str() {
                     STORAGE_PURITY_READ_NAME => add_impurity(Purity::Reads, Purity::Writes),
                     STORAGE_PURITY_WRITE_NAME => add_impurity(Purity::Writes, Purity::Reads),
                     _otherwise => {
@@ -1735,6 +1741,20 @@ fn expr_to_expression(
             }),
             span,
         },
+        Expr::Reassignment {
+            assignable,
+            expr,
+            reassignment_op,
+        } if is_storage_projection_reassignment(&assignable, &reassignment_op) => {
+            storage_field_projection_reassignment_to_expression(
+                handler,
+                engines,
+                assignable,
+                reassignment_op,
+                expr,
+                span,
+            )?
+        }
         Expr::Reassignment {
             assignable,
             expr,
@@ -3069,27 +3089,264 @@ fn assignable_to_reassignment_target(
     engines: Engines<'_>,
     assignable: Assignable,
 ) -> Result<ReassignmentTarget, ErrorEmitted> {
+    if let Some(fields) = storage_field_names(&assignable) {
+        return Ok(ReassignmentTarget::StorageField(fields));
+    }
+    match assignable {
+        Assignable::Index { target, arg } if is_storage_field(&target) => {
+            let fields = storage_field_names(&target).expect("target is a storage field");
+            let index = Box::new(expr_to_expression(handler, engines, *arg.into_inner())?);
+            Ok(ReassignmentTarget::StorageArrayElement { fields, index })
+        }
+        assignable => {
+            let expression = assignable_to_expression(handler, engines, assignable)?;
+            Ok(ReassignmentTarget::VariableExpression(Box::new(expression)))
+        }
+    }
+}
+
+/// The names of the fields of the storage field named by the assignable, e.g. `[a, b]` for
+/// `storage.a.b`, if any.
+fn storage_field_names(assignable: &Assignable) -> Option<Vec<Ident>> {
     let mut idents = Vec::new();
-    let mut base = &assignable;
+    let mut base = assignable;
     loop {
         match base {
             Assignable::FieldProjection { target, name, .. } => {
-                idents.push(name);
+                idents.push(name.clone());
                 base = target;
             }
-            Assignable::Var(name) => {
-                if name.as_str() == "storage" {
-                    let idents = idents.into_iter().rev().cloned().collect();
-                    return Ok(ReassignmentTarget::StorageField(idents));
-                }
-                break;
+            Assignable::Var(name) if name.as_str() == "storage" => {
+                return Some(idents.into_iter().rev().collect());
             }
-            Assignable::Index { .. } => break,
-            Assignable::TupleFieldProjection { .. } => break,
+            Assignable::Var(_)
+            | Assignable::Index { .. }
+            | Assignable::TupleFieldProjection { .. } => return None,
+        }
+    }
+}
+
+/// Whether the assignable names a storage field, e.g. `storage.a.b`.
+fn is_storage_field(assignable: &Assignable) -> bool {
+    matches!(assignable, Assignable::FieldProjection { .. })
+        && storage_field_names(assignable).is_some()
+}
+
+/// Whether the assignable names an element of an array within a storage field, e.g.
+/// `storage.a.b[i]`.
+fn is_storage_array_element(assignable: &Assignable) -> bool {
+    matches!(assignable, Assignable::Index { target, .. } if is_storage_field(target))
+}
+
+/// Find the storage field or storage array element projected into by the assignable, if any. For
+/// example, `storage.a.b` for `storage.a.b.c`, or `storage.a[i]` for `storage.a[i].c`.
+fn storage_field_projection_base(assignable: &Assignable) -> Option<&Assignable> {
+    let mut base = assignable;
+    loop {
+        if is_storage_field(base) || is_storage_array_element(base) {
+            return Some(base);
+        }
+        match base {
+            Assignable::Var(_) => return None,
+            Assignable::Index { target, .. }
+            | Assignable::FieldProjection { target, .. }
+            | Assignable::TupleFieldProjection { target, .. } => base = target,
+        }
+    }
+}
+
+/// Whether the reassignment must be converted by
+/// [storage_field_projection_reassignment_to_expression], i.e. whether it reassigns a projection
+/// into a storage field or storage array element, or reassigns a storage array element using an
+/// operator such as `+=`.
+fn is_storage_projection_reassignment(
+    assignable: &Assignable,
+    reassignment_op: &ReassignmentOp,
+) -> bool {
+    if is_storage_field(assignable) {
+        false
+    } else if is_storage_array_element(assignable) {
+        !matches!(reassignment_op.variant, ReassignmentOpVariant::Equals)
+    } else {
+        storage_field_projection_base(assignable).is_some()
+    }
+}
+
+/// Storage fields can only be written as a whole, and elements of arrays within storage fields
+/// element by element, so a reassignment of a projection into either, such as `storage.a.b = v`
+/// where `b` is a tuple field, or `storage.a[i].b = v`, is converted into a block which reads the
+/// field or element into a variable, reassigns the projection into the variable instead, and writes
+/// the variable back:
+///
+/// ```ignore
+/// {
+///     let __storage_field_0_index = i;
+///     let mut __storage_field_0 = storage.a[__storage_field_0_index];
+///     __storage_field_0.b = v;
+///     storage.a[__storage_field_0_index] = __storage_field_0;
+/// }
+/// ```
+///
+/// The index of an element is bound to a variable so that it is only evaluated once.
+fn storage_field_projection_reassignment_to_expression(
+    handler: &Handler,
+    engines: Engines<'_>,
+    assignable: Assignable,
+    reassignment_op: ReassignmentOp,
+    expr: Box<Expr>,
+    span: Span,
+) -> Result<Expression, ErrorEmitted> {
+    let base = storage_field_projection_base(&assignable).expect("projection into storage field");
+    let base_span = base.span();
+
+    // Because the parser is single threaded, the name generated below will be stable.
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let var_name = format!(
+        "{}{}",
+        STORAGE_FIELD_VAR_NAME_PREFIX,
+        COUNTER.load(Ordering::SeqCst)
+    );
+    COUNTER.fetch_add(1, Ordering::SeqCst);
+    let new_var_ident = |name: String| {
+        Ident::new_with_override(Box::leak(name.into_boxed_str()), base_span.clone())
+    };
+    let index_var_name = new_var_ident(format!("{}_index", var_name));
+    let var_name = new_var_ident(var_name);
+
+    let mut contents = vec![];
+    let (read_kind, write_target) = match base {
+        Assignable::Index { target, arg } => {
+            let field_names = storage_field_names(target).expect("target is a storage field");
+            let index = expr_to_expression(handler, engines, *arg.inner.clone())?;
+            let index_span = index.span.clone();
+            contents.push(AstNode {
+                content: AstNodeContent::Declaration(Declaration::VariableDeclaration(
+                    VariableDeclaration {
+                        name: index_var_name.clone(),
+                        type_ascription: TypeInfo::Unknown,
+                        type_ascription_span: None,
+                        body: index,
+                        is_mutable: false,
+                    },
+                )),
+                span: span.clone(),
+            });
+            let index_var = || {
+                Box::new(Expression {
+                    kind: ExpressionKind::Variable(index_var_name.clone()),
+                    span: index_span.clone(),
+                })
+            };
+            let read_kind = ExpressionKind::ArrayIndex(ArrayIndexExpression {
+                prefix: Box::new(Expression {
+                    kind: ExpressionKind::StorageAccess(StorageAccessExpression {
+                        field_names: field_names.clone(),
+                    }),
+                    span: target.span(),
+                }),
+                index: index_var(),
+            });
+            let write_target = ReassignmentTarget::StorageArrayElement {
+                fields: field_names,
+                index: index_var(),
+            };
+            (read_kind, write_target)
+        }
+        _ => {
+            let field_names = storage_field_names(base).expect("base is a storage field");
+            let read_kind = ExpressionKind::StorageAccess(StorageAccessExpression {
+                field_names: field_names.clone(),
+            });
+            (read_kind, ReassignmentTarget::StorageField(field_names))
         }
+    };
+
+    let read_base = VariableDeclaration {
+        name: var_name.clone(),
+        type_ascription: TypeInfo::Unknown,
+        type_ascription_span: None,
+        body: Expression {
+            kind: read_kind,
+            span: base_span.clone(),
+        },
+        is_mutable: true,
+    };
+    let reassign_var = expr_to_expression(
+        handler,
+        engines,
+        Expr::Reassignment {
+            assignable: replace_storage_field_projection_base(assignable, &var_name),
+            reassignment_op,
+            expr,
+        },
+    )?;
+    let write_base = Expression {
+        kind: ExpressionKind::Reassignment(ReassignmentExpression {
+            lhs: write_target,
+            rhs: Box::new(Expression {
+                kind: ExpressionKind::Variable(var_name),
+                span: base_span,
+            }),
+        }),
+        span: span.clone(),
+    };
+
+    contents.extend([
+        AstNode {
+            content: AstNodeContent::Declaration(Declaration::VariableDeclaration(read_base)),
+            span: span.clone(),
+        },
+        AstNode {
+            content: AstNodeContent::Expression(reassign_var),
+            span: span.clone(),
+        },
+        AstNode {
+            content: AstNodeContent::Expression(write_base),
+            span: span.clone(),
+        },
+    ]);
+    Ok(Expression {
+        kind: ExpressionKind::CodeBlock(CodeBlock {
+            contents,
+            whole_block_span: span.clone(),
+        }),
+        span,
+    })
+}
+
+/// Replace the storage field or storage array element projected into by the assignable with the
+/// variable `var_name`.
+fn replace_storage_field_projection_base(assignable: Assignable, var_name: &Ident) -> Assignable {
+    if is_storage_field(&assignable) || is_storage_array_element(&assignable) {
+        return Assignable::Var(var_name.clone());
+    }
+    match assignable {
+        Assignable::Var(_) => assignable,
+        Assignable::Index { target, arg } => Assignable::Index {
+            target: Box::new(replace_storage_field_projection_base(*target, var_name)),
+            arg,
+        },
+        Assignable::FieldProjection {
+            target,
+            dot_token,
+            name,
+        } => Assignable::FieldProjection {
+            target: Box::new(replace_storage_field_projection_base(*target, var_name)),
+            dot_token,
+            name,
+        },
+        Assignable::TupleFieldProjection {
+            target,
+            dot_token,
+            field,
+            field_span,
+        } => Assignable::TupleFieldProjection {
+            target: Box::new(replace_storage_field_projection_base(*target, var_name)),
+            dot_token,
+            field,
+            field_span,
+        },
     }
-    let expression = assignable_to_expression(handler, engines, assignable)?;
-    Ok(ReassignmentTarget::VariableExpression(Box::new(expression)))
 }
 
 fn generic_args_to_type_arguments(
@@ -3262,7 +3519,20 @@ fn item_attrs_to_map(
             let args = attr
                 .args
                 .as_ref()
-                .map(|parens| parens.get().into_iter().cloned().collect())
+                .map(|parens| {
+                    parens
+                        .get()
+                        .into_iter()
+                        .map(|arg| AttributeArg {
+                            name: arg.name.clone(),
+                            value: arg
+                                .value_opt
+                                .as_ref()
+                                .map(|(_eq_token, value)| value.clone()),
+                            span: arg.span(),
+                        })
+                        .collect()
+                })
                 .unwrap_or_else(Vec::new);
 
             let attribute = Attribute {
@@ -3278,6 +3548,9 @@ fn item_attrs_to_map(
                 INLINE_ATTRIBUTE_NAME => Some(AttributeKind::Inline),
                 TEST_ATTRIBUTE_NAME => Some(AttributeKind::Test),
                 PAYABLE_ATTRIBUTE_NAME => Some(AttributeKind::Payable),
+                HOT_ATTRIBUTE_NAME => Some(AttributeKind::Hot),
+                FALLBACK_ATTRIBUTE_NAME => Some(AttributeKind::Fallback),
+                CFG_ATTRIBUTE_NAME => Some(AttributeKind::Cfg),
                 _ => None,
             } {
                 match attrs_map.get_mut(&attr_kind) {
@@ -1,3 +1,5 @@
+mod cfg;
 mod convert_parse_tree;
 
+pub(crate) use cfg::*;
 pub(crate) use convert_parse_tree::*;
@@ -97,6 +97,8 @@ pub enum ConvertParseTreeError {
     CannotAnnotateDependency { span: Span },
     #[error("Expected dependency at the beginning before any other items.")]
     ExpectedDependencyAtBeginning { span: Span },
+    #[error("invalid `cfg` predicate, expected one of `test`, `feature = \"<name>\"`, `not(..)`, `all(..)` or `any(..)`")]
+    InvalidCfgPredicate { span: Span },
 }
 
 impl Spanned for ConvertParseTreeError {
@@ -149,6 +151,7 @@ impl Spanned for ConvertParseTreeError {
             ConvertParseTreeError::CannotDocCommentDependency { span } => span.clone(),
             ConvertParseTreeError::CannotAnnotateDependency { span } => span.clone(),
             ConvertParseTreeError::ExpectedDependencyAtBeginning { span } => span.clone(),
+            ConvertParseTreeError::InvalidCfgPredicate { span } => span.clone(),
         }
     }
 }
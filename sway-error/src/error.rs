@@ -33,6 +33,8 @@ pub enum CompileError {
     UnknownVariable { var_name: Ident },
     #[error("Variable \"{var_name}\" does not exist in this scope.")]
     UnknownVariablePath { var_name: Ident, span: Span },
+    #[error("Constant \"{name}\" is only available within tests.")]
+    TestConstantOutsideOfTest { name: Ident },
     #[error("Function \"{name}\" does not exist in this scope.")]
     UnknownFunction { name: Ident, span: Span },
     #[error("Identifier \"{name}\" was used as a variable, but it is actually a {what_it_is}.")]
@@ -711,6 +713,15 @@ pub enum CompileError {
         missing_impl_attribute: bool,
         span: Span,
     },
+    #[error("Fallback functions are only allowed in contracts.")]
+    FallbackFnInNonContract { name: Ident },
+    #[error("Multiple fallback functions are declared in this contract.")]
+    MultipleFallbackFns { name: Ident },
+    #[error(
+        "Fallback function \"{name}\" cannot have parameters or type parameters. The call data \
+         may instead be read from the call frame, e.g. using `std::call_frames::second_param`."
+    )]
+    FallbackFnWithParameters { name: Ident },
 }
 
 impl std::convert::From<TypeError> for CompileError {
@@ -725,6 +736,7 @@ impl Spanned for CompileError {
         match self {
             UnknownVariable { var_name } => var_name.span(),
             UnknownVariablePath { span, .. } => span.clone(),
+            TestConstantOutsideOfTest { name } => name.span(),
             UnknownFunction { span, .. } => span.clone(),
             NotAVariable { name, .. } => name.span(),
             NotAFunction { span, .. } => span.clone(),
@@ -898,6 +910,9 @@ impl Spanned for CompileError {
             DisallowedWhileInPredicate { span } => span.clone(),
             CoinsPassedToNonPayableMethod { span, .. } => span.clone(),
             TraitImplPayabilityMismatch { span, .. } => span.clone(),
+            FallbackFnInNonContract { name } => name.span(),
+            MultipleFallbackFns { name } => name.span(),
+            FallbackFnWithParameters { name } => name.span(),
         }
     }
 }
@@ -97,6 +97,9 @@ pub enum Warning {
     StorageReadAfterInteraction {
         block_name: Ident,
     },
+    HotAttributeOutsideContractAbi {
+        fn_name: Ident,
+    },
 }
 
 impl fmt::Display for Warning {
@@ -226,6 +229,11 @@ impl fmt::Display for Warning {
             Consider making all storage writes before calling another contract"),
             StorageReadAfterInteraction {block_name} => write!(f, "Storage read after external contract interaction in function or method \"{block_name}\". \
             Consider making all storage reads before calling another contract"),
+            HotAttributeOutsideContractAbi { fn_name } => write!(
+                f,
+                "The #[hot] attribute of \"{fn_name}\" has no effect, as it only applies to the ABI \
+                 methods of contracts."
+            ),
         }
     }
 }
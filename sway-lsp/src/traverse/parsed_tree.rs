@@ -24,7 +24,10 @@ use sway_core::{
     type_system::{TypeArgument, TypeParameter},
     TypeEngine, TypeInfo,
 };
-use sway_types::constants::{DESTRUCTURE_PREFIX, MATCH_RETURN_VAR_NAME_PREFIX, TUPLE_NAME_PREFIX};
+use sway_types::constants::{
+    DESTRUCTURE_PREFIX, MATCH_RETURN_VAR_NAME_PREFIX, STORAGE_FIELD_VAR_NAME_PREFIX,
+    TUPLE_NAME_PREFIX,
+};
 use sway_types::{Ident, Span, Spanned};
 
 pub struct ParsedTree<'a> {
@@ -83,12 +86,17 @@ impl<'a> ParsedTree<'a> {
         match declaration {
             Declaration::VariableDeclaration(variable) => {
                 // Don't collect tokens if the ident's name contains __tuple_ || __match_return_var_name_
+                // || __storage_field_
                 // The individual elements are handled in the subsequent VariableDeclaration's
                 if !variable.name.as_str().contains(TUPLE_NAME_PREFIX)
                     && !variable
                         .name
                         .as_str()
                         .contains(MATCH_RETURN_VAR_NAME_PREFIX)
+                    && !variable
+                        .name
+                        .as_str()
+                        .contains(STORAGE_FIELD_VAR_NAME_PREFIX)
                 {
                     let symbol_kind = if variable.name.as_str().contains(DESTRUCTURE_PREFIX) {
                         SymbolKind::Struct
@@ -372,6 +380,7 @@ impl<'a> ParsedTree<'a> {
             ExpressionKind::Variable(name) => {
                 if !name.as_str().contains(TUPLE_NAME_PREFIX)
                     && !name.as_str().contains(MATCH_RETURN_VAR_NAME_PREFIX)
+                    && !name.as_str().contains(STORAGE_FIELD_VAR_NAME_PREFIX)
                 {
                     let symbol_kind = if name.as_str().contains(DESTRUCTURE_PREFIX) {
                         SymbolKind::Struct
@@ -660,6 +669,18 @@ impl<'a> ParsedTree<'a> {
                             );
                         }
                     }
+                    ReassignmentTarget::StorageArrayElement { fields, index } => {
+                        for ident in fields {
+                            self.tokens.insert(
+                                to_ident_key(ident),
+                                Token::from_parsed(
+                                    AstToken::Reassignment(reassignment.clone()),
+                                    SymbolKind::Field,
+                                ),
+                            );
+                        }
+                        self.handle_expression(index);
+                    }
                 }
             }
             ExpressionKind::Return(expr) => self.handle_expression(expr),
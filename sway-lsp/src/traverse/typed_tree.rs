@@ -570,6 +570,9 @@ impl<'a> TypedTree<'a> {
                         ));
                     }
                 }
+                if let Some(index) = &storage_reassignment.index {
+                    self.handle_expression(index);
+                }
                 self.handle_expression(&storage_reassignment.rhs);
             }
             ty::TyExpressionVariant::Return(exp) => self.handle_expression(exp),
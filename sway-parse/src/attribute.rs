@@ -1,9 +1,9 @@
 use crate::priv_prelude::{Peek, Peeker};
 use crate::{Parse, ParseBracket, ParseResult, ParseToEnd, Parser, ParserConsumed};
 
-use sway_ast::attribute::{Annotated, Attribute, AttributeDecl};
+use sway_ast::attribute::{Annotated, Attribute, AttributeArg, AttributeDecl};
 use sway_ast::brackets::{Parens, SquareBrackets};
-use sway_ast::keywords::{HashToken, StorageToken, Token};
+use sway_ast::keywords::{EqToken, HashToken, StorageToken, Token};
 use sway_ast::punctuated::Punctuated;
 use sway_ast::token::{DocComment, DocStyle};
 use sway_error::parser_error::ParseErrorKind;
@@ -47,7 +47,11 @@ impl<T: Parse> Parse for Annotated<T> {
                             doc_comment.span.clone(),
                         ),
                         args: Some(Parens::new(
-                            Punctuated::single(value),
+                            Punctuated::single(AttributeArg {
+                                name: value,
+                                value_opt: None,
+                                args: None,
+                            }),
                             doc_comment.content_span,
                         )),
                     }),
@@ -90,6 +94,25 @@ impl Parse for Attribute {
     }
 }
 
+impl Parse for AttributeArg {
+    fn parse(parser: &mut Parser) -> ParseResult<Self> {
+        let name = parser.parse()?;
+        let args = Parens::try_parse(parser)?;
+        let value_opt = match (&args, parser.take::<EqToken>()) {
+            (None, Some(eq_token)) => Some((eq_token, parser.parse()?)),
+            (Some(_), Some(_)) => {
+                return Err(parser.emit_error(ParseErrorKind::UnexpectedTokenAfterAttribute))
+            }
+            (_, None) => None,
+        };
+        Ok(AttributeArg {
+            name,
+            value_opt,
+            args,
+        })
+    }
+}
+
 impl ParseToEnd for Attribute {
     fn parse_to_end<'a, 'e>(mut parser: Parser<'a, 'e>) -> ParseResult<(Self, ParserConsumed<'a>)> {
         let attrib = parser.parse()?;
@@ -165,6 +165,7 @@ mod tests {
     use super::*;
     use std::sync::Arc;
     use sway_ast::{AttributeDecl, Item};
+    use sway_types::Spanned;
 
     fn parse_item(input: &str) -> Item {
         let handler = <_>::default();
@@ -188,9 +189,9 @@ mod tests {
                     .map(|att| {
                         (
                             att.name.as_str(),
-                            att.args
-                                .as_ref()
-                                .map(|arg| arg.get().into_iter().map(|a| a.as_str()).collect()),
+                            att.args.as_ref().map(|arg| {
+                                arg.get().into_iter().map(|a| a.name.as_str()).collect()
+                            }),
                         )
                     })
                     .collect()
@@ -399,6 +400,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_attributes_fn_args_with_values() {
+        let item = parse_item(
+            r#"
+            #[foo(one = "1", two, three = 3)]
+            fn f() -> bool {
+                false
+            }
+            "#,
+        );
+
+        assert!(matches!(item.value, ItemKind::Fn(_)));
+        assert_eq!(
+            attributes(&item.attribute_list),
+            vec![[("foo", Some(vec!["one", "two", "three"]))]]
+        );
+
+        let values: Vec<Option<String>> = item.attribute_list[0]
+            .attribute
+            .get()
+            .into_iter()
+            .flat_map(|attr| attr.args.as_ref().unwrap().get().into_iter())
+            .map(|arg| {
+                arg.value_opt
+                    .as_ref()
+                    .map(|(_eq_token, value)| value.span().as_str().to_string())
+            })
+            .collect();
+        assert_eq!(
+            values,
+            vec![Some("\"1\"".to_string()), None, Some("3".to_string())]
+        );
+    }
+
     #[test]
     fn parse_attributes_fn_zero_one_and_three_args() {
         let item = parse_item(
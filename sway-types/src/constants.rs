@@ -27,6 +27,14 @@ pub const DESTRUCTURE_PREFIX: &str = "__destructure_";
 /// The default prefix for the compiler generated names of match
 pub const MATCH_RETURN_VAR_NAME_PREFIX: &str = "__match_return_var_name_";
 
+/// The default prefix for the compiler generated names of storage fields read into a variable, such
+/// that a projection into them can be reassigned
+pub const STORAGE_FIELD_VAR_NAME_PREFIX: &str = "__storage_field_";
+
+/// The prefix for the compiler generated names of constants provided only to tests, such that they
+/// don't collide with constants of the same name declared within other modules
+pub const TEST_CONSTANT_NAME_PREFIX: &str = "__test_const_";
+
 /// The valid attribute strings related to storage and purity.
 pub const STORAGE_PURITY_ATTRIBUTE_NAME: &str = "storage";
 pub const STORAGE_PURITY_READ_NAME: &str = "read";
@@ -49,6 +57,22 @@ pub const TEST_ATTRIBUTE_NAME: &str = "test";
 /// The valid attribute string used for payable functions.
 pub const PAYABLE_ATTRIBUTE_NAME: &str = "payable";
 
+/// The valid attribute string used for contract methods which are checked first when dispatching
+/// on the function selector.
+pub const HOT_ATTRIBUTE_NAME: &str = "hot";
+
+/// The valid attribute string used for the contract function which is called when no ABI method
+/// matches the function selector.
+pub const FALLBACK_ATTRIBUTE_NAME: &str = "fallback";
+
+/// The valid attribute strings related to conditional compilation.
+pub const CFG_ATTRIBUTE_NAME: &str = "cfg";
+pub const CFG_TEST_NAME: &str = "test";
+pub const CFG_FEATURE_NAME: &str = "feature";
+pub const CFG_NOT_NAME: &str = "not";
+pub const CFG_ALL_NAME: &str = "all";
+pub const CFG_ANY_NAME: &str = "any";
+
 /// The list of valid attributes.
 pub const VALID_ATTRIBUTE_NAMES: &[&str] = &[
     STORAGE_PURITY_ATTRIBUTE_NAME,
@@ -57,4 +81,7 @@ pub const VALID_ATTRIBUTE_NAMES: &[&str] = &[
     TEST_ATTRIBUTE_NAME,
     INLINE_ATTRIBUTE_NAME,
     PAYABLE_ATTRIBUTE_NAME,
+    HOT_ATTRIBUTE_NAME,
+    FALLBACK_ATTRIBUTE_NAME,
+    CFG_ATTRIBUTE_NAME,
 ];
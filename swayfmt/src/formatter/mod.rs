@@ -1375,6 +1375,50 @@ fn foo() {}
 #[test]
 #[inline(always)]
 fn foo() {}
+"#;
+        let mut formatter = Formatter::default();
+        let formatted_sway_code =
+            Formatter::format(&mut formatter, Arc::from(sway_code_to_format), None).unwrap();
+        assert_eq!(correct_sway_code, formatted_sway_code);
+        assert!(test_stability(formatted_sway_code, formatter));
+    }
+
+    #[test]
+    fn test_attribute_args_with_values() {
+        let sway_code_to_format = r#"library my_lib;
+
+#[test(should_revert="18446744073709486080")]
+fn foo() {}
+
+#[test(should_revert   =   42,ignore)]
+fn bar() {}
+"#;
+        let correct_sway_code = r#"library my_lib;
+
+#[test(should_revert = "18446744073709486080")]
+fn foo() {}
+
+#[test(should_revert = 42, ignore)]
+fn bar() {}
+"#;
+        let mut formatter = Formatter::default();
+        let formatted_sway_code =
+            Formatter::format(&mut formatter, Arc::from(sway_code_to_format), None).unwrap();
+        assert_eq!(correct_sway_code, formatted_sway_code);
+        assert!(test_stability(formatted_sway_code, formatter));
+    }
+
+    #[test]
+    fn test_nested_attribute_args() {
+        let sway_code_to_format = r#"library my_lib;
+
+#[cfg( not( any(feature="logging",all( test , feature = "debug" )) ))]
+fn foo() {}
+"#;
+        let correct_sway_code = r#"library my_lib;
+
+#[cfg(not(any(feature = "logging", all(test, feature = "debug"))))]
+fn foo() {}
 "#;
         let mut formatter = Formatter::default();
         let formatted_sway_code =
@@ -7,7 +7,8 @@ use crate::{
 };
 use std::fmt::Write;
 use sway_ast::{
-    attribute::{Annotated, Attribute, AttributeDecl},
+    attribute::{Annotated, Attribute, AttributeArg, AttributeDecl},
+    keywords::Token,
     token::{Delimiter, PunctKind},
 };
 use sway_types::{constants::DOC_COMMENT_ATTRIBUTE_NAME, Spanned};
@@ -53,7 +54,11 @@ impl Format for AttributeDecl {
                 .as_ref()
                 .map(|args| args.inner.final_value_opt.as_ref())
             {
-                writeln!(formatted_code, "///{}", doc_comment.as_str().trim_end())?;
+                writeln!(
+                    formatted_code,
+                    "///{}",
+                    doc_comment.name.as_str().trim_end()
+                )?;
             }
             return Ok(());
         }
@@ -92,6 +97,29 @@ impl Format for AttributeDecl {
     }
 }
 
+impl Format for AttributeArg {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        // name e.g. `should_revert`
+        write!(formatted_code, "{}", self.name.span().as_str())?;
+        if let Some(args) = &self.args {
+            // nested args e.g. `(feature = "logging")`
+            AttributeDecl::open_parenthesis(formatted_code, formatter)?;
+            args.get().format(formatted_code, formatter)?;
+            AttributeDecl::close_parenthesis(formatted_code, formatter)?;
+        }
+        if let Some((eq_token, value)) = &self.value_opt {
+            // ` = ` followed by the value e.g. `"42"`
+            write!(formatted_code, " {} ", eq_token.ident().as_str())?;
+            value.format(formatted_code, formatter)?;
+        }
+        Ok(())
+    }
+}
+
 impl SquareBracket for AttributeDecl {
     fn open_square_bracket(
         line: &mut String,
@@ -141,3 +169,16 @@ impl LeafSpans for Attribute {
         collected_spans
     }
 }
+impl LeafSpans for AttributeArg {
+    fn leaf_spans(&self) -> Vec<ByteSpan> {
+        let mut collected_spans = vec![ByteSpan::from(self.name.span())];
+        if let Some(args) = &self.args {
+            collected_spans.append(&mut args.leaf_spans());
+        }
+        if let Some((eq_token, value)) = &self.value_opt {
+            collected_spans.push(ByteSpan::from(eq_token.span()));
+            collected_spans.append(&mut value.leaf_spans());
+        }
+        collected_spans
+    }
+}
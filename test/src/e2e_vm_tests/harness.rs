@@ -161,6 +161,8 @@ pub(crate) async fn compile_to_bytes(file_name: &str, run_config: &RunConfig) ->
             terse: false,
             ..Default::default()
         },
+        // Tests check the compiler's output, which is not reproduced for cached packages.
+        no_cache: true,
         ..Default::default()
     };
     let result = forc_pkg::build_with_options(build_opts);
@@ -199,9 +201,16 @@ pub(crate) async fn compile_and_run_unit_tests(
                 terse: !(capture_output || run_config.verbose),
                 ..Default::default()
             },
+            // Tests check the compiler's output, which is not reproduced for cached packages.
+            no_cache: true,
             ..Default::default()
         })?;
-        let tested = built_tests.run()?;
+        let tested = built_tests.run(
+            forc_test::TestRunnerCount::Auto,
+            &forc_test::TestFilter::default(),
+            false,
+            forc_test::FuzzConfig::default(),
+        )?;
 
         match tested {
             forc_test::Tested::Package(tested_pkg) => Ok(vec![*tested_pkg]),
@@ -0,0 +1,16 @@
+contract;
+
+abi MyContract {
+    fn id() -> b256;
+}
+
+impl MyContract for Contract {
+    fn id() -> b256 {
+        CONTRACT_ID
+    }
+}
+
+#[test]
+fn test_id() {
+    let _id: b256 = CONTRACT_ID;
+}
@@ -0,0 +1,4 @@
+library lib;
+
+#[fallback]
+fn lib_fallback() {}
@@ -0,0 +1,23 @@
+contract;
+
+dep lib;
+
+abi MyContract {
+    fn foo() -> u64;
+}
+
+impl MyContract for Contract {
+    fn foo() -> u64 {
+        1
+    }
+}
+
+#[fallback]
+fn fallback() -> u64 {
+    2
+}
+
+#[fallback]
+fn other_fallback(x: u64) -> u64 {
+    x
+}
@@ -0,0 +1,41 @@
+script;
+
+// Neither of these modules exist, so they must be removed before submodules are parsed.
+#[cfg(test)]
+dep test_helpers;
+#[cfg(feature = "b")]
+dep b_helpers;
+
+struct S {
+    x: u64,
+    #[cfg(feature = "a")]
+    y: u64,
+    #[cfg(feature = "b")]
+    z: u64,
+}
+
+#[cfg(feature = "a")]
+fn value() -> u64 {
+    let s = S { x: 40, y: 2 };
+    s.x + s.y
+}
+
+#[cfg(not(feature = "a"))]
+fn value() -> u64 {
+    0
+}
+
+#[cfg(any(feature = "b", test))]
+fn other() -> u64 {
+    does_not_exist()
+}
+
+#[cfg(not(any(feature = "b", test)))]
+fn other() -> u64 {
+    0
+}
+
+#[cfg(all(feature = "a", not(feature = "b")))]
+fn main() -> u64 {
+    value() + other()
+}
@@ -0,0 +1,26 @@
+script;
+
+trait Value {
+    fn value(self) -> u64;
+}
+
+struct Wrapper {
+    value: u64,
+}
+
+impl Value for Wrapper {
+    #[hot]
+    fn value(self) -> u64 {
+        self.value
+    }
+}
+
+#[hot]
+fn identity(value: u64) -> u64 {
+    value
+}
+
+fn main() -> u64 {
+    let wrapper = Wrapper { value: 42 };
+    identity(wrapper.value())
+}
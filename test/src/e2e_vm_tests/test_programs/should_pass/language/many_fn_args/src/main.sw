@@ -0,0 +1,29 @@
+script;
+
+// Functions with more args than there are args registers pass the surplus on the stack.
+
+fn weighted_sum(a: u64, b: u64, c: u64, d: u64, e: u64, f: u64, g: u64, h: u64, i: u64) -> u64 {
+    a * 1 + b * 2 + c * 3 + d * 4 + e * 5 + f * 6 + g * 7 + h * 8 + i * 9
+}
+
+#[inline(never)]
+fn reversed_weighted_sum(a: u64, b: u64, c: u64, d: u64, e: u64, f: u64, g: u64, h: u64, i: u64) -> u64 {
+    weighted_sum(i, h, g, f, e, d, c, b, a)
+}
+
+fn choose(a: u64, b: u64, c: u64, d: u64, e: u64, f: u64, g: u64, pick: bool, x: u64, y: u64) -> u64 {
+    if pick {
+        x - a
+    } else {
+        y + b + c + d + e + f + g
+    }
+}
+
+fn main() -> u64 {
+    let s1 = weighted_sum(1, 2, 3, 4, 5, 6, 7, 8, 9);
+    let s2 = reversed_weighted_sum(1, 2, 3, 4, 5, 6, 7, 8, 9);
+    let c1 = choose(1, 0, 0, 0, 0, 0, 0, true, s1, s2);
+    let c2 = choose(1, 0, 0, 0, 0, 0, 0, false, s1, s2);
+
+    s1 + s2 + c1 + c2
+}
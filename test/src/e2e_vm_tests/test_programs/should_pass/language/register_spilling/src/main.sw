@@ -0,0 +1,114 @@
+script;
+
+// Returns `x`, though is not inlined as it contains a loop.
+fn id(x: u64) -> u64 {
+    let mut i = 0;
+    while i < x {
+        i = i + 1;
+    }
+    i
+}
+
+// The result of each call is live until the tuple is constructed, requiring more registers than
+// are available, such that some must be spilled to the stack.
+fn sum_of_calls() -> u64 {
+    let t = (
+        id(1),
+        id(2),
+        id(3),
+        id(4),
+        id(5),
+        id(6),
+        id(7),
+        id(8),
+        id(9),
+        id(10),
+        id(11),
+        id(12),
+        id(13),
+        id(14),
+        id(15),
+        id(16),
+        id(17),
+        id(18),
+        id(19),
+        id(20),
+        id(21),
+        id(22),
+        id(23),
+        id(24),
+        id(25),
+        id(26),
+        id(27),
+        id(28),
+        id(29),
+        id(30),
+        id(31),
+        id(32),
+        id(33),
+        id(34),
+        id(35),
+        id(36),
+        id(37),
+        id(38),
+        id(39),
+        id(40),
+        id(41),
+        id(42),
+        id(43),
+        id(44),
+        id(45),
+    );
+    t.0
+        + t.1
+        + t.2
+        + t.3
+        + t.4
+        + t.5
+        + t.6
+        + t.7
+        + t.8
+        + t.9
+        + t.10
+        + t.11
+        + t.12
+        + t.13
+        + t.14
+        + t.15
+        + t.16
+        + t.17
+        + t.18
+        + t.19
+        + t.20
+        + t.21
+        + t.22
+        + t.23
+        + t.24
+        + t.25
+        + t.26
+        + t.27
+        + t.28
+        + t.29
+        + t.30
+        + t.31
+        + t.32
+        + t.33
+        + t.34
+        + t.35
+        + t.36
+        + t.37
+        + t.38
+        + t.39
+        + t.40
+        + t.41
+        + t.42
+        + t.43
+        + t.44
+}
+
+fn main() -> u64 {
+    // Call twice to ensure that the stack space reserved for spilled registers is freed.
+    let a = sum_of_calls();
+    let b = sum_of_calls();
+    a + b
+}
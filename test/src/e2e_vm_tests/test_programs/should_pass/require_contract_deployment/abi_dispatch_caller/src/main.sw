@@ -0,0 +1,105 @@
+script;
+
+use abi_dispatch_abi::AbiDispatch;
+use std::{assert::assert, registers::global_gas, revert::revert};
+
+const METHOD_COUNT: u64 = 40;
+const HOT_METHOD: u64 = 17;
+const CONTRACT_ID: b256 = 0x5d6de6308a980b9b3c6c7db06e4f4f4677f9cc30621559d98070ee1e987ec88b;
+
+// Compares the gas used to call each method of a contract with a large ABI.  Dispatching on the
+// function selector checks the hot method first and then binary searches the other 39 selectors,
+// so the hot method should be the cheapest to call and the rest should cost about the same.
+//
+// Any method other than the hot one first misses the hot selector, then makes 4 or 5 comparisons
+// (about log2(39 / 3)) to narrow the search to at most 3 selectors and finally up to 3 equality
+// checks.  So the cheapest of them costs 5 checks more than the hot method, while the spread
+// between the cheapest and the dearest is only 3 checks.  Comparing against each selector in turn
+// would instead spread the costs across 38 checks, with the cheapest only 1 check dearer than the
+// hot method.
+fn main() -> bool {
+    let hot_cost = call_cost(HOT_METHOD);
+
+    let mut min_cost = u64::max();
+    let mut max_cost = 0;
+    let mut index = 0;
+    while index < METHOD_COUNT {
+        if index != HOT_METHOD {
+            let cost = call_cost(index);
+            min_cost = min(min_cost, cost);
+            max_cost = max(max_cost, cost);
+        }
+        index += 1;
+    }
+
+    assert(hot_cost < min_cost);
+    assert(max_cost - min_cost < min_cost - hot_cost);
+
+    true
+}
+
+// Calls the method numbered `index`, returning the gas used by the call alone.
+fn call_cost(index: u64) -> u64 {
+    let dispatch = abi(AbiDispatch, CONTRACT_ID);
+    match index {
+        0 => cost_since(global_gas(), dispatch.method_00(), 0),
+        1 => cost_since(global_gas(), dispatch.method_01(), 1),
+        2 => cost_since(global_gas(), dispatch.method_02(), 2),
+        3 => cost_since(global_gas(), dispatch.method_03(), 3),
+        4 => cost_since(global_gas(), dispatch.method_04(), 4),
+        5 => cost_since(global_gas(), dispatch.method_05(), 5),
+        6 => cost_since(global_gas(), dispatch.method_06(), 6),
+        7 => cost_since(global_gas(), dispatch.method_07(), 7),
+        8 => cost_since(global_gas(), dispatch.method_08(), 8),
+        9 => cost_since(global_gas(), dispatch.method_09(), 9),
+        10 => cost_since(global_gas(), dispatch.method_10(), 10),
+        11 => cost_since(global_gas(), dispatch.method_11(), 11),
+        12 => cost_since(global_gas(), dispatch.method_12(), 12),
+        13 => cost_since(global_gas(), dispatch.method_13(), 13),
+        14 => cost_since(global_gas(), dispatch.method_14(), 14),
+        15 => cost_since(global_gas(), dispatch.method_15(), 15),
+        16 => cost_since(global_gas(), dispatch.method_16(), 16),
+        17 => cost_since(global_gas(), dispatch.method_17(), 17),
+        18 => cost_since(global_gas(), dispatch.method_18(), 18),
+        19 => cost_since(global_gas(), dispatch.method_19(), 19),
+        20 => cost_since(global_gas(), dispatch.method_20(), 20),
+        21 => cost_since(global_gas(), dispatch.method_21(), 21),
+        22 => cost_since(global_gas(), dispatch.method_22(), 22),
+        23 => cost_since(global_gas(), dispatch.method_23(), 23),
+        24 => cost_since(global_gas(), dispatch.method_24(), 24),
+        25 => cost_since(global_gas(), dispatch.method_25(), 25),
+        26 => cost_since(global_gas(), dispatch.method_26(), 26),
+        27 => cost_since(global_gas(), dispatch.method_27(), 27),
+        28 => cost_since(global_gas(), dispatch.method_28(), 28),
+        29 => cost_since(global_gas(), dispatch.method_29(), 29),
+        30 => cost_since(global_gas(), dispatch.method_30(), 30),
+        31 => cost_since(global_gas(), dispatch.method_31(), 31),
+        32 => cost_since(global_gas(), dispatch.method_32(), 32),
+        33 => cost_since(global_gas(), dispatch.method_33(), 33),
+        34 => cost_since(global_gas(), dispatch.method_34(), 34),
+        35 => cost_since(global_gas(), dispatch.method_35(), 35),
+        36 => cost_since(global_gas(), dispatch.method_36(), 36),
+        37 => cost_since(global_gas(), dispatch.method_37(), 37),
+        38 => cost_since(global_gas(), dispatch.method_38(), 38),
+        39 => cost_since(global_gas(), dispatch.method_39(), 39),
+        _ => {
+            revert(0);
+            0
+        },
+    }
+}
+
+// Returns the gas used since `gas` was read, checking the called method returned `expected`.
+fn cost_since(gas: u64, result: u64, expected: u64) -> u64 {
+    let cost = gas - global_gas();
+    assert(result == expected);
+    cost
+}
+
+fn min(a: u64, b: u64) -> u64 {
+    if a < b { a } else { b }
+}
+
+fn max(a: u64, b: u64) -> u64 {
+    if a > b { a } else { b }
+}
@@ -4,7 +4,7 @@ use abi_with_tuples::*;
 use std::assert::assert;
 
 fn main() -> bool {
-    let the_abi = abi(MyContract, 0xf403dd5c8c89bf7202bf25d48a381f9d1755b32cd128c3053ef60435a4999bd7);
+    let the_abi = abi(MyContract, 0x4daa3f0cd391aa729984cbdef824db36c458508358b1722ee5da53b83e2c8950);
 
     let param1 = (
         Person {
@@ -2,7 +2,7 @@ script;
 use basic_storage_abi::{Quad, BasicStorage};
 
 fn main() -> u64 {
-    let addr = abi(BasicStorage, 0xe7bb9ac0b381da8de65792ce994d6b8bdd83fa7a5dc4cc65bc45737c3e1c87ea);
+    let addr = abi(BasicStorage, 0x2ec1a3d4f04e4ee9ee27db76431e9198023fb1ad07e07323a0b3a4b7fbf85d99);
     let key = 0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff;
     let value = 4242;
 
@@ -0,0 +1,18 @@
+script;
+
+use fallback_abi::{Fallback, Unimplemented};
+use std::assert::assert;
+
+fn main() -> bool {
+    let contract_id = 0xaf98d56232b1eb4da4370e8eaf2a9b11db3845e11fa33cb269fac844eaac3260;
+
+    // The contract doesn't implement `double()`, so its fallback function is called instead.
+    let unimplemented = abi(Unimplemented, contract_id);
+    assert(unimplemented.double(21) == 42);
+    assert(unimplemented.double(50) == 100);
+
+    let fallback = abi(Fallback, contract_id);
+    assert(fallback.fallback_calls() == 2);
+
+    true
+}
@@ -6,7 +6,7 @@ fn main() -> bool {
     let zero = b256::min();
     let gas: u64 = u64::max();
     let amount: u64 = 11;
-    let other_contract_id = ContractId::from(0x78dc5cda1446f4ddbd1d2e1e688a3be0566caf5d2bde6525dc5b0da90660b9e2);
+    let other_contract_id = ContractId::from(0x580863895b8a5416b8915101c236d748afc67d50a044a897bf5c51616a61ce4a);
     let base_asset_id = BASE_ASSET_ID;
 
     let test_contract = abi(ContextTesting, other_contract_id.into());
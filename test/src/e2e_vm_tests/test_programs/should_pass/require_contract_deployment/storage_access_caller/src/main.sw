@@ -3,7 +3,7 @@ use storage_access_abi::*;
 use std::{assert::assert, hash::sha256, revert::revert};
 
 fn main() -> bool {
-    let contract_id = 0x65e80f51e95a0dbaf0b8cf5569306344b184d5da13160ee156a02c53ff6e20fd;
+    let contract_id = 0xa20fa144159ed80ff0d6ea168d73a13c6c485455e202008005981bfdfac4cdab;
     let caller = abi(StorageAccess, contract_id);
 
     // Test initializers
@@ -212,5 +212,47 @@ fn main() -> bool {
     // Can't compare strings right now so compare hashes instead
     assert(sha256(caller.get_string()) == sha256("fuelfuelfuelfuelfuelfuelfuelfuelfuelfuel"));
 
+    // Test arrays
+    let table = caller.get_table();
+    let mut i = 0;
+    while i < 16 {
+        assert(table[i] == i);
+        assert(caller.get_table_element(i) == i);
+        i += 1;
+    }
+    let ts = caller.get_ts();
+    assert(ts[0].x == 1);
+    assert(ts[0].z == 0x0000000000000000000000000000000000000000000000000000000000000003);
+    assert(ts[0].int32 == 6);
+    assert(ts[1].x == 7);
+    assert(ts[1].z == 0x0000000000000000000000000000000000000000000000000000000000000009);
+    assert(ts[1].boolean == false);
+    assert(ts[1].int32 == 12);
+
+    caller.set_table_element(3, 42);
+    caller.add_to_table_element(15, 100);
+    let table = caller.get_table();
+    assert(table[2] == 2);
+    assert(table[3] == 42);
+    assert(table[4] == 4);
+    assert(table[15] == 115);
+    assert(caller.get_table_element(3) == 42);
+
+    caller.set_table([16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31]);
+    let mut i = 0;
+    while i < 16 {
+        assert(caller.get_table_element(i) == i + 16);
+        i += 1;
+    }
+
+    caller.set_ts([t, t]);
+    caller.set_ts_element_dot_x(1, 99);
+    let ts = caller.get_ts();
+    assert(ts[0].x == t.x);
+    assert(ts[1].x == 99);
+    assert(ts[1].y == t.y);
+    assert(ts[1].z == t.z);
+    assert(ts[1].int32 == t.int32);
+
     true
 }
@@ -17,7 +17,7 @@ fn main() -> bool {
     let default_gas = 1_000_000_000_000;
 
     // the deployed fuel_coin Contract_Id:
-    let fuelcoin_id = ContractId::from(0x1721cddbad97ac6b28708abfd41194749229c658b8356ed79c5439640724f7d6);
+    let fuelcoin_id = ContractId::from(0x8d0d8fc0219b8879404cf1d11e03a72614251483a1e847bb8c74a942c7d241d8);
 
     // contract ID for sway/test/src/e2e_vm_tests/test_programs/should_pass/test_contracts/balance_test_contract/
     let balance_test_id = ContractId::from(0x8dad20e27b24939770b4c58237e7b4ffa1dd11c2d2788feb30914eb57ad85c4f);
@@ -0,0 +1,44 @@
+library abi_dispatch_abi;
+
+abi AbiDispatch {
+    fn method_00() -> u64;
+    fn method_01() -> u64;
+    fn method_02() -> u64;
+    fn method_03() -> u64;
+    fn method_04() -> u64;
+    fn method_05() -> u64;
+    fn method_06() -> u64;
+    fn method_07() -> u64;
+    fn method_08() -> u64;
+    fn method_09() -> u64;
+    fn method_10() -> u64;
+    fn method_11() -> u64;
+    fn method_12() -> u64;
+    fn method_13() -> u64;
+    fn method_14() -> u64;
+    fn method_15() -> u64;
+    fn method_16() -> u64;
+    fn method_17() -> u64;
+    fn method_18() -> u64;
+    fn method_19() -> u64;
+    fn method_20() -> u64;
+    fn method_21() -> u64;
+    fn method_22() -> u64;
+    fn method_23() -> u64;
+    fn method_24() -> u64;
+    fn method_25() -> u64;
+    fn method_26() -> u64;
+    fn method_27() -> u64;
+    fn method_28() -> u64;
+    fn method_29() -> u64;
+    fn method_30() -> u64;
+    fn method_31() -> u64;
+    fn method_32() -> u64;
+    fn method_33() -> u64;
+    fn method_34() -> u64;
+    fn method_35() -> u64;
+    fn method_36() -> u64;
+    fn method_37() -> u64;
+    fn method_38() -> u64;
+    fn method_39() -> u64;
+}
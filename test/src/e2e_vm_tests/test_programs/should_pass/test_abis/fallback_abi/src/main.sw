@@ -0,0 +1,12 @@
+library fallback_abi;
+
+// The ABI implemented by the contract.
+abi Fallback {
+    #[storage(read)]
+    fn fallback_calls() -> u64;
+}
+
+// An ABI which isn't implemented by the contract, so calling it will invoke the fallback function.
+abi Unimplemented {
+    fn double(x: u64) -> u64;
+}
@@ -64,6 +64,14 @@ abi StorageAccess {
     fn set_e(e: E);
     #[storage(write)]
     fn set_string(s: str[40]);
+    #[storage(write)]
+    fn set_table(table: [u64; 16]);
+    #[storage(read, write)]
+    fn set_table_element(index: u64, value: u64);
+    #[storage(write)]
+    fn set_ts(ts: [T; 2]);
+    #[storage(read, write)]
+    fn set_ts_element_dot_x(index: u64, x: u64);
 
     // Getters
     #[storage(read)]
@@ -108,6 +116,12 @@ abi StorageAccess {
     fn get_e2() -> E;
     #[storage(read)]
     fn get_string() -> str[40];
+    #[storage(read)]
+    fn get_table() -> [u64; 16];
+    #[storage(read)]
+    fn get_table_element(index: u64) -> u64;
+    #[storage(read)]
+    fn get_ts() -> [T; 2];
 
     // Operations
     #[storage(read, write)]
@@ -122,4 +136,6 @@ abi StorageAccess {
     fn shift_left_s_dot_t_dot_x(k: u64);
     #[storage(read, write)]
     fn shift_right_s_dot_t_dot_x(k: u64);
+    #[storage(read, write)]
+    fn add_to_table_element(index: u64, k: u64);
 }
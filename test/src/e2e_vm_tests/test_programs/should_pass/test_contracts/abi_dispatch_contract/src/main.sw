@@ -0,0 +1,166 @@
+contract;
+
+use abi_dispatch_abi::AbiDispatch;
+
+impl AbiDispatch for Contract {
+    fn method_00() -> u64 {
+        0
+    }
+
+    fn method_01() -> u64 {
+        1
+    }
+
+    fn method_02() -> u64 {
+        2
+    }
+
+    fn method_03() -> u64 {
+        3
+    }
+
+    fn method_04() -> u64 {
+        4
+    }
+
+    fn method_05() -> u64 {
+        5
+    }
+
+    fn method_06() -> u64 {
+        6
+    }
+
+    fn method_07() -> u64 {
+        7
+    }
+
+    fn method_08() -> u64 {
+        8
+    }
+
+    fn method_09() -> u64 {
+        9
+    }
+
+    fn method_10() -> u64 {
+        10
+    }
+
+    fn method_11() -> u64 {
+        11
+    }
+
+    fn method_12() -> u64 {
+        12
+    }
+
+    fn method_13() -> u64 {
+        13
+    }
+
+    fn method_14() -> u64 {
+        14
+    }
+
+    fn method_15() -> u64 {
+        15
+    }
+
+    fn method_16() -> u64 {
+        16
+    }
+
+    #[hot]
+    fn method_17() -> u64 {
+        17
+    }
+
+    fn method_18() -> u64 {
+        18
+    }
+
+    fn method_19() -> u64 {
+        19
+    }
+
+    fn method_20() -> u64 {
+        20
+    }
+
+    fn method_21() -> u64 {
+        21
+    }
+
+    fn method_22() -> u64 {
+        22
+    }
+
+    fn method_23() -> u64 {
+        23
+    }
+
+    fn method_24() -> u64 {
+        24
+    }
+
+    fn method_25() -> u64 {
+        25
+    }
+
+    fn method_26() -> u64 {
+        26
+    }
+
+    fn method_27() -> u64 {
+        27
+    }
+
+    fn method_28() -> u64 {
+        28
+    }
+
+    fn method_29() -> u64 {
+        29
+    }
+
+    fn method_30() -> u64 {
+        30
+    }
+
+    fn method_31() -> u64 {
+        31
+    }
+
+    fn method_32() -> u64 {
+        32
+    }
+
+    fn method_33() -> u64 {
+        33
+    }
+
+    fn method_34() -> u64 {
+        34
+    }
+
+    fn method_35() -> u64 {
+        35
+    }
+
+    fn method_36() -> u64 {
+        36
+    }
+
+    fn method_37() -> u64 {
+        37
+    }
+
+    fn method_38() -> u64 {
+        38
+    }
+
+    fn method_39() -> u64 {
+        39
+    }
+}
@@ -0,0 +1,24 @@
+contract;
+
+use fallback_abi::Fallback;
+use std::call_frames::second_param;
+
+storage {
+    fallback_calls: u64 = 0,
+}
+
+impl Fallback for Contract {
+    #[storage(read)]
+    fn fallback_calls() -> u64 {
+        storage.fallback_calls
+    }
+}
+
+// Called for any method other than `fallback_calls()`, which is expected to take a single `u64`
+// argument.
+#[fallback]
+#[storage(read, write)]
+fn fallback() -> u64 {
+    storage.fallback_calls += 1;
+    second_param::<u64>() * 2
+}
@@ -34,6 +34,27 @@ storage {
     },
     ), e2: E = E::A(777),
     string: str[40] = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+    table: [u64; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    ts: [T; 2] = [
+        T {
+            x: 1,
+            y: 2,
+            z: 0x0000000000000000000000000000000000000000000000000000000000000003,
+            boolean: true,
+            int8: 4,
+            int16: 5,
+            int32: 6,
+        },
+        T {
+            x: 7,
+            y: 8,
+            z: 0x0000000000000000000000000000000000000000000000000000000000000009,
+            boolean: false,
+            int8: 10,
+            int16: 11,
+            int32: 12,
+        },
+    ],
 }
 
 impl StorageAccess for Contract {
@@ -98,6 +119,18 @@ impl StorageAccess for Contract {
     #[storage(write)]fn set_string(string: str[40]) {
         storage.string = string;
     }
+    #[storage(write)]fn set_table(table: [u64; 16]) {
+        storage.table = table;
+    }
+    #[storage(read, write)]fn set_table_element(index: u64, value: u64) {
+        storage.table[index] = value;
+    }
+    #[storage(write)]fn set_ts(ts: [T; 2]) {
+        storage.ts = ts;
+    }
+    #[storage(read, write)]fn set_ts_element_dot_x(index: u64, x: u64) {
+        storage.ts[index].x = x;
+    }
 
     // Getters
     #[storage(read)]fn get_x() -> u64 {
@@ -163,6 +196,15 @@ impl StorageAccess for Contract {
     #[storage(read)]fn get_string() -> str[40] {
         storage.string
     }
+    #[storage(read)]fn get_table() -> [u64; 16] {
+        storage.table
+    }
+    #[storage(read)]fn get_table_element(index: u64) -> u64 {
+        storage.table[index]
+    }
+    #[storage(read)]fn get_ts() -> [T; 2] {
+        storage.ts
+    }
 
     // Operations
     #[storage(read, write)]fn add_to_s_dot_t_dot_x(k: u64) {
@@ -183,4 +225,7 @@ impl StorageAccess for Contract {
     #[storage(read, write)]fn shift_right_s_dot_t_dot_x(k: u64) {
         storage.s.t.x >>= k;
     }
+    #[storage(read, write)]fn add_to_table_element(index: u64, k: u64) {
+        storage.table[index] += k;
+    }
 }
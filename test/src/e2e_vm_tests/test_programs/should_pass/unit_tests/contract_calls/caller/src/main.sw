@@ -0,0 +1,31 @@
+contract;
+
+use counter_abi::Counter;
+
+abi Caller {
+    #[storage(read, write)]
+    fn increment_twice(amount: u64) -> u64;
+}
+
+impl Caller for Contract {
+    #[storage(read, write)]
+    fn increment_twice(amount: u64) -> u64 {
+        let counter = abi(Counter, counter::CONTRACT_ID);
+        counter.increment(amount);
+        counter.increment(amount)
+    }
+}
+
+#[test]
+fn test_call_contract_dependency() {
+    let counter = abi(Counter, counter::CONTRACT_ID);
+    assert(counter.increment(2) == 42);
+}
+
+#[test]
+fn test_call_through_contract() {
+    let caller = abi(Caller, CONTRACT_ID);
+    assert(caller.increment_twice(1) == 42);
+    let counter = abi(Counter, counter::CONTRACT_ID);
+    assert(counter.count() == 42);
+}
@@ -0,0 +1,41 @@
+contract;
+
+use counter_abi::Counter;
+
+storage {
+    count: u64 = 40,
+}
+
+impl Counter for Contract {
+    #[storage(read, write)]
+    fn increment(amount: u64) -> u64 {
+        let incremented = storage.count + amount;
+        storage.count = incremented;
+        incremented
+    }
+
+    #[storage(read)]
+    fn count() -> u64 {
+        storage.count
+    }
+}
+
+#[test]
+fn test_initial_storage() {
+    let counter = abi(Counter, CONTRACT_ID);
+    assert(counter.count() == 40);
+}
+
+#[test]
+fn test_increment() {
+    let counter = abi(Counter, CONTRACT_ID);
+    assert(counter.increment(1) == 41);
+    assert(counter.increment(1) == 42);
+    assert(counter.count() == 42);
+}
+
+#[test]
+fn test_storage_is_fresh_for_each_test() {
+    let counter = abi(Counter, CONTRACT_ID);
+    assert(counter.increment(2) == 42);
+}
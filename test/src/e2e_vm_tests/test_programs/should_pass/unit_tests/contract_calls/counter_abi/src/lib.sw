@@ -0,0 +1,8 @@
+library counter_abi;
+
+abi Counter {
+    #[storage(read, write)]
+    fn increment(amount: u64) -> u64;
+    #[storage(read)]
+    fn count() -> u64;
+}
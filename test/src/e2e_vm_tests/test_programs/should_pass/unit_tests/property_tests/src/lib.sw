@@ -0,0 +1,68 @@
+library property_tests;
+
+pub struct Point {
+    x: u64,
+    y: u64,
+}
+
+pub enum Shape {
+    Dot: Point,
+    Line: (Point, Point),
+}
+
+fn distance(a: u64, b: u64) -> u64 {
+    if a > b { a - b } else { b - a }
+}
+
+fn is_symmetric(a: Point, b: Point) -> bool {
+    distance(a.x, b.x) == distance(b.x, a.x) && distance(a.y, b.y) == distance(b.y, a.y)
+}
+
+#[test]
+fn test_max_is_commutative(a: u64, b: u64) {
+    let ab = if a > b { a } else { b };
+    let ba = if b > a { b } else { a };
+    assert(ab == ba);
+}
+
+#[test]
+fn test_small_ints(a: u8, b: u16, c: u32, flag: bool) {
+    assert(a / 2 <= a);
+    assert(b / 2 <= b);
+    assert(c / 2 <= c);
+    assert(flag || !flag);
+}
+
+#[test]
+fn test_distance_is_symmetric(a: Point, b: Point) {
+    assert(is_symmetric(a, b));
+}
+
+#[test]
+fn test_shapes(shape: Shape, id: b256) {
+    let _ = id;
+    match shape {
+        Shape::Dot(p) => assert(distance(p.x, p.x) == 0 && distance(p.y, p.y) == 0),
+        Shape::Line((a, b)) => assert(is_symmetric(a, b)),
+    }
+}
+
+#[test]
+fn test_array_max(values: [u32; 4]) {
+    let mut max = values[0];
+    let mut i = 1;
+    while i < 4 {
+        if values[i] > max {
+            max = values[i];
+        }
+        i += 1;
+    }
+    assert(max >= values[0] && max >= values[3]);
+}
+
+#[test(should_revert)]
+fn test_overflow_reverts(a: u64) {
+    // Any non-zero value overflows when added to `u64::max()`.
+    let sum = (a | 1) + 0xFFFFFFFFFFFFFFFF;
+    assert(sum > 0);
+}
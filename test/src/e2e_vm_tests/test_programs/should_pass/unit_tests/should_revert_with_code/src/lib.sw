@@ -0,0 +1,33 @@
+library should_revert_with_code;
+
+use std::revert::{require, revert};
+
+#[test(should_revert = "42")]
+fn should_revert_with_code_as_string() {
+    revert(42)
+}
+
+#[test(should_revert = 42)]
+fn should_revert_with_code_as_integer() {
+    revert(42)
+}
+
+#[test(should_revert = "0xffff_ffff_ffff_0000")]
+fn should_revert_with_code_as_hex_string() {
+    require(1 == 2, 0)
+}
+
+#[test(should_revert = "FAILED_REQUIRE_SIGNAL")]
+fn should_revert_with_failed_require_signal() {
+    require(1 == 2, 0)
+}
+
+#[test(should_revert = "std::error_signals::FAILED_REQUIRE_SIGNAL")]
+fn should_revert_with_failed_require_signal_path() {
+    require(1 == 2, 0)
+}
+
+#[test(should_revert = "0")]
+fn should_revert_with_failed_assert() {
+    assert(1 == 2)
+}
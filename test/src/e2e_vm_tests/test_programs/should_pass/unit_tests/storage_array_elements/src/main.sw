@@ -0,0 +1,228 @@
+contract;
+
+struct Pair {
+    a: u64,
+    b: u64,
+}
+
+struct Wide {
+    x: b256,
+    y: b256,
+}
+
+struct Odd {
+    a: u64,
+    b: u64,
+    c: u64,
+}
+
+struct Nested {
+    flag: bool,
+    words: [u64; 3],
+}
+
+storage {
+    words: [u64; 6] = [0, 1, 2, 3, 4, 5],
+    flags: [bool; 5] = [false, false, false, false, false],
+    hashes: [b256; 2] = [
+        0x0000000000000000000000000000000000000000000000000000000000000001,
+        0x0000000000000000000000000000000000000000000000000000000000000002,
+    ],
+    pairs: [Pair; 3] = [Pair { a: 1, b: 2 }, Pair { a: 3, b: 4 }, Pair { a: 5, b: 6 }],
+    wides: [Wide; 2] = [
+        Wide {
+            x: 0x0000000000000000000000000000000000000000000000000000000000000001,
+            y: 0x0000000000000000000000000000000000000000000000000000000000000002,
+        },
+        Wide {
+            x: 0x0000000000000000000000000000000000000000000000000000000000000003,
+            y: 0x0000000000000000000000000000000000000000000000000000000000000004,
+        },
+    ],
+    odds: [Odd; 2] = [Odd { a: 1, b: 2, c: 3 }, Odd { a: 4, b: 5, c: 6 }],
+    nested: Nested = Nested {
+        flag: true,
+        words: [7, 8, 9],
+    },
+}
+
+abi StorageArrayElements {
+    #[storage(read, write)]
+    fn set_word(index: u64, value: u64);
+    #[storage(read, write)]
+    fn add_to_word(index: u64, value: u64);
+    #[storage(read)]
+    fn word(index: u64) -> u64;
+    #[storage(read)]
+    fn words() -> [u64; 6];
+    #[storage(read, write)]
+    fn set_flag(index: u64, value: bool);
+    #[storage(read)]
+    fn flags() -> [bool; 5];
+    #[storage(write)]
+    fn set_hash(index: u64, value: b256);
+    #[storage(read)]
+    fn hash(index: u64) -> b256;
+    #[storage(read, write)]
+    fn set_pair_b(index: u64, value: u64);
+    #[storage(read)]
+    fn pair(index: u64) -> Pair;
+    #[storage(write)]
+    fn set_wide(index: u64, value: Wide);
+    #[storage(read)]
+    fn wide(index: u64) -> Wide;
+    #[storage(read, write)]
+    fn set_odd(index: u64, value: Odd);
+    #[storage(read)]
+    fn odd(index: u64) -> Odd;
+    #[storage(read, write)]
+    fn set_nested_word(index: u64, value: u64);
+    #[storage(read)]
+    fn nested_word(index: u64) -> u64;
+    #[storage(read)]
+    fn nested_flag() -> bool;
+}
+
+impl StorageArrayElements for Contract {
+    #[storage(read, write)]
+    fn set_word(index: u64, value: u64) {
+        storage.words[index] = value;
+    }
+    #[storage(read, write)]
+    fn add_to_word(index: u64, value: u64) {
+        storage.words[index] += value;
+    }
+    #[storage(read)]
+    fn word(index: u64) -> u64 {
+        storage.words[index]
+    }
+    #[storage(read)]
+    fn words() -> [u64; 6] {
+        storage.words
+    }
+    #[storage(read, write)]
+    fn set_flag(index: u64, value: bool) {
+        storage.flags[index] = value;
+    }
+    #[storage(read)]
+    fn flags() -> [bool; 5] {
+        storage.flags
+    }
+    #[storage(write)]
+    fn set_hash(index: u64, value: b256) {
+        storage.hashes[index] = value;
+    }
+    #[storage(read)]
+    fn hash(index: u64) -> b256 {
+        storage.hashes[index]
+    }
+    #[storage(read, write)]
+    fn set_pair_b(index: u64, value: u64) {
+        storage.pairs[index].b = value;
+    }
+    #[storage(read)]
+    fn pair(index: u64) -> Pair {
+        storage.pairs[index]
+    }
+    #[storage(write)]
+    fn set_wide(index: u64, value: Wide) {
+        storage.wides[index] = value;
+    }
+    #[storage(read)]
+    fn wide(index: u64) -> Wide {
+        storage.wides[index]
+    }
+    #[storage(read, write)]
+    fn set_odd(index: u64, value: Odd) {
+        storage.odds[index] = value;
+    }
+    #[storage(read)]
+    fn odd(index: u64) -> Odd {
+        storage.odds[index]
+    }
+    #[storage(read, write)]
+    fn set_nested_word(index: u64, value: u64) {
+        storage.nested.words[index] = value;
+    }
+    #[storage(read)]
+    fn nested_word(index: u64) -> u64 {
+        storage.nested.words[index]
+    }
+    #[storage(read)]
+    fn nested_flag() -> bool {
+        storage.nested.flag
+    }
+}
+
+#[test]
+fn test_words() {
+    let c = abi(StorageArrayElements, CONTRACT_ID);
+    assert(c.word(0) == 0 && c.word(3) == 3 && c.word(4) == 4 && c.word(5) == 5);
+    c.set_word(5, 50);
+    c.set_word(2, 20);
+    c.add_to_word(4, 36);
+    let words = c.words();
+    assert(words[0] == 0 && words[1] == 1 && words[2] == 20);
+    assert(words[3] == 3 && words[4] == 40 && words[5] == 50);
+    assert(c.word(4) == 40);
+}
+
+#[test]
+fn test_flags() {
+    let c = abi(StorageArrayElements, CONTRACT_ID);
+    c.set_flag(4, true);
+    c.set_flag(1, true);
+    let flags = c.flags();
+    assert(!flags[0] && flags[1] && !flags[2] && !flags[3] && flags[4]);
+}
+
+#[test]
+fn test_hashes() {
+    let c = abi(StorageArrayElements, CONTRACT_ID);
+    c.set_hash(1, 0x0000000000000000000000000000000000000000000000000000000000000022);
+    assert(c.hash(0) == 0x0000000000000000000000000000000000000000000000000000000000000001);
+    assert(c.hash(1) == 0x0000000000000000000000000000000000000000000000000000000000000022);
+}
+
+#[test]
+fn test_pairs() {
+    let c = abi(StorageArrayElements, CONTRACT_ID);
+    c.set_pair_b(1, 40);
+    let pair = c.pair(1);
+    assert(pair.a == 3 && pair.b == 40);
+    let pair = c.pair(2);
+    assert(pair.a == 5 && pair.b == 6);
+}
+
+#[test]
+fn test_wides() {
+    let c = abi(StorageArrayElements, CONTRACT_ID);
+    c.set_wide(1, Wide {
+        x: 0x0000000000000000000000000000000000000000000000000000000000000033,
+        y: 0x0000000000000000000000000000000000000000000000000000000000000044,
+    });
+    let wide = c.wide(0);
+    assert(wide.x == 0x0000000000000000000000000000000000000000000000000000000000000001);
+    assert(wide.y == 0x0000000000000000000000000000000000000000000000000000000000000002);
+    let wide = c.wide(1);
+    assert(wide.x == 0x0000000000000000000000000000000000000000000000000000000000000033);
+    assert(wide.y == 0x0000000000000000000000000000000000000000000000000000000000000044);
+}
+
+#[test]
+fn test_odds() {
+    let c = abi(StorageArrayElements, CONTRACT_ID);
+    c.set_odd(0, Odd { a: 10, b: 20, c: 30 });
+    let odd = c.odd(0);
+    assert(odd.a == 10 && odd.b == 20 && odd.c == 30);
+    let odd = c.odd(1);
+    assert(odd.a == 4 && odd.b == 5 && odd.c == 6);
+}
+
+#[test]
+fn test_nested() {
+    let c = abi(StorageArrayElements, CONTRACT_ID);
+    c.set_nested_word(2, 90);
+    assert(c.nested_word(0) == 7 && c.nested_word(1) == 8 && c.nested_word(2) == 90);
+    assert(c.nested_flag());
+}
@@ -0,0 +1,102 @@
+library test_environment;
+
+use std::{
+    block::{
+        height,
+        timestamp,
+        timestamp_of_block,
+    },
+    inputs::{
+        Input,
+        input_count,
+        input_type,
+    },
+    outputs::{
+        Output,
+        output_amount,
+        output_count,
+        output_type,
+    },
+    tx::{
+        tx_maturity,
+        tx_script_data,
+        tx_script_data_length,
+    },
+};
+
+const BASE_ASSET_ID = 0x0000000000000000000000000000000000000000000000000000000000000000;
+const OTHER_ASSET_ID = 0x0101010101010101010101010101010101010101010101010101010101010101;
+
+fn input_coin_amount(index: u64) -> u64 {
+    asm(amount, index: index) {
+        gtf amount index i261;
+        amount: u64
+    }
+}
+
+fn input_coin_asset_id(index: u64) -> b256 {
+    asm(asset_id, index: index) {
+        gtf asset_id index i262;
+        asset_id: b256
+    }
+}
+
+#[test]
+fn test_block() {
+    assert(height() == 42);
+    assert(timestamp() == 4611686020108779340);
+    assert(timestamp_of_block(41) == 4611686020108779330);
+}
+
+#[test]
+fn test_maturity() {
+    assert(tx_maturity() == 5u32);
+}
+
+#[test]
+fn test_script_data() {
+    assert(tx_script_data_length() == 8);
+    assert(tx_script_data::<u64>() == 1234);
+}
+
+#[test]
+fn test_inputs() {
+    assert(input_count() == 3u8);
+    match input_type(0) {
+        Input::Coin => {},
+        _ => revert(0),
+    }
+    assert(input_coin_amount(0) == 100);
+    assert(input_coin_asset_id(0) == BASE_ASSET_ID);
+    assert(input_coin_amount(1) == 7);
+    assert(input_coin_asset_id(1) == OTHER_ASSET_ID);
+    match input_type(2) {
+        Input::Message => {},
+        _ => revert(0),
+    }
+}
+
+#[test]
+fn test_outputs() {
+    assert(output_count() == 2);
+    match output_type(0) {
+        Output::Coin => {},
+        _ => revert(0),
+    }
+    assert(output_amount(0) == 10);
+    match output_type(1) {
+        Output::Change => {},
+        _ => revert(0),
+    }
+}
+
+#[test]
+fn test_overrides() {
+    assert(height() == 7);
+    assert(tx_maturity() == 3u32);
+    assert(input_count() == 1u8);
+    assert(input_coin_amount(0) == 3);
+    assert(output_count() == 0);
+    // Fields that are not overridden are those of the `[test]` section.
+    assert(tx_script_data::<u64>() == 1234);
+}
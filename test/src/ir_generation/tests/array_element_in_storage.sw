@@ -0,0 +1,93 @@
+contract;
+
+abi StorageAccess {
+    #[storage(read)]
+    fn get_word(i: u64) -> u64;
+    #[storage(write)]
+    fn set_hash(i: u64, h: b256);
+    #[storage(read, write)]
+    fn set_word(i: u64, w: u64);
+}
+
+storage {
+    words: [u64; 16] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    hashes: [b256; 3] = [
+        0x0000000000000000000000000000000000000000000000000000000000000000,
+        0x0000000000000000000000000000000000000000000000000000000000000000,
+        0x0000000000000000000000000000000000000000000000000000000000000000,
+    ],
+}
+
+impl StorageAccess for Contract {
+    #[storage(read)]
+    fn get_word(i: u64) -> u64 {
+        storage.words[i]
+    }
+
+    #[storage(write)]
+    fn set_hash(i: u64, h: b256) {
+        storage.hashes[i] = h;
+    }
+
+    #[storage(read, write)]
+    fn set_word(i: u64, w: u64) {
+        storage.words[i] = w;
+    }
+}
+
+// An element of `words` shares its slot with three others, so the slot holding it is found by
+// dividing the index by 4 and added to the low word of the key. Only that one slot is loaded.
+
+// check: fn get_word
+// check: local mut ptr { u64, u64, u64, u64 } $(key=$ID)
+// check: local mut ptr [u64; 4] $(val=$ID)
+// check: $(four=$VAL) = const u64 4
+// check: $(slot=$VAL) = div i, $four
+// check: $(first=$VAL) = mul $slot, $four
+// check: $(idx=$VAL) = sub i, $first
+// check: $(key_ptr=$VAL) = get_ptr mut ptr { u64, u64, u64, u64 } $key, ptr b256, 0
+// check: $(key_val=$VAL) = const b256 0xf383b0ce51358be57daa3b725fe44acdb2d880604e367199080b4379c41bb6ed
+// check: store $key_val, ptr $key_ptr
+// check: $(key_words=$VAL) = get_ptr mut ptr { u64, u64, u64, u64 } $key, ptr { u64, u64, u64, u64 }, 0
+// check: $(low=$VAL) = extract_value $key_words, { u64, u64, u64, u64 }, 3
+// check: $(new_low=$VAL) = add $low, $slot
+// check: insert_value $key_words, { u64, u64, u64, u64 }, $new_low, 3
+// check: $(val_b256=$VAL) = get_ptr mut ptr [u64; 4] $val, ptr b256, 0
+// check: $(one=$VAL) = const u64 1
+// check: state_load_quad_word ptr $val_b256, key ptr $key_ptr, $one
+// not: state_load_quad_word
+// check: $(val_ary=$VAL) = get_ptr mut ptr [u64; 4] $val, ptr [u64; 4], 0
+// check: $(elem=$VAL) = extract_element $val_ary, [u64; 4], $idx
+// check: ret u64 $elem
+
+// An element of `hashes` fills a whole slot, so the slot offset is the index itself and the slot
+// is stored without being loaded first.
+
+// check: fn set_hash
+// check: local mut ptr { u64, u64, u64, u64 } $(key=$ID)
+// check: local mut ptr [b256; 1] $(val=$ID)
+// not: div
+// check: $(key_ptr=$VAL) = get_ptr mut ptr { u64, u64, u64, u64 } $key, ptr b256, 0
+// check: $(key_words=$VAL) = get_ptr mut ptr { u64, u64, u64, u64 } $key, ptr { u64, u64, u64, u64 }, 0
+// check: $(low=$VAL) = extract_value $key_words, { u64, u64, u64, u64 }, 3
+// check: $(new_low=$VAL) = add $low, i
+// not: state_load_quad_word
+// check: $(val_ary=$VAL) = get_ptr mut ptr [b256; 1] $val, ptr [b256; 1], 0
+// check: insert_element $val_ary, [b256; 1], h, $VAL
+// check: $(val_b256=$VAL) = get_ptr mut ptr [b256; 1] $val, ptr b256, 0
+// check: state_store_quad_word ptr $val_b256, key ptr $key_ptr
+
+// Writing an element of `words` loads its slot, inserts the element and stores the slot back.
+
+// check: fn set_word
+// check: local mut ptr { u64, u64, u64, u64 } $(key=$ID)
+// check: local mut ptr [u64; 4] $(val=$ID)
+// check: $(key_ptr=$VAL) = get_ptr mut ptr { u64, u64, u64, u64 } $key, ptr b256, 0
+// check: $(val_b256=$VAL) = get_ptr mut ptr [u64; 4] $val, ptr b256, 0
+// check: $(one=$VAL) = const u64 1
+// check: state_load_quad_word ptr $val_b256, key ptr $key_ptr, $one
+// check: $(val_ary=$VAL) = get_ptr mut ptr [u64; 4] $val, ptr [u64; 4], 0
+// check: insert_element $val_ary, [u64; 4], w, $VAL
+// check: $(val_b256=$VAL) = get_ptr mut ptr [u64; 4] $val, ptr b256, 0
+// check: state_store_quad_word ptr $val_b256, key ptr $key_ptr, $one
+// not: state_store_quad_word
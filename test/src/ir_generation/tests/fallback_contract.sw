@@ -0,0 +1,45 @@
+contract;
+
+abi Test {
+    fn get_u64(val: u64) -> u64;
+}
+
+impl Test for Contract {
+    fn get_u64(val: u64) -> u64 {
+        val
+    }
+}
+
+#[fallback]
+fn fallback() -> u64 {
+    42
+}
+
+// ::check-ir::
+
+// check: contract {
+// check: fn get_u64<9890aef4>($ID $MD: u64) -> u64
+// check: entry fn fallback() -> u64, $(fallback_md=$MD)
+
+// check: $(fallback_attr_md=$MD) = fallback
+// check: $fallback_md = ($MD $fallback_attr_md)
+
+// ::check-asm::
+
+// regex: REG=\$r\d+
+
+// Get the called selector.
+// check: lw   $(sel_reg=$REG) $$fp i73
+
+// Check selector at data_1 2559618804 (0x9890aef4)
+// check: lw   $(data_1_reg=$REG) data_1
+// check: eq   $(eq_reg=$REG) $sel_reg $data_1_reg
+// check: jnzi $eq_reg
+
+// Jump to the fallback function on no match, rather than reverting.
+// nextln: ji
+// not: rvrt
+
+// check: .data:
+// check: data_0 .word 42
+// check: data_1 .word 2559618804
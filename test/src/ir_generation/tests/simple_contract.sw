@@ -39,15 +39,15 @@ impl Test for Contract {
 
 // regex: REG=\$r\d+
 
-// Get the called selector.
+// Get the called selector.  The selectors are compared in sorted order.
 // check: lw   $(sel_reg=$REG) $$fp i73
 
-// Check selector at data_2 2559618804 (0x9890aef4)
+// Check selector at data_2 1108491158 (0x42123b96)
 // check: lw   $(data_2_reg=$REG) data_2
 // check: eq   $(eq_reg=$REG) $sel_reg $data_2_reg
 // check: jnzi $eq_reg
 
-// Check selector at data_3 1108491158 (0x42123b96)
+// Check selector at data_3 2559618804 (0x9890aef4)
 // check: lw   $(data_3_reg=$REG) data_3
 // check: eq   $(eq_reg=$REG) $sel_reg $data_3_reg
 // check: jnzi $eq_reg
@@ -66,5 +66,5 @@ impl Test for Contract {
 
 // check: .data:
 // check: data_2 .word 1108491158
-// check: data_3 .word 4234334249
-// check: data_4 .word 2559618804
+// check: data_3 .word 2559618804
+// check: data_4 .word 4234334249